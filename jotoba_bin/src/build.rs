@@ -0,0 +1,128 @@
+use crate::{cli::BuildOptions, webserver};
+use config::Config;
+use resources::import::ImportSources;
+use std::{
+    error::Error,
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+/// Builds resources and/or indexes as requested by the CLI options. If both are requested the
+/// indexes get built from the freshly built resources
+pub fn build(options: BuildOptions, build_resources: bool, build_indexes: bool) {
+    let config = Config::new(None).expect("Config invalid");
+
+    let mut res_file = config.get_storage_data_path();
+    if build_resources {
+        match build_resources_file(&options, &config) {
+            Ok(file) => res_file = file,
+            Err(err) => fail(err),
+        }
+    }
+
+    if build_indexes {
+        // Output option only applies to resources if both get built
+        let output = options.output.as_deref().filter(|_| !build_resources);
+
+        if let Err(err) = build_index_files(&options, &config, &res_file, output) {
+            fail(err);
+        }
+    }
+
+    println!("Success");
+}
+
+fn fail<E: std::fmt::Display>(err: E) -> ! {
+    println!("Failed: {err}");
+    std::process::exit(1);
+}
+
+/// Builds resources from the raw dictionary files and writes them into the configured (or
+/// passed) storage data file. Returns the path of the written file
+fn build_resources_file(options: &BuildOptions, config: &Config) -> Result<String, Box<dyn Error>> {
+    let start = Instant::now();
+
+    let output = options
+        .output
+        .clone()
+        .unwrap_or_else(|| config.get_storage_data_path());
+
+    let base = match options.base {
+        Some(ref base) => {
+            println!("Loading base resources");
+            Some(resources::load_raw(base)?)
+        }
+        None => None,
+    };
+
+    let sources = ImportSources {
+        jmdict: PathBuf::from(options.jmdict.as_ref().ok_or("Missing JMdict")?),
+        jmnedict: options.jmnedict.as_ref().map(PathBuf::from),
+        kanjidic: options.kanjidic.as_ref().map(PathBuf::from),
+        jlpt: options.jlpt.as_ref().map(PathBuf::from),
+    };
+
+    println!("Building resources");
+    let storage = resources::import::build(&sources, base)?;
+
+    let missing = storage.missing_but_required();
+    if !missing.is_empty() {
+        println!("Warning: missing required features: {missing:?}");
+    }
+
+    println!(
+        "Writing {} words, {} names and {} kanji to {output}",
+        storage.words.count(),
+        storage.names.names.len(),
+        storage.kanji.literal_index.len(),
+    );
+    resources::store(BufWriter::new(File::create(&output)?), &storage)?;
+
+    println!("Building resources took {:?}", start.elapsed());
+    Ok(output)
+}
+
+/// Builds all indexes from the resources in `res_file` and writes them into `output` or the
+/// configured index folder. A rebuilt hashtag index gets written into `output` as well, if set
+fn build_index_files(
+    options: &BuildOptions,
+    config: &Config,
+    res_file: &str,
+    output: Option<&str>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let start = Instant::now();
+    let index_output = output.unwrap_or_else(|| config.get_indexes_source());
+
+    println!("Loading resources");
+    let storage = resources::load_raw(res_file).map_err(|e| e.to_string())?;
+    webserver::load_tokenizer(config);
+
+    println!("Building indexes");
+    let indexes = indexes::builder::build(&storage);
+
+    println!("Writing indexes to {index_output}");
+    indexes.write(index_output)?;
+
+    if let Some(ref hashtags) = options.hashtags {
+        println!("Rebuilding hashtag index");
+        let source = Path::new(config.get_suggestion_sources());
+        let mut suggestions = indexes::storage::suggestions::load_raw(source)?;
+        let tags = indexes::builder::hashtag::load_tags(hashtags)?;
+        suggestions.set_hashtags(indexes::builder::hashtag::build(tags));
+
+        let out_file = match output {
+            Some(output) => Path::new(output).join(source.file_name().unwrap_or_default()),
+            None => source.to_path_buf(),
+        };
+        println!("Writing suggestions to {}", out_file.display());
+        indexes::storage::suggestions::store(
+            BufWriter::new(File::create(out_file)?),
+            &suggestions,
+        )?;
+    }
+
+    println!("Building indexes took {:?}", start.elapsed());
+    Ok(())
+}
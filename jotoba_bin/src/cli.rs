@@ -1,6 +1,6 @@
 use std::process::exit;
 
-use argparse::{ArgumentParser, Print, StoreTrue};
+use argparse::{ArgumentParser, Print, StoreOption, StoreTrue};
 
 /// Command line arguments
 #[derive(Default)]
@@ -9,6 +9,23 @@ pub struct Options {
     pub start: bool,
     pub debug: bool,
     pub check_resources: bool,
+    pub build_resources: bool,
+    pub build_indexes: bool,
+    pub build: BuildOptions,
+}
+
+/// Source and output files for `--build-resources` and `--build-indexes`
+#[derive(Default)]
+pub struct BuildOptions {
+    pub jmdict: Option<String>,
+    pub jmnedict: Option<String>,
+    pub kanjidic: Option<String>,
+    pub jlpt: Option<String>,
+    /// Existing resources to take data from, which can't be built from the dictionaries
+    pub base: Option<String>,
+    /// Hashtag list to rebuild the hashtag suggestion index from
+    pub hashtags: Option<String>,
+    pub output: Option<String>,
 }
 
 // Parse CLI args
@@ -36,6 +53,60 @@ pub fn parse() -> Options {
             "Check resources",
         );
 
+        ap.refer(&mut options.build_resources).add_option(
+            &["--build-resources"],
+            StoreTrue,
+            "Build resources from the JMdict/JMnedict/KANJIDIC XML files",
+        );
+
+        ap.refer(&mut options.build_indexes).add_option(
+            &["--build-indexes"],
+            StoreTrue,
+            "Build search indexes from the configured resources",
+        );
+
+        ap.refer(&mut options.build.jmdict).add_option(
+            &["--jmdict"],
+            StoreOption,
+            "JMdict XML file",
+        );
+
+        ap.refer(&mut options.build.jmnedict).add_option(
+            &["--jmnedict"],
+            StoreOption,
+            "JMnedict XML file",
+        );
+
+        ap.refer(&mut options.build.kanjidic).add_option(
+            &["--kanjidic"],
+            StoreOption,
+            "KANJIDIC2 XML file",
+        );
+
+        ap.refer(&mut options.build.jlpt).add_option(
+            &["--jlpt"],
+            StoreOption,
+            "JLPT mapping file with tab separated sequence ids and levels",
+        );
+
+        ap.refer(&mut options.build.base).add_option(
+            &["--base"],
+            StoreOption,
+            "Existing resources to take additional data from",
+        );
+
+        ap.refer(&mut options.build.hashtags).add_option(
+            &["--hashtags"],
+            StoreOption,
+            "Hashtag list to rebuild the hashtag suggestions from",
+        );
+
+        ap.refer(&mut options.build.output).add_option(
+            &["--output", "-o"],
+            StoreOption,
+            "Output file of the built resources or output folder of the built indexes and hashtags",
+        );
+
         ap.parse_args_or_exit();
     }
 
@@ -44,5 +115,20 @@ pub fn parse() -> Options {
         exit(1);
     }
 
+    if options.build_resources && (options.start || options.check_resources) {
+        println!("Can't use build_resources with start or check_resources");
+        exit(1);
+    }
+
+    if options.build_indexes && (options.start || options.check_resources) {
+        println!("Can't use build_indexes with start or check_resources");
+        exit(1);
+    }
+
+    if options.build_resources && options.build.jmdict.is_none() {
+        println!("Missing --jmdict");
+        exit(1);
+    }
+
     options
 }
@@ -4,8 +4,10 @@
 #[global_allocator]
 static ALLOC: snmalloc_rs::SnMalloc = snmalloc_rs::SnMalloc;
 
+mod build;
 mod check;
 mod cli;
+mod pin;
 mod webserver;
 
 #[actix_web::main]
@@ -18,6 +20,16 @@ pub async fn main() {
         return;
     }
 
+    // Build resources/indexes on --build-resources/--build-indexes
+    if options.build_resources || options.build_indexes {
+        build::build(
+            options.build,
+            options.build_resources,
+            options.build_indexes,
+        );
+        return;
+    }
+
     // Start the webserver on --stat/-s
     if options.start {
         webserver::start(options).await.expect("webserver failed");
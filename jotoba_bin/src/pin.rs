@@ -0,0 +1,62 @@
+//! Middleware keeping resources and indexes from being reloaded while a request uses them
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error, HttpMessage,
+};
+use std::{
+    future::{ready, Future, Ready},
+    pin::Pin,
+    rc::Rc,
+};
+use utils::swap_cell;
+
+/// Pins the loaded data for each request. Handlers running work on other threads get a clone of
+/// the pin by extracting `web::ReqData<ReadPin>` and have to move it into that work
+pub struct PinData;
+
+impl<S, B> Transform<S, ServiceRequest> for PinData
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = PinDataMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(PinDataMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct PinDataMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for PinDataMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        Box::pin(async move {
+            // Waits for a running reload without blocking the worker thread
+            let pin = swap_cell::pin_async().await;
+            req.extensions_mut().insert(pin.clone());
+            let res = service.call(req).await;
+            drop(pin);
+            res
+        })
+    }
+}
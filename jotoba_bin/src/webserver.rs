@@ -5,7 +5,7 @@ use indexes::storage::suggestions;
 use localization::TranslationDict;
 
 use actix_web::{
-    dev::ServiceRequest,
+    dev::{Service, ServiceRequest},
     http::{
         header::{ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_ORIGIN, CACHE_CONTROL},
         StatusCode,
@@ -17,8 +17,9 @@ use actix_web::{
 use config::Config;
 use log::{debug, warn};
 use std::{path::Path, sync::Arc, thread, time::Instant};
+use types::jotoba::search::SearchTarget;
 
-use crate::{check, cli::Options};
+use crate::{check, cli::Options, pin::PinData};
 
 /// How long frontend assets are going to be cached by the clients. Currently 1 week
 const ASSET_CACHE_MAX_AGE: u64 = 604800;
@@ -65,6 +66,21 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
             .app_data(Data::new(locale_dict_arc.clone()))
             // Middlewares
             .wrap(middleware::Logger::default())
+            // Keeps resources and indexes from being reloaded while a request uses them
+            .wrap(PinData)
+            .wrap_fn(|req, srv| {
+                let start = Instant::now();
+                let fut = srv.call(req);
+                async move {
+                    let res = fut.await?;
+                    let route = res.request().match_pattern();
+                    let route = route.as_deref().unwrap_or("unmatched");
+                    let method = res.request().method().as_str();
+                    let status = res.status().as_u16();
+                    metrics::observe_request(route, method, status, start.elapsed());
+                    Ok(res)
+                }
+            })
             .service(
                 actixweb::resource("/")
                     .wrap(Compat::new(middleware::Compress::default()))
@@ -162,6 +178,10 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
                                 "words",
                                 actixweb::post().to(api::app::search::words::search),
                             )
+                            .route(
+                                "furigana",
+                                actixweb::post().to(api::app::furigana::furigana),
+                            )
                             .service(
                                 actixweb::scope("details")
                                     .route(
@@ -178,11 +198,23 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
                     .service(
                         actixweb::scope("search")
                             .route("words", actixweb::post().to(api::search::word::word_search))
+                            .route(
+                                "words/stream",
+                                actixweb::post().to(api::search::word::word_search_stream),
+                            )
+                            .route(
+                                "words/batch",
+                                actixweb::post().to(api::search::word::batch::word_search_batch),
+                            )
                             .route(
                                 "kanji",
                                 actixweb::post().to(api::search::kanji::kanji_search),
                             )
                             .route("names", actixweb::post().to(api::search::name::name_search))
+                            .route(
+                                "pitch",
+                                actixweb::post().to(api::search::pitch::pitch_search),
+                            )
                             .route(
                                 "sentences",
                                 actixweb::post().to(api::search::sentence::sentence_search),
@@ -194,7 +226,13 @@ pub(super) async fn start(options: Options) -> std::io::Result<()> {
                             .service(actixweb::scope("info").route(
                                 "words",
                                 actixweb::post().to(api::internal::info::words::word_info),
-                            )),
+                            ))
+                            .service(actixweb::scope("explain").route(
+                                "words",
+                                actixweb::post().to(api::internal::explain::explain_words),
+                            ))
+                            .route("reload", actixweb::post().to(api::internal::reload::reload))
+                            .route("metrics", actixweb::get().to(metrics_ep)),
                     )
                     .service(
                         actixweb::scope("kanji")
@@ -312,9 +350,40 @@ async fn docs(config: Data<Config>, _req: HttpRequest) -> actix_web::Result<Name
 }
 
 pub(crate) fn prepare_data(ccf: &Config) {
+    if ccf.is_query_reporting_enabled() {
+        let timeout = ccf.get_query_report_timeout();
+        if let Err(err) = search::report::init(ccf.get_slow_query_log(), timeout) {
+            warn!("Failed to open slow query log: {}", err);
+        }
+    }
+
+    search::cache::init(ccf.get_cache_size(), ccf.get_cache_ttl());
+
+    if let Some(profiles) = ccf.get_ranking_profiles() {
+        match search::ranking::load(profiles) {
+            Ok(count) => debug!("Loaded {count} ranking profiles"),
+            Err(err) => warn!("Failed to load ranking profiles: {}", err),
+        }
+    }
+
+    let timeouts = ccf.get_search_timeouts();
+    let budgets = [
+        (SearchTarget::Words, timeouts.words()),
+        (SearchTarget::Names, timeouts.names()),
+        (SearchTarget::Sentences, timeouts.sentences()),
+        (SearchTarget::Kanji, timeouts.kanji()),
+    ];
+    for (target, budget) in budgets {
+        if let Some(budget) = budget {
+            search::executor::budget::set(target, budget);
+        }
+    }
+
     let cf = ccf.clone();
     thread::spawn(move || {
-        suggestions::load(cf.get_suggestion_sources()).expect("Failed to load suggestions");
+        timed("suggestions", || {
+            suggestions::load(cf.get_suggestion_sources()).expect("Failed to load suggestions");
+        });
         log::debug!("Suggestions loaded");
     });
 
@@ -322,19 +391,19 @@ pub(crate) fn prepare_data(ccf: &Config) {
         let cf = ccf.clone();
         s.spawn(move |_| {
             log::debug!("Loading Resources");
-            load_resources(&cf.get_storage_data_path());
+            timed("resources", || load_resources(&cf));
         });
 
         let cf = ccf.clone();
         s.spawn(move |_| {
             log::debug!("Loading Indexes");
-            load_indexes(&cf);
+            timed("indexes", || load_indexes(&cf));
         });
 
         let cf = ccf.clone();
         s.spawn(move |_| {
             log::debug!("Loading tokenizer");
-            load_tokenizer(&cf);
+            timed("tokenizer", || load_tokenizer(&cf));
         });
 
         let cf = ccf.clone();
@@ -343,19 +412,41 @@ pub(crate) fn prepare_data(ccf: &Config) {
         let cf = ccf.clone();
         s.spawn(move |_| {
             log::debug!("Loading News");
-            if let Err(err) = news::News::init(cf.server.get_news_folder()) {
-                warn!("Failed to load news: {}", err);
-            }
+            timed("news", || {
+                if let Err(err) = news::News::init(cf.server.get_news_folder()) {
+                    warn!("Failed to load news: {}", err);
+                }
+            });
         });
     });
 }
 
+/// Runs `load` and exposes its duration as load time of `component` in the metrics
+fn timed<F: FnOnce()>(component: &str, load: F) {
+    let start = Instant::now();
+    load();
+    metrics::set_load_duration(component, start.elapsed());
+}
+
+/// Exposes all metrics in the prometheus text format. Requires the internal API key
+async fn metrics_ep() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(metrics::content_type())
+        .body(metrics::encode())
+}
+
 fn setup_logger() {
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("debug"));
 }
 
 pub fn load_tokenizer(config: &Config) {
     sentence_reader::load_parser(&config.get_unidic_dict());
+
+    if let Some(grammar) = config.get_sentence_reader_grammar() {
+        if let Err(err) = sentence_reader::load_grammar(grammar) {
+            warn!("Failed to load sentence reader grammar: {}", err);
+        }
+    }
 }
 
 /// Clears uploaded images which haven't been cleared yet
@@ -374,9 +465,12 @@ fn debug_info() {
     log::debug!("Not supported: {:?}", resources::get().missing_features());
 }
 
-pub fn load_resources(src: &str) {
+pub fn load_resources(config: &Config) {
     let start = Instant::now();
-    resources::load(src).expect("Failed to load resource storage");
+    let (storage, custom) =
+        api::internal::reload::load_resources(config).expect("Failed to load resource storage");
+    resources::set(storage);
+    indexes::custom::set(custom);
     debug!("Resources took: {:?}", start.elapsed());
 }
 
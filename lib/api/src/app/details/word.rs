@@ -43,6 +43,7 @@ impl<'a> Details<'a> {
         let transitivity_pair = self.transitivity_pair();
         let collocations = self.get_collocations(config);
         let inflection_table = self.word.get_inflections();
+        let conjugation_table = self.word.get_conjugation_table();
 
         let word = self.get_word(config);
 
@@ -50,6 +51,7 @@ impl<'a> Details<'a> {
             word,
             kanji,
             inflection_table,
+            conjugation_table,
             collocations,
             has_sentence,
             transitivity_pair,
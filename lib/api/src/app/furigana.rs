@@ -0,0 +1,189 @@
+use super::Result;
+use actix_web::web::{self, Json, ReqData};
+use error::api_error::{Origin, RestError};
+use jp_utils::furi::{
+    segment::{kanji::as_kanji::AsKanjiSegment, AsSegment},
+    Furigana,
+};
+use search::word::producer::japanese::sentence_reader::set_part_furigana;
+use types::api::app::furigana::{Request, Response, Segment};
+use utils::real_string_len;
+use utils::swap_cell::ReadPin;
+
+/// Max amount of characters to annotate in a single request
+const MAX_TEXT_LEN: usize = 1000;
+
+/// Annotates the given text with furigana
+pub async fn furigana(payload: Json<Request>, pin: ReqData<ReadPin>) -> Result<Json<Response>> {
+    let len = real_string_len(&payload.text);
+    if payload.text.trim().is_empty() || len > MAX_TEXT_LEN {
+        return Err(RestError::BadRequest);
+    }
+
+    // Parsing the text panics without a loaded tokenizer
+    if !sentence_reader::is_loaded() {
+        return Err(RestError::Missing(Origin::Tokenizer));
+    }
+
+    let payload = payload.into_inner();
+    let segments = web::block(move || {
+        let _pin = pin;
+        annotate(&payload)
+    })
+    .await?;
+    let html = to_html(&segments);
+    Ok(Json(Response::new(segments, html)))
+}
+
+/// Splits the text into segments with the readings of all words taken from their dictionary entry
+fn annotate(payload: &Request) -> Vec<Segment> {
+    let mut out = vec![];
+
+    // The parser might drop parts of the text like whitespaces, so we keep track of the text
+    // not yet covered by a word and add everything skipped as plain text
+    let mut rest = payload.text.as_str();
+
+    for mut part in sentence_reader::Parser::new(&payload.text).parts() {
+        let surface = part.get_inflected();
+        let Some(start) = rest.find(surface.as_str()) else {
+            continue;
+        };
+
+        push_plain(&mut out, &rest[..start]);
+        rest = &rest[start + surface.len()..];
+
+        set_part_furigana(&mut part);
+        match part.furigana() {
+            Some(furi) => push_furigana(&mut out, furi, payload),
+            None => push_plain(&mut out, &surface),
+        }
+    }
+
+    push_plain(&mut out, rest);
+    out
+}
+
+/// Adds all segments of the encoded furigana `furi`
+fn push_furigana(out: &mut Vec<Segment>, furi: &str, payload: &Request) {
+    for segment in Furigana(furi).segments() {
+        let reading = segment.get_kana_reading();
+
+        let Some(kanji) = segment.as_kanji() else {
+            push_plain(out, &reading);
+            continue;
+        };
+
+        let literals = kanji.literals().to_string();
+        if literals.chars().all(|i| is_known(i, payload)) {
+            push_plain(out, &literals);
+            continue;
+        }
+
+        out.push(Segment::new(literals, Some(reading.to_string())));
+    }
+}
+
+/// Adds `text` as segment without reading. Merges it into the previous segment if that one has no
+/// reading either
+fn push_plain(out: &mut Vec<Segment>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+
+    if let Some(last) = out.last_mut().filter(|i| i.reading.is_none()) {
+        last.text.push_str(text);
+        return;
+    }
+
+    out.push(Segment::plain(text.to_string()));
+}
+
+/// Returns `true` if the user opted out of furigana for `literal`
+fn is_known(literal: char, payload: &Request) -> bool {
+    let Some(kanji) = resources::get().kanji().by_literal(literal) else {
+        return false;
+    };
+
+    let by_jlpt = payload
+        .known_jlpt
+        .zip(kanji.jlpt)
+        .map_or(false, |(known, jlpt)| jlpt >= known);
+
+    let by_grade = payload
+        .known_grade
+        .zip(kanji.grade)
+        .map_or(false, |(known, grade)| grade <= known);
+
+    by_jlpt || by_grade
+}
+
+/// Renders the segments as HTML using ruby annotations for readings
+fn to_html(segments: &[Segment]) -> String {
+    let mut out = String::new();
+
+    for segment in segments {
+        match &segment.reading {
+            Some(reading) => {
+                out.push_str("<ruby>");
+                out.push_str(&escape_html(&segment.text));
+                out.push_str("<rt>");
+                out.push_str(&escape_html(reading));
+                out.push_str("</rt></ruby>");
+            }
+            None => out.push_str(&escape_html(&segment.text)),
+        }
+    }
+
+    out
+}
+
+fn escape_html(inp: &str) -> String {
+    let mut out = String::with_capacity(inp.len());
+    for c in inp.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_push_plain_merges() {
+        let mut out = vec![];
+        push_plain(&mut out, "これは");
+        push_plain(&mut out, "");
+        push_plain(&mut out, "ペン");
+        out.push(Segment::new("本".to_string(), Some("ほん".to_string())));
+        push_plain(&mut out, "です");
+
+        assert_eq!(
+            out,
+            vec![
+                Segment::plain("これはペン".to_string()),
+                Segment::new("本".to_string(), Some("ほん".to_string())),
+                Segment::plain("です".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_html() {
+        let segments = vec![
+            Segment::new("日本".to_string(), Some("にほん".to_string())),
+            Segment::plain("<b>".to_string()),
+        ];
+        assert_eq!(
+            to_html(&segments),
+            "<ruby>日本<rt>にほん</rt></ruby>&lt;b&gt;"
+        );
+    }
+}
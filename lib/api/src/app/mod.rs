@@ -1,5 +1,6 @@
 pub mod completions;
 pub mod details;
+pub mod furigana;
 pub mod img;
 pub mod kanji;
 pub mod news;
@@ -2,7 +2,7 @@ use super::new_page;
 
 use super::convert_payload;
 use crate::app::Result;
-use actix_web::web::{self, Json};
+use actix_web::web::{self, Json, ReqData};
 use error::api_error::RestError;
 
 use types::jotoba::language::param::AsLangParam;
@@ -19,18 +19,26 @@ use types::{
         words::{filter_languages, Word},
     },
 };
+use utils::swap_cell::ReadPin;
 
 /// API response type
 pub type SearchResp = Response<kanji::KanjiResponse>;
 
 /// Do an app kanji search via API
-pub async fn search(payload: Json<SearchPayload>) -> Result<Json<SearchResp>> {
+pub async fn search(
+    payload: Json<SearchPayload>,
+    pin: ReqData<ReadPin>,
+) -> Result<Json<SearchResp>> {
     let query = convert_payload(&payload)
         .parse()
         .ok_or(RestError::BadRequest)?;
 
     let query_c = query.clone();
-    let result = web::block(move || search::kanji::search(&query_c)).await??;
+    let result = web::block(move || {
+        let _pin = pin;
+        search::kanji::search(&query_c)
+    })
+    .await??;
 
     let items = result
         .items
@@ -2,7 +2,7 @@ use super::new_page;
 
 use super::convert_payload;
 use crate::app::Result;
-use actix_web::web::{self, Json};
+use actix_web::web::{self, Json, ReqData};
 use error::api_error::RestError;
 use search::SearchExecutor;
 use types::{
@@ -12,20 +12,22 @@ use types::{
     },
     jotoba::search::SearchTarget,
 };
+use utils::swap_cell::ReadPin;
 
 /// API response type
 pub type Resp = Response<names::Response>;
 
 /// Do an app name search via API
-pub async fn search(payload: Json<SearchPayload>) -> Result<Json<Resp>> {
+pub async fn search(payload: Json<SearchPayload>, pin: ReqData<ReadPin>) -> Result<Json<Resp>> {
     let query = convert_payload(&payload)
         .parse()
         .ok_or(RestError::BadRequest)?;
 
     let query_c = query.clone();
     let result = web::block(move || {
+        let _pin = pin;
         let search = search::name::Search::new(&query_c);
-        SearchExecutor::new(search).run()
+        SearchExecutor::new(search).run_cached()
     })
     .await?;
     let res = names::Response::new(result.items.into_iter().cloned().collect());
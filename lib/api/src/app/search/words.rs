@@ -3,7 +3,7 @@ use super::new_page;
 use super::convert_payload;
 use crate::app::Result;
 use actix_web::web::Data;
-use actix_web::web::{self, Json};
+use actix_web::web::{self, Json, ReqData};
 use config::Config;
 use error::api_error::RestError;
 use search::{word::Search, SearchExecutor};
@@ -17,12 +17,17 @@ use types::{
     },
     jotoba::search::SearchTarget,
 };
+use utils::swap_cell::ReadPin;
 
 /// API response type
 pub type Resp = Response<words::Response>;
 
 /// Do an app word search via API
-pub async fn search(payload: Json<SearchPayload>, config: Data<Config>) -> Result<Json<Resp>> {
+pub async fn search(
+    payload: Json<SearchPayload>,
+    config: Data<Config>,
+    pin: ReqData<ReadPin>,
+) -> Result<Json<Resp>> {
     let query = convert_payload(&payload)
         .parse()
         .ok_or(RestError::BadRequest)?;
@@ -30,8 +35,9 @@ pub async fn search(payload: Json<SearchPayload>, config: Data<Config>) -> Resul
 
     let query_c = query.clone();
     let result = web::block(move || {
+        let _pin = pin;
         let search = Search::new(&query_c);
-        SearchExecutor::new(search).run()
+        SearchExecutor::new(search).run_cached()
     })
     .await?;
 
@@ -0,0 +1,68 @@
+use actix_web::web::{self, Data, Json, ReqData};
+use config::Config;
+use engine::relevance::explain;
+use search::{executor::search_result::ItemExplanation, word::Search, SearchExecutor};
+use types::{
+    api::{
+        internal::explain::{ExplainedWord, Factor, FactorOp, Response},
+        search::SearchRequest,
+    },
+    jotoba::{search::SearchTarget, words::Word},
+};
+use utils::swap_cell::ReadPin;
+
+use crate::search::{parse_query, Result};
+
+/// Runs a word search and explains how each result was found and scored
+pub async fn explain_words(
+    payload: Json<SearchRequest>,
+    config: Data<Config>,
+    pin: ReqData<ReadPin>,
+) -> Result<Json<Response>> {
+    let query = parse_query(payload, SearchTarget::Words, &config)?;
+    let result = web::block(move || {
+        let _pin = pin;
+        SearchExecutor::new(Search::new(&query)).run_explained()
+    })
+    .await?;
+
+    let words = result
+        .items
+        .iter()
+        .zip(result.explanations.iter())
+        .map(|(word, explanation)| explain_word(word, explanation))
+        .collect();
+
+    Ok(Json(Response { words }))
+}
+
+fn explain_word(word: &Word, explanation: &ItemExplanation) -> ExplainedWord {
+    let score = explanation.score.as_ref();
+
+    let factors = score
+        .map(|i| i.factors.iter().map(convert_factor).collect())
+        .unwrap_or_default();
+
+    ExplainedWord {
+        sequence: word.sequence,
+        reading: word.get_reading().reading.clone(),
+        producer: explanation.producer.clone(),
+        relevance: explanation.relevance,
+        index_relevance: score.and_then(|i| i.index_relevance),
+        factors,
+    }
+}
+
+#[inline]
+fn convert_factor(factor: &explain::Factor) -> Factor {
+    let op = match factor.op {
+        explain::FactorOp::Multiply => FactorOp::Multiply,
+        explain::FactorOp::Replace => FactorOp::Replace,
+    };
+
+    Factor {
+        name: factor.name.to_string(),
+        value: factor.value,
+        op,
+    }
+}
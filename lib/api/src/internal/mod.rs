@@ -1 +1,3 @@
+pub mod explain;
 pub mod info;
+pub mod reload;
@@ -0,0 +1,101 @@
+use actix_web::{web::Data, HttpResponse};
+use config::Config;
+use error::api_error::RestError;
+use indexes::custom::CustomIndex;
+use log::{debug, error, info};
+use resources::ResourceStorage;
+use std::{
+    error::Error,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Instant,
+};
+use types::api::internal::reload::{ReloadStatus, Response};
+use utils::swap_cell;
+
+/// `true` while a reload is running
+static RELOADING: AtomicBool = AtomicBool::new(false);
+
+/// Resets [`RELOADING`] once dropped, even if the reload panicked
+struct ReloadGuard;
+
+impl Drop for ReloadGuard {
+    fn drop(&mut self) {
+        RELOADING.store(false, Ordering::Release);
+    }
+}
+
+/// Starts loading a new generation of resources and indexes in the background. The new data gets
+/// swapped in once it's fully loaded and passed all checks. Only one reload can run at a time
+pub async fn reload(config: Data<Config>) -> Result<HttpResponse, RestError> {
+    if RELOADING.swap(true, Ordering::AcqRel) {
+        return Ok(HttpResponse::Conflict().json(Response::new(ReloadStatus::Running)));
+    }
+    let guard = ReloadGuard;
+
+    let config = config.get_ref().clone();
+    thread::spawn(move || {
+        let _guard = guard;
+        if let Err(err) = reload_data(&config) {
+            error!("Reloading data failed: {err}");
+        }
+    });
+
+    Ok(HttpResponse::Accepted().json(Response::new(ReloadStatus::Started)))
+}
+
+/// Loads resources and indexes from the configured sources and replaces the currently loaded ones
+fn reload_data(config: &Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let start = Instant::now();
+    info!("Reloading resources and indexes");
+
+    let (storage, custom) = load_resources(config).map_err(|e| e.to_string())?;
+    if !storage.check() {
+        let missing = storage.missing_but_required();
+        return Err(format!("Missing required features: {missing:?}").into());
+    }
+
+    let index_store = indexes::storage::load_raw(config.get_indexes_source())?;
+    if !index_store.check() {
+        return Err("Not all indexes are available".into());
+    }
+
+    let stats = search::cache::stats();
+
+    // Waits for running requests to finish, so no request sees parts of both generations
+    let old = swap_cell::swap_all(|| {
+        // Cached results refer to the old data which gets freed
+        search::cache::clear();
+        (
+            resources::swap(storage),
+            indexes::storage::swap(index_store),
+            indexes::custom::swap(custom),
+        )
+    });
+    // Frees the previous generation
+    drop(old);
+
+    debug!(
+        "Cleared search cache ({} entries, hit rate {:.2})",
+        stats.entries,
+        stats.hit_rate()
+    );
+
+    info!("Reloading data took {:?}", start.elapsed());
+    Ok(())
+}
+
+/// Loads the resource storage and merges all configured custom dictionaries into it. Returns the
+/// storage together with the index for the custom words
+pub fn load_resources(config: &Config) -> Result<(ResourceStorage, CustomIndex), Box<dyn Error>> {
+    let mut storage = resources::load_raw(config.get_storage_data_path())?;
+
+    let dicts = config.get_custom_dictionaries();
+    let custom = resources::custom::merge(&mut storage, dicts)?;
+    if !custom.is_empty() {
+        debug!("Loaded {} custom words", custom.len());
+    }
+
+    let index = CustomIndex::new(custom.iter().filter_map(|i| storage.words.words.get(*i)));
+    Ok((storage, index))
+}
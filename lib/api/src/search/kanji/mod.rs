@@ -1,9 +1,14 @@
-use actix_web::web::{self, Data, Json};
+use actix_web::web::{self, Data, Json, ReqData};
 use config::Config;
+use error::api_error::RestError;
 use types::{
-    api::search::kanji::{Kanji, Response},
+    api::search::{
+        kanji::{Kanji, Response},
+        PageInfo,
+    },
     jotoba::search::SearchTarget,
 };
+use utils::swap_cell::ReadPin;
 
 use super::{Result, SearchRequest};
 
@@ -11,19 +16,33 @@ use super::{Result, SearchRequest};
 pub async fn kanji_search(
     payload: Json<SearchRequest>,
     config: Data<Config>,
+    pin: ReqData<ReadPin>,
 ) -> Result<Json<Response>> {
-    let query = super::parse_query(payload, SearchTarget::Kanji)?;
-    let result = web::block(move || search::kanji::search(&query))
-        .await??
-        .items;
-    Ok(Json(to_response(result, &config)))
+    let query = super::parse_query(payload, SearchTarget::Kanji, &config)?;
+    let query_c = query.clone();
+    let result = web::block(move || {
+        let _pin = pin;
+        search::kanji::search(&query_c)
+    })
+    .await??;
+    if result.incomplete && result.items.is_empty() {
+        return Err(RestError::Timeout);
+    }
+
+    let mut page = super::page_info(&query, result.total_len);
+    page.incomplete = result.incomplete;
+    Ok(Json(to_response(result.items, page, &config)))
 }
 
 #[inline]
-fn to_response(items: Vec<search::kanji::result::Item>, config: &Config) -> Response {
+fn to_response(
+    items: Vec<search::kanji::result::Item>,
+    page: PageInfo,
+    config: &Config,
+) -> Response {
     let kanji = items
         .into_iter()
         .map(|i| Kanji::from(&i.kanji, config.server.get_html_files()))
         .collect();
-    Response { kanji }
+    Response { kanji, page }
 }
@@ -1,27 +1,143 @@
 pub mod kanji;
 pub mod name;
+pub mod pitch;
 pub mod sentence;
 pub mod word;
 
 use actix_web::web::Json;
+use config::Config;
 use error::api_error::RestError;
-use search::query::{parser::QueryParser, Query, UserSettings};
-use types::{api::search::SearchRequest, jotoba::search::SearchTarget};
+use search::{
+    executor::search_result::SearchResult,
+    query::{
+        parser::{parse_tag, QueryParser},
+        Query, UserSettings,
+    },
+};
+use types::{
+    api::search::{PageInfo, SearchRequest},
+    jotoba::{language::Language, search::SearchTarget},
+};
 
 pub type Result<T> = std::result::Result<T, RestError>;
 
-pub(crate) fn parse_query(payload: Json<SearchRequest>, q_type: SearchTarget) -> Result<Query> {
+/// Default amount of results per page
+const DEFAULT_PAGE_SIZE: u32 = 10;
+
+/// Highest page that can be requested. Same as the limits of the paginator in the frontend
+const MAX_PAGE: u32 = 100;
+const MAX_KANJI_PAGE: u32 = 400;
+
+pub(crate) fn parse_query(
+    payload: Json<SearchRequest>,
+    q_type: SearchTarget,
+    config: &Config,
+) -> Result<Query> {
+    let mut tags = vec![];
+    for tag in payload.tags.iter() {
+        let parsed = parse_tag(tag);
+        if parsed.is_empty() {
+            return Err(RestError::BadRequest);
+        }
+        tags.extend(parsed);
+    }
+
+    let page_size = payload
+        .page_size
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, config.get_max_page_size());
+
     let settings = UserSettings {
         user_lang: payload.language,
         show_english: !payload.no_english,
+        page_size,
         ..UserSettings::default()
     };
 
     let q_str = payload.query_str.clone();
+    let page = parse_page(payload.page, q_type)?;
 
-    let query = QueryParser::new(q_str, q_type, settings)
-        .parse()
-        .ok_or(RestError::BadRequest)?;
+    let mut parser = QueryParser::new(q_str, q_type, settings)
+        .with_page(page)
+        .with_tags(tags);
+
+    if let Some(profile) = &payload.ranking_profile {
+        if !search::ranking::exists(profile) {
+            return Err(RestError::BadRequest);
+        }
+        parser = parser.with_ranking_profile(profile.clone());
+    }
+
+    let query = parser.parse().ok_or(RestError::BadRequest)?;
 
     Ok(query)
 }
+
+/// Returns the requested page starting at 1. Fails if the page is higher than the allowed maximum
+fn parse_page(page: Option<u32>, q_type: SearchTarget) -> Result<usize> {
+    let max_page = match q_type {
+        SearchTarget::Kanji => MAX_KANJI_PAGE,
+        _ => MAX_PAGE,
+    };
+
+    let page = page.unwrap_or(1);
+    if page > max_page {
+        return Err(RestError::BadRequest);
+    }
+
+    Ok(page.max(1) as usize)
+}
+
+/// Returns the pagination information of a search response
+pub(crate) fn page_info(query: &Query, total: usize) -> PageInfo {
+    PageInfo::new(query.page as u32, query.settings.page_size, total)
+}
+
+/// Returns the pagination information of an executed search. Fails with a timeout if the search
+/// ran out of time before finding anything
+pub(crate) fn result_page_info<T, O>(
+    query: &Query,
+    result: &SearchResult<T, O>,
+) -> Result<PageInfo> {
+    if result.incomplete && result.is_empty() {
+        return Err(RestError::Timeout);
+    }
+
+    let mut page = page_info(query, result.total);
+    page.incomplete = result.incomplete;
+    Ok(page)
+}
+
+/// Parses a single query with the given user settings
+pub(crate) fn new_query(
+    q_str: String,
+    language: Language,
+    no_english: bool,
+    q_type: SearchTarget,
+) -> Option<Query> {
+    let settings = UserSettings {
+        user_lang: language,
+        show_english: !no_english,
+        ..UserSettings::default()
+    };
+
+    QueryParser::new(q_str, q_type, settings).parse()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_page() {
+        assert_eq!(parse_page(None, SearchTarget::Words).unwrap(), 1);
+        assert_eq!(parse_page(Some(0), SearchTarget::Words).unwrap(), 1);
+        assert_eq!(
+            parse_page(Some(MAX_PAGE), SearchTarget::Words).unwrap(),
+            100
+        );
+        assert!(parse_page(Some(MAX_PAGE + 1), SearchTarget::Words).is_err());
+        assert!(parse_page(Some(u32::MAX), SearchTarget::Names).is_err());
+        assert!(parse_page(Some(MAX_PAGE + 1), SearchTarget::Kanji).is_ok());
+    }
+}
@@ -1,16 +1,25 @@
-use actix_web::web::{self, Json};
+use actix_web::web::{self, Data, Json, ReqData};
+use config::Config;
 use search::SearchExecutor;
 use types::{api::search::name::Response, jotoba::search::SearchTarget};
+use utils::swap_cell::ReadPin;
 
 use super::{Result, SearchRequest};
 
 /// Do a name search via API
-pub async fn name_search(payload: Json<SearchRequest>) -> Result<Json<Response>> {
-    let query = super::parse_query(payload, SearchTarget::Kanji)?;
+pub async fn name_search(
+    payload: Json<SearchRequest>,
+    config: Data<Config>,
+    pin: ReqData<ReadPin>,
+) -> Result<Json<Response>> {
+    let query = super::parse_query(payload, SearchTarget::Names, &config)?;
+    let query_c = query.clone();
     let result = web::block(move || {
-        let search = search::name::Search::new(&query);
-        SearchExecutor::new(search).run()
+        let _pin = pin;
+        let search = search::name::Search::new(&query_c);
+        SearchExecutor::new(search).run_cached()
     })
     .await?;
-    Ok(Json(result.items.into()))
+    let page = super::result_page_info(&query, &result)?;
+    Ok(Json(Response::from(result.items).with_page(page)))
 }
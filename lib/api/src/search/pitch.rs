@@ -0,0 +1,95 @@
+use super::Result;
+use actix_web::web::{self, Json, ReqData};
+use engine::task::SearchTask;
+use error::api_error::RestError;
+use japanese::ToKanaExt;
+use jp_utils::JapaneseExt;
+use search::{engine::words::native::Engine, query::UserSettings};
+use types::{
+    api::search::{
+        pitch::{PitchGroup, Request, Response},
+        word::Accent,
+    },
+    jotoba::words::Word,
+};
+use utils::swap_cell::ReadPin;
+
+/// Max amount of homophones to look up
+const MAX_WORDS: usize = 100;
+
+/// Returns the pitch accents of all words written with the given kana, grouped by their accent
+pub async fn pitch_search(payload: Json<Request>, pin: ReqData<ReadPin>) -> Result<Json<Response>> {
+    let kana = payload.kana.trim().to_string();
+    if kana.is_empty() || !kana.is_kana() {
+        return Err(RestError::BadRequest);
+    }
+
+    let payload = payload.into_inner();
+    let res = web::block(move || {
+        let _pin = pin;
+        lookup(&kana, &payload)
+    })
+    .await?;
+    Ok(Json(res))
+}
+
+fn lookup(kana: &str, payload: &Request) -> Response {
+    let settings = UserSettings {
+        user_lang: payload.language,
+        show_english: !payload.no_english,
+        ..UserSettings::default()
+    };
+
+    let hiragana = kana.to_hiragana();
+    let hiragana_c = hiragana.clone();
+    let mut task = SearchTask::<Engine>::new(kana)
+        .with_limit(MAX_WORDS)
+        .with_result_filter(move |word| word.get_kana().to_hiragana() == hiragana_c);
+    let mut words: Vec<&Word> = task
+        .find()
+        .into_inner()
+        .into_iter()
+        .map(|i| i.item)
+        .collect();
+
+    // Words of custom dictionaries aren't part of the word index
+    let custom = indexes::custom::get()
+        .map(|i| i.find_native(kana))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(seq, _)| resources::get().words().by_sequence(seq))
+        .filter(|i| i.get_kana().to_hiragana() == hiragana);
+    words.extend(custom);
+
+    let mut res = Response::default();
+
+    for word in words {
+        let mut word = word.clone();
+        word.adjust_language(settings.lang_param());
+
+        // Words without any accent which can be displayed are listed as unknown
+        let mut grouped = false;
+
+        for drop in word.accents.iter() {
+            let pos = match res.groups.iter().position(|i| i.accent.drop == drop) {
+                Some(pos) => pos,
+                None => {
+                    let Some(accent) = Accent::new(word.get_kana(), drop) else {
+                        continue;
+                    };
+                    res.groups.push(PitchGroup::new(accent));
+                    res.groups.len() - 1
+                }
+            };
+            res.groups[pos].words.push((&word).into());
+            grouped = true;
+        }
+
+        if !grouped {
+            res.unknown.push((&word).into());
+        }
+    }
+
+    res.groups.sort_by_key(|i| i.accent.drop);
+    res
+}
@@ -1,26 +1,37 @@
-use actix_web::web::{self, Json};
+use actix_web::web::{self, Data, Json, ReqData};
+use config::Config;
 use types::{
     api::search::sentence::{Response, Sentence},
     jotoba::search::SearchTarget,
 };
+use utils::swap_cell::ReadPin;
 
 use super::{Result, SearchRequest};
 
 /// Do a Sentence search via API
-pub async fn sentence_search(payload: Json<SearchRequest>) -> Result<Json<Response>> {
-    let query = super::parse_query(payload, SearchTarget::Kanji)?;
+pub async fn sentence_search(
+    payload: Json<SearchRequest>,
+    config: Data<Config>,
+    pin: ReqData<ReadPin>,
+) -> Result<Json<Response>> {
+    let query = super::parse_query(payload, SearchTarget::Sentences, &config)?;
+    let query_c = query.clone();
 
     let result = web::block(move || {
-        let search = search::sentence::Search::new(&query);
-        search::SearchExecutor::new(search).run()
+        let _pin = pin;
+        let search = search::sentence::Search::new(&query_c);
+        search::SearchExecutor::new(search).run_cached()
     })
-    .await?
-    .items
-    .into_iter()
-    .map(|i| search_to_sentence(i))
-    .collect::<Vec<_>>();
+    .await?;
 
-    Ok(Json(result.into()))
+    let page = super::result_page_info(&query, &result)?;
+    let sentences = result
+        .items
+        .into_iter()
+        .map(|i| search_to_sentence(i))
+        .collect::<Vec<_>>();
+
+    Ok(Json(Response::from(sentences).with_page(page)))
 }
 
 #[inline]
@@ -0,0 +1,78 @@
+use super::super::{new_query, Result};
+use actix_web::web::{self, Data, Json, ReqData};
+use config::Config;
+use error::api_error::RestError;
+use rayon::prelude::*;
+use search::{query::UserSettings, word::Search, SearchExecutor};
+use types::{
+    api::search::word::{BatchRequest, BatchResponse, BatchResult},
+    jotoba::search::SearchTarget,
+};
+use utils::swap_cell::ReadPin;
+
+/// Looks up multiple queries and sequence IDs in one request. Lookups run in parallel and each
+/// of them reports its own error
+pub async fn word_search_batch(
+    payload: Json<BatchRequest>,
+    config: Data<Config>,
+    pin: ReqData<ReadPin>,
+) -> Result<Json<BatchResponse>> {
+    if payload.is_empty() || payload.len() > config.get_batch_limit() {
+        return Err(RestError::BadRequest);
+    }
+
+    let payload = payload.into_inner();
+    let res = web::block(move || {
+        let _pin = pin;
+        let queries = payload
+            .queries
+            .par_iter()
+            .map(|q| (q.clone(), search_query(q, &payload)))
+            .collect();
+
+        let sequences = payload
+            .sequences
+            .par_iter()
+            .map(|seq| (*seq, find_sequence(*seq, &payload)))
+            .collect();
+
+        BatchResponse { queries, sequences }
+    })
+    .await?;
+
+    Ok(Json(res))
+}
+
+/// Runs a word search for a single query of the batch
+fn search_query(q_str: &str, payload: &BatchRequest) -> BatchResult {
+    let query = match new_query(
+        q_str.to_string(),
+        payload.language,
+        payload.no_english,
+        SearchTarget::Words,
+    ) {
+        Some(query) => query,
+        None => return BatchResult::Error("Invalid query".to_string()),
+    };
+
+    let result = SearchExecutor::new(Search::new(&query)).run_cached();
+    BatchResult::Words(result.items.iter().map(|i| i.into()).collect())
+}
+
+/// Looks up a single word by its sequence ID
+fn find_sequence(seq: u32, payload: &BatchRequest) -> BatchResult {
+    let word = match resources::get().words().by_sequence(seq) {
+        Some(word) => word,
+        None => return BatchResult::Error("Not found".to_string()),
+    };
+
+    let settings = UserSettings {
+        user_lang: payload.language,
+        show_english: !payload.no_english,
+        ..UserSettings::default()
+    };
+
+    let mut word = word.clone();
+    word.adjust_language(settings.lang_param());
+    BatchResult::Words(vec![(&word).into()])
+}
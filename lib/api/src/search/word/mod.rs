@@ -1,24 +1,36 @@
+pub mod batch;
+
 use super::{Result, SearchRequest};
-use actix_web::web::{self, Data, Json};
+use actix_web::{
+    http::header::ACCEPT,
+    rt,
+    web::{self, Bytes, Data, Json, ReqData},
+    HttpRequest, HttpResponse,
+};
 use config::Config;
+use futures::{channel::mpsc, StreamExt};
 use search::{word::Search, SearchExecutor};
 use types::{
     api::search::{
         kanji::Kanji,
-        word::{Response, Word},
+        word::{Response, StreamEvent, Word},
     },
     jotoba::search::SearchTarget,
 };
+use utils::swap_cell::ReadPin;
 
 /// Do a word search via API
 pub async fn word_search(
     payload: Json<SearchRequest>,
     config: Data<Config>,
+    pin: ReqData<ReadPin>,
 ) -> Result<Json<Response>> {
-    let query = super::parse_query(payload, SearchTarget::Words)?;
+    let query = super::parse_query(payload, SearchTarget::Words, &config)?;
+    let query_c = query.clone();
     let result = web::block(move || {
-        let search = Search::new(&query);
-        SearchExecutor::new(search).run()
+        let _pin = pin;
+        let search = Search::new(&query_c);
+        SearchExecutor::new(search).run_cached()
     })
     .await?;
 
@@ -26,6 +38,79 @@ pub async fn word_search(
         .into_iter()
         .map(|i| Kanji::from(&i, config.server.get_html_files()))
         .collect();
+    let page = super::result_page_info(&query, &result)?;
     let words: Vec<Word> = result.items.into_iter().map(|i| (&i).into()).collect();
-    Ok(Json(Response::new(words, kanji)))
+    Ok(Json(Response::new(words, kanji).with_page(page)))
+}
+
+/// Do a word search via API and stream the words of each producer as soon as it finished. The
+/// last event contains the final result. Events are sent as Server-Sent Events if requested by
+/// the `Accept` header and as NDJSON otherwise
+pub async fn word_search_stream(
+    payload: Json<SearchRequest>,
+    request: HttpRequest,
+    config: Data<Config>,
+    pin: ReqData<ReadPin>,
+) -> Result<HttpResponse> {
+    let query = super::parse_query(payload, SearchTarget::Words, &config)?;
+
+    let sse = request
+        .headers()
+        .get(ACCEPT)
+        .and_then(|i| i.to_str().ok())
+        .map(|i| i.contains("text/event-stream"))
+        .unwrap_or(false);
+
+    let (sender, receiver) = mpsc::unbounded();
+    let assets = config.server.get_html_files().to_string();
+
+    rt::task::spawn_blocking(move || {
+        // The search outlives the request handler
+        let _pin = pin;
+        let search = Search::new(&query);
+        let result = SearchExecutor::new(search).run_streamed(|producer, words| {
+            if words.is_empty() {
+                return;
+            }
+            let words = words.iter().map(|i| i.into()).collect();
+            let producer = producer.to_string();
+            // The client might have disconnected already
+            let _ = sender.unbounded_send(StreamEvent::Results { producer, words });
+        });
+
+        let kanji = search::word::kanji::load_word_kanji_info(&result.items)
+            .into_iter()
+            .map(|i| Kanji::from(&i, &assets))
+            .collect();
+        let words = result.items.iter().map(|i| i.into()).collect();
+        let total = result.total;
+        let _ = sender.unbounded_send(StreamEvent::Summary {
+            total,
+            words,
+            kanji,
+            incomplete: result.incomplete,
+        });
+    });
+
+    let content_type = if sse {
+        "text/event-stream"
+    } else {
+        "application/x-ndjson"
+    };
+
+    let body = receiver.map(move |event| encode_event(&event, sse));
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .streaming(body))
+}
+
+/// Encodes a single stream event as SSE message or NDJSON line
+fn encode_event(event: &StreamEvent, sse: bool) -> std::result::Result<Bytes, serde_json::Error> {
+    let json = serde_json::to_string(event)?;
+    let encoded = if sse {
+        format!("data: {json}\n\n")
+    } else {
+        format!("{json}\n")
+    };
+    Ok(Bytes::from(encoded))
 }
@@ -30,6 +30,8 @@ pub struct ServerConfig {
     pub tess_data: Option<String>,
     pub news_folder: Option<String>,
     pub unidic_dict: Option<String>,
+    /// JSON or TOML file extending the grammar of the sentence reader
+    pub sentence_reader_grammar: Option<String>,
     pub debug_mode: Option<bool>,
     pub internal_api_key: String,
 }
@@ -43,7 +45,61 @@ pub struct SentryConfig {
 pub struct SearchConfig {
     pub suggestion_sources: Option<String>,
     pub indexes_source: Option<String>,
+    /// Seconds after which a search gets reported as slow query. Reporting is disabled if unset
     pub report_queries_after: Option<u64>,
+    /// JSON-lines file slow queries get reported to
+    pub slow_query_log: Option<String>,
+    /// JSON or TSV files with additional words
+    pub custom_dictionaries: Option<Vec<String>>,
+    /// Max amount of queries in a single batch word search
+    pub batch_limit: Option<usize>,
+    /// Max page size API clients can request
+    pub max_page_size: Option<u32>,
+    /// Max amount of cached search results. Caching is disabled if set to 0
+    pub cache_size: Option<usize>,
+    /// Seconds after which cached search results expire
+    pub cache_ttl: Option<u64>,
+    /// Time budgets of searches
+    pub timeouts: Option<SearchTimeouts>,
+    /// JSON or TOML file with ranking profiles which can be selected per search
+    pub ranking_profiles: Option<String>,
+}
+
+/// Time budgets in milliseconds after which searches return the results found so far. A value of
+/// 0 disables the budget
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct SearchTimeouts {
+    pub words: Option<u64>,
+    pub names: Option<u64>,
+    pub sentences: Option<u64>,
+    pub kanji: Option<u64>,
+}
+
+impl SearchTimeouts {
+    /// Returns the budget of word searches
+    pub fn words(&self) -> Option<Duration> {
+        to_budget(self.words.unwrap_or(5000))
+    }
+
+    /// Returns the budget of name searches
+    pub fn names(&self) -> Option<Duration> {
+        to_budget(self.names.unwrap_or(3000))
+    }
+
+    /// Returns the budget of sentence searches
+    pub fn sentences(&self) -> Option<Duration> {
+        to_budget(self.sentences.unwrap_or(3000))
+    }
+
+    /// Returns the budget of kanji searches
+    pub fn kanji(&self) -> Option<Duration> {
+        to_budget(self.kanji.unwrap_or(3000))
+    }
+}
+
+#[inline]
+fn to_budget(ms: u64) -> Option<Duration> {
+    (ms > 0).then(|| Duration::from_millis(ms))
 }
 
 impl Config {
@@ -63,6 +119,78 @@ impl Config {
             .unwrap_or("./resources/suggestions")
     }
 
+    /// Returns the configured custom dictionary files
+    pub fn get_custom_dictionaries(&self) -> &[String] {
+        self.search
+            .as_ref()
+            .and_then(|i| i.custom_dictionaries.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Returns the configured max amount of queries in a batch word search
+    pub fn get_batch_limit(&self) -> usize {
+        self.search
+            .as_ref()
+            .and_then(|i| i.batch_limit)
+            .unwrap_or(100)
+    }
+
+    /// Returns the configured max page size for API searches
+    pub fn get_max_page_size(&self) -> u32 {
+        self.search
+            .as_ref()
+            .and_then(|i| i.max_page_size)
+            .unwrap_or(100)
+    }
+
+    /// Returns the configured max amount of cached search results
+    pub fn get_cache_size(&self) -> usize {
+        self.search
+            .as_ref()
+            .and_then(|i| i.cache_size)
+            .unwrap_or(1000)
+    }
+
+    /// Returns the configured time to live of cached search results
+    pub fn get_cache_ttl(&self) -> Duration {
+        let ttl = self
+            .search
+            .as_ref()
+            .and_then(|i| i.cache_ttl)
+            .unwrap_or(300);
+        Duration::from_secs(ttl)
+    }
+
+    /// Returns the configured time budgets of searches
+    pub fn get_search_timeouts(&self) -> SearchTimeouts {
+        self.search
+            .as_ref()
+            .and_then(|i| i.timeouts.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the configured file with ranking profiles
+    pub fn get_ranking_profiles(&self) -> Option<&str> {
+        self.search
+            .as_ref()
+            .and_then(|i| i.ranking_profiles.as_deref())
+    }
+
+    /// Returns `true` if slow queries should be reported
+    pub fn is_query_reporting_enabled(&self) -> bool {
+        self.search
+            .as_ref()
+            .map_or(false, |i| i.report_queries_after.is_some())
+    }
+
+    /// Returns the configured file for slow query reports
+    pub fn get_slow_query_log(&self) -> &str {
+        self.search
+            .as_ref()
+            .and_then(|i| i.slow_query_log.as_deref())
+            .unwrap_or("./slow_queries.jsonl")
+    }
+
     /// Returns the configured query report timeout
     pub fn get_query_report_timeout(&self) -> Duration {
         let timeout = self
@@ -100,6 +228,11 @@ impl Config {
             .unwrap_or_else(|| ServerConfig::default().unidic_dict.unwrap())
     }
 
+    /// Returns the configured grammar file for the sentence reader
+    pub fn get_sentence_reader_grammar(&self) -> Option<&str> {
+        self.server.sentence_reader_grammar.as_deref()
+    }
+
     /// Returns the configured (or default) path for the radical map
     pub fn get_img_scan_upload_path(&self) -> String {
         self.server
@@ -125,6 +258,7 @@ impl Default for ServerConfig {
             storage_data: Some(String::from("./resources/storage_data")),
             img_upload_dir: Some(String::from("./img_scan_tmp")),
             unidic_dict: Some(String::from("./resources/unidic-mecab")),
+            sentence_reader_grammar: None,
             tess_data: None,
             news_folder: Some(String::from("./resources/news")),
             debug_mode: Some(false),
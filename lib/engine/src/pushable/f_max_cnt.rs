@@ -1,5 +1,5 @@
 use super::Pushable;
-use std::marker::PhantomData;
+use std::{marker::PhantomData, time::Instant};
 
 /// A counter that Implements CancelPushable which counts up to a fixed value and
 /// Cancels counting if this value has been reached
@@ -7,6 +7,8 @@ pub struct FilteredMaxCounter<'a, T> {
     val: usize,
     max: usize,
     pub filter: Box<dyn Fn(&T) -> bool + 'a>,
+    /// Point in time after which counting stops
+    deadline: Option<Instant>,
     p: PhantomData<T>,
 }
 
@@ -20,10 +22,18 @@ impl<'a, T> FilteredMaxCounter<'a, T> {
             val: 0,
             max,
             filter: Box::new(filter),
+            deadline: None,
             p: PhantomData,
         }
     }
 
+    /// Sets the point in time after which producers should stop counting
+    #[inline]
+    pub fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
     #[inline]
     pub fn val(&self) -> usize {
         self.val
@@ -55,4 +65,14 @@ impl<'a, T> Pushable for FilteredMaxCounter<'a, T> {
 
         true
     }
+
+    #[inline]
+    fn timed_out(&self) -> bool {
+        self.deadline.map_or(false, |i| Instant::now() >= i)
+    }
+
+    #[inline]
+    fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
 }
@@ -10,14 +10,41 @@ pub use f_max_cnt::FilteredMaxCounter;
 pub use max_cnt::MaxCounter;
 pub use push_mod::PushMod;
 
-use super::relevance::item::RelItem;
+use super::relevance::{explain::Explanation, item::RelItem};
 use priority_container::StableUniquePrioContainerMax;
-use std::hash::Hash;
+use std::{hash::Hash, time::Instant};
 
 pub trait Pushable {
     type Item;
 
     fn push(&mut self, i: Self::Item) -> bool;
+
+    /// Pushes an item together with the explanation of its score. Only called if
+    /// `wants_explanation` returns `true`
+    #[inline]
+    fn push_explained(&mut self, i: Self::Item, _explanation: Explanation) -> bool {
+        self.push(i)
+    }
+
+    /// Returns `true` if the consumer wants to know how the score of each item got calculated
+    #[inline]
+    fn wants_explanation(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if the deadline of the consumer has passed and no more items should be
+    /// retrieved
+    #[inline]
+    fn timed_out(&self) -> bool {
+        false
+    }
+
+    /// Returns the point in time after which the consumer doesn't want any more items. Lets
+    /// producers stop expensive work before the first item gets pushed
+    #[inline]
+    fn deadline(&self) -> Option<Instant> {
+        None
+    }
 }
 
 impl<T> Pushable for StableUniquePrioContainerMax<RelItem<T>>
@@ -1,5 +1,6 @@
 use super::Pushable;
-use std::{fmt::Debug, marker::PhantomData};
+use crate::relevance::explain::Explanation;
+use std::{fmt::Debug, marker::PhantomData, time::Instant};
 
 /// Allows debugging pushed items
 pub struct PushDbg<'a, P, I> {
@@ -34,4 +35,28 @@ where
         println!(" continue: {cont}");
         cont
     }
+
+    #[inline]
+    fn push_explained(&mut self, i: Self::Item, explanation: Explanation) -> bool {
+        print!("{i:#?} {explanation:#?}");
+        let cont = self.output.push_explained(i, explanation);
+        println!(" continue: {cont}");
+        cont
+    }
+
+    /// Always explain the scores of debugged items
+    #[inline]
+    fn wants_explanation(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn timed_out(&self) -> bool {
+        self.output.timed_out()
+    }
+
+    #[inline]
+    fn deadline(&self) -> Option<Instant> {
+        self.output.deadline()
+    }
 }
@@ -1,6 +1,7 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, time::Instant};
 
 use super::Pushable;
+use crate::relevance::explain::Explanation;
 
 /// Allows modifying pushed data
 pub struct PushMod<'a, P, I, O, F> {
@@ -36,4 +37,24 @@ where
     fn push(&mut self, i: Self::Item) -> bool {
         self.output.push((self.f)(i))
     }
+
+    #[inline]
+    fn push_explained(&mut self, i: Self::Item, explanation: Explanation) -> bool {
+        self.output.push_explained((self.f)(i), explanation)
+    }
+
+    #[inline]
+    fn wants_explanation(&self) -> bool {
+        self.output.wants_explanation()
+    }
+
+    #[inline]
+    fn timed_out(&self) -> bool {
+        self.output.timed_out()
+    }
+
+    #[inline]
+    fn deadline(&self) -> Option<Instant> {
+        self.output.deadline()
+    }
 }
@@ -0,0 +1,102 @@
+//! Recording of the steps a [`RelevanceEngine`](super::RelevanceEngine) took to calculate the
+//! score of an item. Engines report their factors using [`index_relevance`], [`factor`] and
+//! [`replace`] which are no-ops unless the score is calculated within [`record`].
+
+use std::cell::RefCell;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Explanation>> = RefCell::new(None);
+}
+
+/// How the score of a single item has been calculated
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Explanation {
+    /// Relevance of the item within the index before any factor got applied
+    pub index_relevance: Option<f32>,
+    /// All factors in the order they got applied
+    pub factors: Vec<Factor>,
+    /// The final score
+    pub score: f32,
+}
+
+/// A single step in the calculation of a score
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Factor {
+    pub name: &'static str,
+    pub value: f32,
+    pub op: FactorOp,
+}
+
+/// How a factor got applied to the score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactorOp {
+    /// The score got multiplied with the value
+    Multiply,
+    /// The score got replaced by the value
+    Replace,
+}
+
+/// Calls `score` and records all factors reported while calculating the score
+pub fn record<F: FnOnce() -> f32>(score: F) -> Explanation {
+    let prev = CURRENT.with(|c| c.replace(Some(Explanation::default())));
+    let score = score();
+    let mut explanation = CURRENT.with(|c| c.replace(prev)).unwrap_or_default();
+    explanation.score = score;
+    explanation
+}
+
+/// Reports the relevance of the item within the index
+#[inline]
+pub fn index_relevance(value: f32) {
+    with_current(|e| e.index_relevance = Some(value));
+}
+
+/// Reports a factor the score got multiplied with
+#[inline]
+pub fn factor(name: &'static str, value: f32) {
+    push(name, value, FactorOp::Multiply);
+}
+
+/// Reports that the score got replaced with `value`
+#[inline]
+pub fn replace(name: &'static str, value: f32) {
+    push(name, value, FactorOp::Replace);
+}
+
+#[inline]
+fn push(name: &'static str, value: f32, op: FactorOp) {
+    with_current(|e| e.factors.push(Factor { name, value, op }));
+}
+
+#[inline]
+fn with_current<F: FnOnce(&mut Explanation)>(f: F) {
+    CURRENT.with(|c| {
+        if let Some(e) = c.borrow_mut().as_mut() {
+            f(e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        // Not recorded
+        factor("ignored", 0.5);
+
+        let explanation = record(|| {
+            index_relevance(0.8);
+            factor("a", 0.5);
+            replace("b", 0.9);
+            0.9
+        });
+
+        assert_eq!(explanation.index_relevance, Some(0.8));
+        assert_eq!(explanation.score, 0.9);
+        assert_eq!(explanation.factors.len(), 2);
+        assert_eq!(explanation.factors[0].op, FactorOp::Multiply);
+        assert_eq!(explanation.factors[1].op, FactorOp::Replace);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod data;
+pub mod explain;
 pub mod item;
 
 use data::SortData;
@@ -1,12 +1,12 @@
 use crate::{
     pushable::{MaxCounter, PushMod, Pushable},
     relevance::{data::SortData, RelevanceEngine},
-    relevance::{item::RelItem, RelEngineInit},
+    relevance::{explain, item::RelItem, RelEngineInit},
     result::SearchResult,
     Engine,
 };
 use priority_container::StableUniquePrioContainerMax;
-use std::marker::PhantomData;
+use std::{marker::PhantomData, time::Instant};
 use types::jotoba::{
     language::Language,
     search::guess::{Guess, GuessType},
@@ -36,6 +36,9 @@ pub struct SearchTask<'index, E: Engine<'index>> {
     /// Max distance to max item
     max_dist: Option<f32>,
 
+    /// Point in time after which no more items get retrieved
+    deadline: Option<Instant>,
+
     limit: usize,
     offset: usize,
     est_limit: usize,
@@ -82,6 +85,14 @@ where
         self
     }
 
+    /// Sets a deadline after which the task stops retrieving items and only returns the ones found
+    /// so far
+    #[inline]
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
     /// Sets the search task's threshold. This does not apply on the final score, which can be
     /// overwritten by `order` but applies to the vector space relevance itself.
     #[inline]
@@ -207,16 +218,29 @@ where
             E::retrieve_for(&query, &self.query_str, self.query_lang).get();
 
         let mut pushed = 0;
+        let explaining = out.wants_explanation();
 
         loop {
+            if self.is_expired() || out.timed_out() {
+                break;
+            }
+
             let (index_item, out_items) = match self.retrieve_next(&mut retr) {
                 Some(v) => v,
                 None => break,
             };
 
             for i in out_items {
+                let mut explanation = None;
                 let score = if sort || self.has_threshold() {
-                    self.score(&i, &index_item, &query)
+                    if explaining {
+                        let e = explain::record(|| self.score(&i, &index_item, &query));
+                        let score = e.score;
+                        explanation = Some(e);
+                        score
+                    } else {
+                        self.score(&i, &index_item, &query)
+                    }
                 } else {
                     0.0
                 };
@@ -227,7 +251,12 @@ where
 
                 // Break if caller doesn't want to consume more
                 pushed += 1;
-                if !out.push(RelItem::new(i, score)) {
+                let item = RelItem::new(i, score);
+                let cont = match explanation {
+                    Some(explanation) => out.push_explained(item, explanation),
+                    None => out.push(item),
+                };
+                if !cont {
                     break;
                 }
             }
@@ -236,6 +265,12 @@ where
         Some(pushed)
     }
 
+    /// Returns `true` if the deadline of the task has passed
+    #[inline]
+    fn is_expired(&self) -> bool {
+        self.deadline.map_or(false, |i| Instant::now() >= i)
+    }
+
     #[inline]
     fn score(&self, out_item: &E::Output, index_item: &E::Document, query: &E::Query) -> f32 {
         let threshold = self.has_threshold().then(|| self.threshold);
@@ -327,6 +362,7 @@ impl<'a, T: Engine<'a>> Default for SearchTask<'a, T> {
             est_limit: 100,
             phantom: PhantomData,
             max_dist: None,
+            deadline: None,
         }
     }
 }
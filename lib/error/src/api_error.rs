@@ -9,6 +9,7 @@ pub enum Origin {
     Radicals,
     Suggestions,
     File,
+    Tokenizer,
 }
 
 impl std::fmt::Debug for Origin {
@@ -20,6 +21,7 @@ impl std::fmt::Debug for Origin {
                 Origin::Radicals => "radicals",
                 Origin::Suggestions => "suggestions",
                 Origin::File => "file",
+                Origin::Tokenizer => "tokenizer",
             }
         )
     }
@@ -17,6 +17,7 @@ use search::{
 use std::{sync::Arc, time::Instant};
 use types::jotoba::search::help::SearchHelp;
 use types::jotoba::search::SearchTarget;
+use utils::swap_cell::ReadPin;
 
 /// Endpoint to perform a search
 pub async fn search_ep_no_js(
@@ -24,9 +25,10 @@ pub async fn search_ep_no_js(
     locale_dict: web::Data<Arc<TranslationDict>>,
     config: web::Data<Config>,
     request: HttpRequest,
+    pin: web::ReqData<ReadPin>,
 ) -> Result<HttpResponse, web_error::Error> {
     let (query_data, query) = query_data.0.to_query_struct();
-    search(query, query_data, locale_dict, config, request).await
+    search(query, query_data, locale_dict, config, request, &pin).await
 }
 
 /// Endpoint to perform a search
@@ -36,9 +38,10 @@ pub async fn search_ep(
     locale_dict: web::Data<Arc<TranslationDict>>,
     config: web::Data<Config>,
     request: HttpRequest,
+    pin: web::ReqData<ReadPin>,
 ) -> Result<HttpResponse, web_error::Error> {
     let query = percent_decode(query.as_bytes()).decode_utf8()?.to_string();
-    search(query, query_data.0, locale_dict, config, request).await
+    search(query, query_data.0, locale_dict, config, request, &pin).await
 }
 
 async fn search(
@@ -47,6 +50,7 @@ async fn search(
     locale_dict: web::Data<Arc<TranslationDict>>,
     config: web::Data<Config>,
     request: HttpRequest,
+    pin: &ReadPin,
 ) -> Result<HttpResponse, web_error::Error> {
     let settings = user_settings::parse(&request);
 
@@ -63,7 +67,8 @@ async fn search(
     let start = Instant::now();
 
     // Log search duration if too long and available
-    let search_result = do_search(query.target, &locale_dict, settings, &query, &config).await?;
+    let search_result =
+        do_search(query.target, &locale_dict, settings, &query, &config, pin).await?;
 
     log::debug!(
         "{:?} search for {:?} took {:?}",
@@ -75,27 +80,34 @@ async fn search(
     Ok(HttpResponse::Ok().body(render!(templates::base, search_result).render()))
 }
 
-/// Run the search and return the `BaseData` for the result page to render
+/// Run the search and return the `BaseData` for the result page to render. Searches run on other
+/// threads, each holding a clone of `pin`
 async fn do_search<'a>(
     querytype: SearchTarget,
     locale_dict: &'a TranslationDict,
     settings: UserSettings,
     query: &'a Query,
     config: &'a Config,
+    pin: &ReadPin,
 ) -> Result<BaseData<'a>, web_error::Error> {
     let mut base_data = BaseData::new(locale_dict, settings, &config.asset_hash, &config);
 
     let result_data = match querytype {
-        SearchTarget::Kanji => kanji_search(&mut base_data, &query).await,
-        SearchTarget::Sentences => sentence_search(&mut base_data, &query).await,
-        SearchTarget::Names => name_search(&mut base_data, &query).await,
-        SearchTarget::Words => word_search(&mut base_data, &query).await,
+        SearchTarget::Kanji => kanji_search(&mut base_data, &query, pin).await,
+        SearchTarget::Sentences => sentence_search(&mut base_data, &query, pin).await,
+        SearchTarget::Names => name_search(&mut base_data, &query, pin).await,
+        SearchTarget::Words => word_search(&mut base_data, &query, pin).await,
     }?;
 
     let mut search_help: Option<SearchHelp> = None;
     if result_data.is_empty() {
         let query = query.to_owned();
-        search_help = web::block(move || search::build_help(querytype, &query)).await?;
+        let pin = pin.clone();
+        search_help = web::block(move || {
+            let _pin = pin;
+            search::build_help(querytype, &query)
+        })
+        .await?;
     }
 
     Ok(base_data.with_search_result(query, result_data, search_help))
@@ -104,13 +116,19 @@ async fn do_search<'a>(
 type SResult = Result<ResultData, web_error::Error>;
 
 /// Perform a sentence search
-async fn sentence_search<'a>(base_data: &mut BaseData<'a>, query: &'a Query) -> SResult {
+async fn sentence_search<'a>(
+    base_data: &mut BaseData<'a>,
+    query: &'a Query,
+    pin: &ReadPin,
+) -> SResult {
     let q = query.to_owned();
+    let pin = pin.clone();
 
     //let result = web::block(move || search::sentence::Search::new(&q).search()).await??;
     let result = web::block(move || {
+        let _pin = pin;
         let s = search::sentence::Search::new(&q);
-        search::SearchExecutor::new(s).run()
+        search::SearchExecutor::new(s).run_cached()
     })
     .await?;
 
@@ -119,9 +137,18 @@ async fn sentence_search<'a>(base_data: &mut BaseData<'a>, query: &'a Query) ->
 }
 
 /// Perform a kanji search
-async fn kanji_search<'a>(base_data: &mut BaseData<'a>, query: &'a Query) -> SResult {
+async fn kanji_search<'a>(
+    base_data: &mut BaseData<'a>,
+    query: &'a Query,
+    pin: &ReadPin,
+) -> SResult {
     let q = query.to_owned();
-    let result = web::block(move || search::kanji::search(&q)).await??;
+    let pin = pin.clone();
+    let result = web::block(move || {
+        let _pin = pin;
+        search::kanji::search(&q)
+    })
+    .await??;
     base_data.with_cust_pages(
         result.total_len as u32,
         query.page as u32,
@@ -132,11 +159,13 @@ async fn kanji_search<'a>(base_data: &mut BaseData<'a>, query: &'a Query) -> SRe
 }
 
 /// Perform a name search
-async fn name_search<'a>(base_data: &mut BaseData<'a>, query: &'a Query) -> SResult {
+async fn name_search<'a>(base_data: &mut BaseData<'a>, query: &'a Query, pin: &ReadPin) -> SResult {
     let q = query.to_owned();
+    let pin = pin.clone();
     let result = web::block(move || {
+        let _pin = pin;
         let search = search::name::Search::new(&q);
-        SearchExecutor::new(search).run()
+        SearchExecutor::new(search).run_cached()
     })
     .await?;
 
@@ -145,11 +174,13 @@ async fn name_search<'a>(base_data: &mut BaseData<'a>, query: &'a Query) -> SRes
 }
 
 /// Perform a word search
-async fn word_search<'a>(base_data: &mut BaseData<'a>, query: &'a Query) -> SResult {
+async fn word_search<'a>(base_data: &mut BaseData<'a>, query: &'a Query, pin: &ReadPin) -> SResult {
     let q = query.to_owned();
+    let pin = pin.clone();
     let result = web::block(move || {
+        let _pin = pin;
         let search = search::word::Search::new(&q);
-        SearchExecutor::new(search).run()
+        SearchExecutor::new(search).run_cached()
     })
     .await?;
 
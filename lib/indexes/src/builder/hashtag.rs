@@ -0,0 +1,56 @@
+use super::{index::IndexBuilder, ngrams};
+use crate::hashtag::HashTagIndex;
+use ngindex::{item::IndexItem, termset::TermSet, NgramIndex};
+use qp_trie::{wrapper::BString, Trie};
+use std::{
+    error::Error,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+use types::jotoba::{indexes::hashtag::RawHashtag, search::SearchTarget};
+
+/// N of the hashtag ngram index
+const HASHTAG_NGRAM: usize = 2;
+
+/// Builds a hashtag index out of `tags`. Each tag gets indexed in the posting lists of its search
+/// targets
+pub fn build(tags: Vec<RawHashtag>) -> HashTagIndex {
+    let postings = SearchTarget::iterate().count();
+    let mut builder = IndexBuilder::<NgramIndex<HASHTAG_NGRAM, u32>, _, _>::new(postings);
+    let mut trie: Trie<BString, u32> = Trie::new();
+
+    for (pos, tag) in tags.iter().enumerate() {
+        let tag_str = tag.tag.to_lowercase();
+        trie.insert_str(&tag_str, pos as u32);
+
+        let mut ids: Vec<u32> = ngrams(&tag_str, HASHTAG_NGRAM)
+            .map(|i| builder.term_id(i))
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        let item = IndexItem::new(pos as u32, TermSet::new(ids.clone()));
+        for target in tag.s_targets.iter() {
+            builder.insert(target.get_type_id() as u32, item.clone(), &ids);
+        }
+    }
+
+    HashTagIndex::new(tags, builder.build(), trie)
+}
+
+/// Loads hashtags from a file containing one tag per line in the format
+/// `<tag> <frequency> [<search target>...]`
+pub fn load_tags<P: AsRef<Path>>(path: P) -> Result<Vec<RawHashtag>, Box<dyn Error + Send + Sync>> {
+    let mut out = vec![];
+
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if let Ok(tag) = RawHashtag::from_str(&line) {
+            out.push(tag);
+        }
+    }
+
+    Ok(out)
+}
@@ -0,0 +1,46 @@
+use index_framework::{
+    backend::memory::build::MemIndexBuilder,
+    traits::{backend::Backend, build::IndexBuilder as _, deser::DeSer},
+};
+use std::marker::PhantomData;
+
+/// Generic builder for in-memory indexes (`MemBackend`, `NGIndex`, `VSMIndexSimple`). Terms have
+/// to be inserted first in order to get their IDs which are required to build documents
+pub struct IndexBuilder<B, T, S> {
+    builder: MemIndexBuilder<B, T, S>,
+    p: PhantomData<B>,
+}
+
+impl<B, T, S> IndexBuilder<B, T, S>
+where
+    B: Backend<T, S>,
+    T: DeSer + Ord + From<String>,
+    S: DeSer,
+{
+    /// Creates a new builder for an index with `postings` posting lists
+    pub fn new(postings: usize) -> Self {
+        Self {
+            builder: MemIndexBuilder::new(postings),
+            p: PhantomData,
+        }
+    }
+
+    /// Inserts a term into the dictionary and returns its ID. Returns the ID of the existing
+    /// term if `term` was inserted before
+    #[inline]
+    pub fn term_id<A: AsRef<str>>(&mut self, term: A) -> u32 {
+        self.builder.insert_term(T::from(term.as_ref().to_string()))
+    }
+
+    /// Inserts a document with its term IDs into the posting list `posting`
+    #[inline]
+    pub fn insert(&mut self, posting: u32, doc: S, terms: &[u32]) {
+        self.builder.insert_item(posting, doc, terms);
+    }
+
+    /// Builds the index
+    #[inline]
+    pub fn build(self) -> B {
+        self.builder.build()
+    }
+}
@@ -0,0 +1,34 @@
+use super::{index::IndexBuilder, ngrams};
+use crate::kanji::meaning::{self, format_meaning, stem};
+use ngindex::{item::IndexItem, termset::TermSet};
+use resources::ResourceStorage;
+use types::jotoba::language::Language;
+
+/// Builds the kanji meaning index containing the meanings of all languages
+pub fn meaning(storage: &ResourceStorage) -> meaning::Index {
+    let mut builder = IndexBuilder::<meaning::Index, _, _>::new(meaning::POSTINGS);
+
+    for kanji in storage.kanji().iter() {
+        for language in Language::iter() {
+            let posting = meaning::posting(language);
+
+            for meaning in kanji.meanings_in(language) {
+                let meaning = stem(&format_meaning(meaning));
+                if meaning.is_empty() {
+                    continue;
+                }
+
+                let mut ids: Vec<u32> = ngrams(&meaning, meaning::NGRAM)
+                    .map(|i| builder.term_id(i))
+                    .collect();
+                ids.sort_unstable();
+                ids.dedup();
+
+                let item = IndexItem::new(kanji.literal as u32, TermSet::new(ids.clone()));
+                builder.insert(posting, item, &ids);
+            }
+        }
+    }
+
+    builder.build()
+}
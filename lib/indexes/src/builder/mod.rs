@@ -0,0 +1,143 @@
+//! Builds the search indexes from a [`ResourceStorage`] so changes of the dictionary data become
+//! searchable without prebuilt index files.
+//!
+//! The radical and suggestion indexes aren't derived from the resources and are left untouched,
+//! except for the hashtag index which can be rebuilt from a list of hashtags.
+
+pub mod hashtag;
+pub mod index;
+pub mod kanji;
+pub mod names;
+pub mod sentences;
+pub mod words;
+
+use crate::{
+    kanji::{self as kanji_types, reading_freq::FrequencyIndex},
+    names as name_types,
+    regex::RegexSearchIndex,
+    sentences as sentence_types,
+    storage::{
+        kanji::{K_MEANING_FILE, K_READINGS_FREQ_FILE},
+        name, sentence, word,
+    },
+    words::{ForeignIndex, NativeIndex},
+};
+use log::debug;
+use ngindex::{utils::padded, Wordgrams};
+use resources::ResourceStorage;
+use serde::Serialize;
+use sparse_vec::{SpVec32, VecExt};
+use std::{collections::HashMap, error::Error, fs::File, io::BufWriter, path::Path};
+use types::jotoba::language::Language;
+
+/// All indexes that can be built from a [`ResourceStorage`]
+pub struct Indexes {
+    pub word_foreign: HashMap<Language, ForeignIndex>,
+    pub word_native: NativeIndex,
+    pub word_regex: RegexSearchIndex,
+    pub word_k_reading: kanji_types::reading::Index,
+    pub kreading_freq: FrequencyIndex,
+    pub kanji_meaning: kanji_types::meaning::Index,
+    pub sentence_native: sentence_types::NativeIndex,
+    pub sentence_foreign: sentence_types::ForeignIndex,
+    pub name_native: name_types::NativeIndex,
+    pub name_foreign: name_types::ForeignIndex,
+}
+
+/// Builds all indexes from `storage`. The sentence_reader parser has to be loaded in order to
+/// build the japanese sentence index
+pub fn build(storage: &ResourceStorage) -> Indexes {
+    debug!("Building foreign word indexes");
+    let word_foreign = Language::iter_word()
+        .map(|lang| (lang, words::foreign(storage, lang)))
+        .collect();
+
+    debug!("Building japanese word index");
+    let word_native = words::native(storage);
+    let word_regex = words::regex(storage);
+
+    debug!("Building kanji reading indexes");
+    let word_k_reading = words::kanji_reading(storage);
+    let kreading_freq = words::reading_freq(storage);
+
+    debug!("Building kanji meaning index");
+    let kanji_meaning = kanji::meaning(storage);
+
+    debug!("Building sentence indexes");
+    let sentence_native = sentences::native(storage);
+    let sentence_foreign = sentences::foreign(storage);
+
+    debug!("Building name indexes");
+    let name_native = names::native(storage);
+    let name_foreign = names::foreign(storage);
+
+    Indexes {
+        word_foreign,
+        word_native,
+        word_regex,
+        word_k_reading,
+        kreading_freq,
+        kanji_meaning,
+        sentence_native,
+        sentence_foreign,
+        name_native,
+        name_foreign,
+    }
+}
+
+impl Indexes {
+    /// Writes all indexes into `index_folder` using the file names expected by
+    /// [`crate::storage::load`]
+    pub fn write<P: AsRef<Path>>(
+        &self,
+        index_folder: P,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let dir = index_folder.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        for (lang, index) in self.word_foreign.iter() {
+            let file = format!("{}{}", word::FOREIGN_PREFIX, lang.to_query_format());
+            ser_file(dir, &file, index)?;
+        }
+
+        ser_file(dir, word::NATIVE_FILE, &self.word_native)?;
+        let regex_file = BufWriter::new(File::create(dir.join(word::REGEX_FILE))?);
+        self.word_regex.write(regex_file)?;
+        ser_file(dir, word::KANJI_READING_INDEX, &self.word_k_reading)?;
+        ser_file(dir, K_READINGS_FREQ_FILE, &self.kreading_freq)?;
+        ser_file(dir, K_MEANING_FILE, &self.kanji_meaning)?;
+        ser_file(dir, sentence::NATIVE_FILE, &self.sentence_native)?;
+        ser_file(dir, sentence::FOREIGN_FILE, &self.sentence_foreign)?;
+        ser_file(dir, name::NATIVE_FILE, &self.name_native)?;
+        ser_file(dir, name::FOREIGN_FILE, &self.name_foreign)?;
+
+        Ok(())
+    }
+}
+
+/// Serializes `data` into the file `name` within `dir`
+fn ser_file<T: Serialize>(
+    dir: &Path,
+    name: &str,
+    data: &T,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let writer = BufWriter::new(File::create(dir.join(name))?);
+    bincode::serialize_into(writer, data)?;
+    Ok(())
+}
+
+/// Returns all padded ngrams of `inp` the same way they get generated for search queries
+pub(crate) fn ngrams(inp: &str, n: usize) -> impl Iterator<Item = String> {
+    let padded = padded(inp, n - 1);
+    Wordgrams::new(&padded, n)
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// Converts term weights into a sparse vector
+pub(crate) fn to_vec(terms: HashMap<u32, f32>) -> SpVec32 {
+    let mut terms: Vec<_> = terms.into_iter().collect();
+    terms.sort_unstable_by_key(|i| i.0);
+    SpVec32::create_new_raw(terms)
+}
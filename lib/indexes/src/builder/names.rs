@@ -0,0 +1,88 @@
+use super::{index::IndexBuilder, ngrams};
+use crate::names::{ForeignIndex, NativeIndex, FOREIGN_NGRAM, NATIVE_NGRAM};
+use index_framework::traits::backend::Backend;
+use jp_utils::JapaneseExt;
+use ngindex::{item::IndexItem, termset::TermSet};
+use resources::ResourceStorage;
+
+/// Posting list for kana readings
+const KANA_POSTING: u32 = 0;
+/// Posting list for readings containing kanji and kana
+const MIXED_POSTING: u32 = 1;
+/// Posting list for kanji only readings
+const KANJI_POSTING: u32 = 2;
+
+/// Builds the japanese name index. Readings get split into posting lists depending on whether
+/// they contain kanji
+pub fn native(storage: &ResourceStorage) -> NativeIndex {
+    let mut builder = IndexBuilder::<NativeIndex, _, _>::new(3);
+
+    for name in storage.names().iter() {
+        insert(
+            &mut builder,
+            KANA_POSTING,
+            name.sequence,
+            &name.kana,
+            NATIVE_NGRAM,
+        );
+
+        if let Some(ref kanji) = name.kanji {
+            let posting = if kanji.is_kanji() {
+                KANJI_POSTING
+            } else {
+                MIXED_POSTING
+            };
+            insert(&mut builder, posting, name.sequence, kanji, NATIVE_NGRAM);
+        }
+    }
+
+    builder.build()
+}
+
+/// Builds the name transcription index
+pub fn foreign(storage: &ResourceStorage) -> ForeignIndex {
+    let mut builder = IndexBuilder::<ForeignIndex, _, _>::new(1);
+
+    for name in storage.names().iter() {
+        let transcription = format_transcription(&name.transcription);
+        insert(
+            &mut builder,
+            0,
+            name.sequence,
+            &transcription,
+            FOREIGN_NGRAM,
+        );
+    }
+
+    builder.build()
+}
+
+fn insert<B>(
+    builder: &mut IndexBuilder<B, String, IndexItem<u32>>,
+    posting: u32,
+    seq: u32,
+    term: &str,
+    n: usize,
+) where
+    B: Backend<String, IndexItem<u32>>,
+{
+    if term.trim().is_empty() {
+        return;
+    }
+
+    let mut ids: Vec<u32> = ngrams(term, n).map(|i| builder.term_id(i)).collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let item = IndexItem::new(seq, TermSet::new(ids.clone()));
+    builder.insert(posting, item, &ids);
+}
+
+/// Formats a transcription the same way search queries get formatted
+fn format_transcription(inp: &str) -> String {
+    let mut out = inp.to_lowercase();
+    for i in ".,[]() \t\"'\\/-;:".chars() {
+        out = out.replace(i, " ");
+    }
+    out
+}
@@ -0,0 +1,97 @@
+use super::{index::IndexBuilder, to_vec};
+use crate::sentences::{ForeignIndex, NativeIndex};
+use jp_utils::JapaneseExt;
+use resources::ResourceStorage;
+use sentence_reader::output::ParseResult;
+use std::collections::{HashMap, HashSet};
+use types::jotoba::{language::Language, sentences::Sentence};
+use vsm::doc_vec::DocVector;
+
+/// Builds the japanese sentence index. Sentences get inserted into the posting lists of all
+/// languages they have a translation for. Requires the sentence_reader parser to be loaded
+pub fn native(storage: &ResourceStorage) -> NativeIndex {
+    let mut builder = IndexBuilder::<NativeIndex, _, _>::new(posting_count());
+
+    for sentence in storage.sentences().iter() {
+        let terms: HashMap<u32, f32> = native_terms(&sentence.japanese)
+            .into_iter()
+            .map(|i| (builder.term_id(i), 1.0))
+            .collect();
+        if terms.is_empty() {
+            continue;
+        }
+
+        let ids: Vec<u32> = terms.keys().copied().collect();
+        let doc = DocVector::new(sentence.id, to_vec(terms));
+        for language in languages(sentence) {
+            builder.insert(language as u32, doc.clone(), &ids);
+        }
+    }
+
+    builder.build()
+}
+
+/// Builds the foreign sentence index with one posting list per language
+pub fn foreign(storage: &ResourceStorage) -> ForeignIndex {
+    let mut builder = IndexBuilder::<ForeignIndex, _, _>::new(posting_count());
+
+    for sentence in storage.sentences().iter() {
+        for translation in sentence.translations.iter() {
+            let text = translation.text.to_lowercase();
+
+            let mut terms: HashMap<u32, f32> = all_terms(&text)
+                .into_iter()
+                .map(|i| (builder.term_id(i), 1.0))
+                .collect();
+            terms.insert(builder.term_id(&text), 1.0);
+
+            let ids: Vec<u32> = terms.keys().copied().collect();
+            let doc = DocVector::new(sentence.id, to_vec(terms));
+            builder.insert(translation.language as u32, doc, &ids);
+        }
+    }
+
+    builder.build()
+}
+
+/// Returns all (inflected and normalized) words of a japanese sentence
+fn native_terms(japanese: &str) -> HashSet<String> {
+    let mut terms = HashSet::new();
+
+    match sentence_reader::Parser::new(japanese).parse() {
+        ParseResult::Sentence(s) => {
+            terms.extend(s.iter().map(|i| i.get_inflected()));
+            terms.extend(s.iter().map(|i| i.get_normalized()));
+        }
+        ParseResult::InflectedWord(w) => {
+            terms.insert(w.get_inflected());
+            terms.insert(w.get_normalized());
+        }
+        ParseResult::None => (),
+    }
+
+    terms.into_iter().map(|i| i.to_halfwidth()).collect()
+}
+
+/// Splits a translation into its words
+fn all_terms(text: &str) -> Vec<String> {
+    let mut out = text.to_string();
+    for i in ".,[]() \t\"'\\/-;:".chars() {
+        out = out.replace(i, " ");
+    }
+    out.split(' ')
+        .filter(|i| !i.is_empty())
+        .map(|i| i.to_string())
+        .collect()
+}
+
+/// Returns all languages a sentence has a translation for
+fn languages(sentence: &Sentence) -> HashSet<Language> {
+    sentence.translations.iter().map(|i| i.language).collect()
+}
+
+/// Amount of posting lists required to have one list for each language
+#[inline]
+fn posting_count() -> usize {
+    Language::iter_word().count()
+}
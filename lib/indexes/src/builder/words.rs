@@ -0,0 +1,201 @@
+use super::{index::IndexBuilder, ngrams, to_vec};
+use crate::{
+    kanji::{self, reading_freq::FrequencyIndex},
+    ng_freq::NgFreqIndex,
+    regex::RegexSearchIndex,
+    words::{
+        foreign::{WordVecIndex, NG_FREQ_N},
+        native::{self, N as NATIVE_NGRAM},
+        ForeignIndex, NativeIndex,
+    },
+};
+use japanese::ToKanaExt;
+use jp_utils::furi::{segment::AsSegment, Furigana};
+use ngindex::{item::IndexItem, termset::TermSet};
+use resources::ResourceStorage;
+use std::collections::HashMap;
+use types::jotoba::{language::Language, words::Word};
+use vsm::doc_vec::DocVector;
+
+/// Weight of single words within a gloss compared to the whole gloss
+const GLOSS_WORD_WEIGHT: f32 = 0.001;
+
+/// Builds the foreign word index for `language`
+pub fn foreign(storage: &ResourceStorage, language: Language) -> ForeignIndex {
+    let mut builder = IndexBuilder::<WordVecIndex, _, _>::new(1);
+    let mut ng_index = NgFreqIndex::new(NG_FREQ_N);
+
+    for word in storage.words().iter() {
+        let mut terms: HashMap<u32, f32> = HashMap::new();
+
+        let glosses = word
+            .senses
+            .iter()
+            .filter(|i| i.language == language)
+            .flat_map(|i| i.glosses.iter());
+
+        for gloss in glosses {
+            let gloss = format_gloss(&gloss.gloss);
+            if gloss.is_empty() {
+                continue;
+            }
+
+            ng_index.insert(&gloss);
+
+            for term in gloss.split(' ').map(|i| i.trim()).filter(|i| !i.is_empty()) {
+                let id = builder.term_id(term);
+                terms.entry(id).or_insert(GLOSS_WORD_WEIGHT);
+            }
+
+            terms.insert(builder.term_id(&gloss), 1.0);
+        }
+
+        if terms.is_empty() {
+            continue;
+        }
+
+        let ids: Vec<u32> = terms.keys().copied().collect();
+        let doc = DocVector::new(word.sequence, to_vec(terms));
+        builder.insert(0, doc, &ids);
+    }
+
+    ForeignIndex::new(builder.build(), ng_index)
+}
+
+/// Builds the japanese word index
+pub fn native(storage: &ResourceStorage) -> NativeIndex {
+    let mut builder = IndexBuilder::<native::WordVecIndex, _, _>::new(1);
+    let mut tf_index = NgFreqIndex::new(NATIVE_NGRAM);
+
+    for word in storage.words().iter() {
+        for reading in word.reading.iter(true) {
+            let reading = &reading.reading;
+            tf_index.insert(reading);
+
+            let mut ids: Vec<u32> = ngrams(reading, NATIVE_NGRAM)
+                .map(|i| builder.term_id(i))
+                .collect();
+            ids.sort_unstable();
+            ids.dedup();
+
+            let item = IndexItem::new(word.sequence, TermSet::new(ids.clone()));
+            builder.insert(0, item, &ids);
+        }
+    }
+
+    NativeIndex::new(builder.build(), tf_index)
+}
+
+/// Builds the regex index containing all characters of all readings
+pub fn regex(storage: &ResourceStorage) -> RegexSearchIndex {
+    let mut index = RegexSearchIndex::new();
+
+    for word in storage.words().iter() {
+        for reading in word.reading.iter(true) {
+            index.add_term(&reading.reading, word.sequence);
+        }
+    }
+
+    index
+}
+
+/// Builds the kanji reading index mapping `<literal><reading>` to all words using the kanji
+/// with the reading
+pub fn kanji_reading(storage: &ResourceStorage) -> kanji::reading::Index {
+    let mut builder = IndexBuilder::<kanji::reading::Index, String, u32>::new(1);
+    let kanji_retr = storage.kanji();
+
+    for word in storage.words().iter() {
+        for (literal, kana) in kanji_readings(word) {
+            let kanji = match kanji_retr.by_literal(literal) {
+                Some(k) => k,
+                None => continue,
+            };
+
+            let on = kanji.onyomi.iter().filter(|i| i.to_hiragana() == kana);
+            let kun = kanji.kunyomi.iter().filter(|i| kun_stem(i) == kana);
+
+            for reading in on.chain(kun) {
+                let id = builder.term_id(format!("{literal}{reading}"));
+                builder.insert(0, word.sequence, &[id]);
+            }
+        }
+    }
+
+    builder.build()
+}
+
+/// Builds the kanji reading frequency index by counting kanji readings of all words
+pub fn reading_freq(storage: &ResourceStorage) -> FrequencyIndex {
+    let kanji: Vec<_> = storage.kanji().iter().cloned().collect();
+    let mut index = FrequencyIndex::new(&kanji);
+
+    for word in storage.words().iter() {
+        for (literal, kana) in kanji_readings(word) {
+            index.add_reading(literal, |r| r.to_hiragana() == kana || kun_stem(r) == kana);
+        }
+    }
+
+    index.clear();
+    index
+}
+
+/// Returns all single kanji along with their (hiragana) reading of a words furigana
+fn kanji_readings(word: &Word) -> Vec<(char, String)> {
+    let furi = match word.furigana.as_ref() {
+        Some(f) => f,
+        None => return vec![],
+    };
+
+    let mut out = vec![];
+    for segment in Furigana(furi).segments() {
+        let Some(kanji) = segment.as_kanji() else {
+            continue;
+        };
+        let literals = kanji.literals();
+        if literals.chars().count() != 1 {
+            continue;
+        }
+        let literal = literals.chars().next().unwrap();
+        out.push((literal, segment.get_kana_reading().to_hiragana()));
+    }
+    out
+}
+
+/// Returns the part of a kunyomi reading which is written with the kanji (eg. "はな.す" => "はな")
+#[inline]
+fn kun_stem(reading: &str) -> String {
+    reading
+        .split('.')
+        .next()
+        .unwrap_or(reading)
+        .replace('-', "")
+        .to_hiragana()
+}
+
+/// Formats a gloss the same way search queries get formatted
+fn format_gloss(gloss: &str) -> String {
+    let mut out = gloss.to_lowercase();
+    for i in ".,[]() \t\"'\\/-;:・".chars() {
+        out = out.replace(i, " ");
+    }
+    let out = out.trim();
+    out.strip_prefix("to ").unwrap_or(out).trim().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_gloss() {
+        assert_eq!(format_gloss("to eat"), "eat");
+        assert_eq!(format_gloss("House (building)"), "house  building");
+    }
+
+    #[test]
+    fn test_kun_stem() {
+        assert_eq!(kun_stem("はな.す"), "はな");
+        assert_eq!(kun_stem("-がわ"), "がわ");
+    }
+}
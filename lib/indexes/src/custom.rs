@@ -0,0 +1,219 @@
+//! Index for words of custom dictionaries. Custom dictionaries get loaded at startup and aren't
+//! part of the prebuilt index files, so they're kept in a separate, small in-memory index.
+//!
+//! Unlike the n-gram index of the native word search, readings are only matched exactly or by
+//! their beginning. Misspelled readings or terms in the middle of a reading don't find custom
+//! words.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Bound,
+    sync::Arc,
+};
+use types::jotoba::{language::Language, words::Word};
+use utils::swap_cell::SwapCell;
+
+/// In-memory index for custom words
+static CUSTOM_INDEX: SwapCell<CustomIndex> = SwapCell::new();
+
+/// Max amount of words to find by the beginning of their reading
+const MAX_PREFIX_MATCHES: usize = 100;
+
+/// Maps readings and glosses of custom words to their sequence ids
+#[derive(Default)]
+pub struct CustomIndex {
+    /// Sorted by reading, so readings starting with a term are next to each other
+    native: BTreeMap<String, Vec<u32>>,
+    gloss: HashMap<(Language, String), Vec<u32>>,
+    gloss_words: HashMap<(Language, String), Vec<u32>>,
+}
+
+impl CustomIndex {
+    /// Builds a new index out of `words`
+    pub fn new<'a, I: IntoIterator<Item = &'a Word>>(words: I) -> Self {
+        let mut index = Self::default();
+        for word in words {
+            index.insert(word);
+        }
+        index
+    }
+
+    /// Returns the sequence ids of all words with a reading starting with `term` together with a
+    /// relevance multiplier. Words with a reading equal to `term` are preferred
+    pub fn find_native(&self, term: &str) -> Vec<(u32, f32)> {
+        if term.is_empty() {
+            return vec![];
+        }
+
+        let mut out: Vec<(u32, f32)> = self
+            .native
+            .get(term)
+            .into_iter()
+            .flatten()
+            .map(|i| (*i, 1.0))
+            .collect();
+
+        let prefixed = self
+            .native
+            .range::<str, _>((Bound::Excluded(term), Bound::Unbounded))
+            .take_while(|(reading, _)| reading.starts_with(term))
+            .flat_map(|(_, ids)| ids.iter());
+        for seq in prefixed {
+            if out.len() >= MAX_PREFIX_MATCHES {
+                break;
+            }
+            if !out.iter().any(|i| i.0 == *seq) {
+                out.push((*seq, 0.5));
+            }
+        }
+
+        out
+    }
+
+    /// Returns the sequence ids of all words with a gloss matching `term` in `language` together
+    /// with a relevance multiplier. Matches of the whole gloss are preferred over matches of
+    /// single words within a gloss
+    pub fn find_foreign(&self, term: &str, language: Language) -> Vec<(u32, f32)> {
+        let key = (language, term.trim().to_lowercase());
+
+        let mut out: Vec<(u32, f32)> = self
+            .gloss
+            .get(&key)
+            .into_iter()
+            .flatten()
+            .map(|i| (*i, 1.0))
+            .collect();
+
+        for seq in self.gloss_words.get(&key).into_iter().flatten() {
+            if !out.iter().any(|i| i.0 == *seq) {
+                out.push((*seq, 0.5));
+            }
+        }
+
+        out
+    }
+
+    /// Returns `true` if the index doesn't contain any words
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.native.is_empty() && self.gloss.is_empty()
+    }
+
+    fn insert(&mut self, word: &Word) {
+        let seq = word.sequence;
+
+        for reading in word.reading_iter(true) {
+            push_unique(self.native.entry(reading.reading.clone()).or_default(), seq);
+        }
+
+        for sense in word.senses.iter() {
+            for gloss in sense.glosses.iter() {
+                let gloss = gloss.gloss.to_lowercase();
+
+                for term in gloss.split(|c: char| !c.is_alphanumeric()) {
+                    if term.is_empty() || term == gloss {
+                        continue;
+                    }
+                    let key = (sense.language, term.to_string());
+                    push_unique(self.gloss_words.entry(key).or_default(), seq);
+                }
+
+                let key = (sense.language, gloss);
+                push_unique(self.gloss.entry(key).or_default(), seq);
+            }
+        }
+    }
+}
+
+#[inline]
+fn push_unique(ids: &mut Vec<u32>, seq: u32) {
+    if !ids.contains(&seq) {
+        ids.push(seq);
+    }
+}
+
+/// Sets the custom word index. Does nothing if it was set already
+pub fn set(index: CustomIndex) {
+    CUSTOM_INDEX.set(index);
+}
+
+/// Replaces the custom word index and returns the previous one. Has to be called within
+/// [`utils::swap_cell::swap_all`]
+pub fn swap(index: CustomIndex) -> Option<Arc<CustomIndex>> {
+    CUSTOM_INDEX.swap(index)
+}
+
+/// Returns the custom word index or `None` if no custom words were loaded
+#[inline]
+pub fn get() -> Option<&'static CustomIndex> {
+    CUSTOM_INDEX.get().filter(|i| !i.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::jotoba::words::{
+        reading::Reading,
+        sense::{Gloss, Sense},
+        Dict,
+    };
+
+    fn word(seq: u32, kana: &str, gloss: &str) -> Word {
+        Word {
+            sequence: seq,
+            reading: Reading {
+                kana: Dict {
+                    reading: kana.to_string(),
+                    ..Dict::default()
+                },
+                ..Reading::default()
+            },
+            senses: vec![Sense {
+                glosses: vec![Gloss {
+                    gloss: gloss.to_string(),
+                    ..Gloss::default()
+                }],
+                ..Sense::default()
+            }],
+            ..Word::default()
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let words = [word(1, "てすと", "Test run"), word(2, "ほか", "test")];
+        let index = CustomIndex::new(words.iter());
+
+        assert_eq!(index.find_native("てすと"), vec![(1, 1.0)]);
+        assert!(index.find_native("ほげ").is_empty());
+
+        assert_eq!(
+            index.find_foreign("test", Language::English),
+            vec![(2, 1.0), (1, 0.5)]
+        );
+        assert_eq!(
+            index.find_foreign("Test Run", Language::English),
+            vec![(1, 1.0)]
+        );
+        assert!(index.find_foreign("test", Language::German).is_empty());
+    }
+
+    #[test]
+    fn test_find_native_partial() {
+        let words = [
+            word(1, "てすと", "test"),
+            word(2, "てすとらん", "test run"),
+            word(3, "すと", "strike"),
+        ];
+        let index = CustomIndex::new(words.iter());
+
+        // Readings starting with the term are found as well, but rank below exact matches
+        assert_eq!(index.find_native("てすと"), vec![(1, 1.0), (2, 0.5)]);
+        assert_eq!(index.find_native("てす"), vec![(1, 0.5), (2, 0.5)]);
+
+        // Neither terms in the middle of a reading nor misspelled readings are matched
+        assert!(index.find_native("すとら").is_empty());
+        assert!(index.find_native("てずと").is_empty());
+        assert!(index.find_native("").is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+//! Index for finding kanji by their meanings. Each meaning is stored as separate item within the
+//! posting list of its language and references the kanji by its literal.
+
+use types::jotoba::language::Language;
+
+pub const NGRAM: usize = 3;
+pub type Index = ngindex::NgramIndex<NGRAM, u32>;
+
+/// Amount of posting lists in the index
+pub const POSTINGS: usize = 10;
+
+/// Returns the posting list containing the meanings in `language`
+#[inline]
+pub fn posting(language: Language) -> u32 {
+    language as u32
+}
+
+/// Formats a meaning the same way for indexing and searching
+pub fn format_meaning(inp: &str) -> String {
+    let mut out = inp.to_lowercase();
+    for i in ".,[]()\t\"'\\/-;:".chars() {
+        out = out.replace(i, " ");
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Strips common english inflection suffixes of each word in `inp`. Meanings get stemmed when
+/// indexing and queries when searching, so stems don't have to be proper words as long as both
+/// end up the same (eg. "make" and "making" both become "mak")
+pub fn stem(inp: &str) -> String {
+    inp.split(' ').map(stem_word).collect::<Vec<_>>().join(" ")
+}
+
+fn stem_word(word: &str) -> String {
+    if !word.is_ascii() || word.len() <= 3 {
+        return word.to_string();
+    }
+
+    if let Some(stem) = word.strip_suffix("ies").filter(|i| i.len() >= 2) {
+        return format!("{stem}y");
+    }
+
+    for suffix in ["ing", "ed"] {
+        if let Some(stem) = word.strip_suffix(suffix).filter(|i| i.len() >= 3) {
+            return strip_e(undouble(stem)).to_string();
+        }
+    }
+
+    let plural = word.ends_with('s') && !["ss", "us", "is"].iter().any(|i| word.ends_with(i));
+    let word = if plural {
+        &word[..word.len() - 1]
+    } else {
+        word
+    };
+    strip_e(word).to_string()
+}
+
+/// Removes the doubled consonant of stems like "runn" or "stopp"
+fn undouble(stem: &str) -> &str {
+    let b = stem.as_bytes();
+    let last = b[b.len() - 1];
+    if stem.len() > 3 && b[b.len() - 2] == last && !b"aeioulsz".contains(&last) {
+        return &stem[..stem.len() - 1];
+    }
+    stem
+}
+
+/// Removes a trailing "e" which gets dropped by suffixes like "ing" (eg. "make" => "making")
+fn strip_e(word: &str) -> &str {
+    match word.strip_suffix('e') {
+        Some(stem) if stem.len() >= 3 => stem,
+        _ => word,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        assert_eq!(stem("cats"), "cat");
+        assert_eq!(stem("cat"), "cat");
+        assert_eq!(stem("glass"), "glass");
+        assert_eq!(stem("bodies"), "body");
+        assert_eq!(stem("running fast"), "run fast");
+        assert_eq!(stem("status"), "status");
+        assert_eq!(stem("add"), stem("added"));
+    }
+
+    #[test]
+    fn test_stem_same_as_inflected() {
+        let same = [
+            ("run", "running"),
+            ("stop", "stopped"),
+            ("make", "making"),
+            ("make", "makes"),
+            ("fall", "falling"),
+            ("box", "boxes"),
+            ("glass", "glasses"),
+            ("body", "bodies"),
+            ("tree", "trees"),
+        ];
+        for (word, inflected) in same {
+            assert_eq!(stem(word), stem(inflected), "{word} {inflected}");
+        }
+        assert_eq!(stem(&format_meaning("Running (fast)")), "run fast");
+    }
+
+    #[test]
+    fn test_format_meaning() {
+        assert_eq!(format_meaning("Cat (animal)"), "cat animal");
+        assert_eq!(format_meaning("  to-go "), "to go");
+    }
+
+    #[test]
+    fn test_postings() {
+        assert!(Language::iter().all(|i| (posting(i) as usize) < POSTINGS));
+    }
+}
@@ -1,2 +1,3 @@
+pub mod meaning;
 pub mod reading;
 pub mod reading_freq;
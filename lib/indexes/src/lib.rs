@@ -1,3 +1,6 @@
+#[cfg(feature = "builder")]
+pub mod builder;
+pub mod custom;
 pub mod hashtag;
 pub mod kanji;
 pub mod names;
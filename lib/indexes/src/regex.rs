@@ -1,10 +1,58 @@
 use serde::{Deserialize, Serialize};
-use std::collections::{hash_map::Iter, HashMap, HashSet};
+use std::{
+    collections::{hash_map::Iter, HashMap, HashSet},
+    error::Error,
+    io::{Read, Write},
+};
+
+/// Header of regex index files. Files written before the format got versioned don't have it
+const MAGIC: &[u8] = b"JOTOBA_REGEX";
+
+/// Version of the regex index layout. Has to be increased on each change of [`RegexSearchIndex`]
+pub const FORMAT_VERSION: u32 = 1;
 
 /// Index to allow fast and efficient regex search queries.
 #[derive(Serialize, Deserialize)]
 pub struct RegexSearchIndex {
     data: HashMap<char, HashSet<u32>>,
+    /// Maps the character count of a term to all words having a term with this length
+    lengths: HashMap<usize, HashSet<u32>>,
+    /// Maps a script to all words having a term containing a character of this script
+    scripts: HashMap<Script, HashSet<u32>>,
+}
+
+/// Scripts of Japanese characters which can be searched for without knowing the exact character
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Script {
+    Kanji,
+    Hiragana,
+    Katakana,
+}
+
+impl Script {
+    /// Returns the script of `c` or `None` if it isn't a Japanese character
+    pub fn of(c: char) -> Option<Self> {
+        Some(match c {
+            '\u{3041}'..='\u{309F}' => Self::Hiragana,
+            '\u{30A0}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' => Self::Katakana,
+            '\u{4E00}'..='\u{9FFF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '々'
+            | '〆' => Self::Kanji,
+            _ => return None,
+        })
+    }
+
+    /// Returns a regex character class matching a single character of the script. Uses explicit
+    /// ranges so the same characters match as the ones getting indexed by [`Script::of`]
+    pub fn regex_class(&self) -> &'static str {
+        match self {
+            Self::Kanji => "[\u{4E00}-\u{9FFF}\u{3400}-\u{4DBF}\u{F900}-\u{FAFF}々〆]",
+            Self::Hiragana => "[\u{3041}-\u{309F}]",
+            Self::Katakana => "[\u{30A0}-\u{30FF}\u{31F0}-\u{31FF}]",
+        }
+    }
 }
 
 impl RegexSearchIndex {
@@ -13,7 +61,36 @@ impl RegexSearchIndex {
     pub fn new() -> Self {
         RegexSearchIndex {
             data: HashMap::new(),
+            lengths: HashMap::new(),
+            scripts: HashMap::new(),
+        }
+    }
+
+    /// Reads a regex index written by [`RegexSearchIndex::write`]. Fails if the index has
+    /// another layout than the current one
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let outdated = "Outdated regex index. Rebuild the indexes to update it";
+
+        let mut magic = [0u8; MAGIC.len()];
+        if reader.read_exact(&mut magic).is_err() || magic != MAGIC {
+            return Err(outdated.into());
         }
+
+        let version: u32 = bincode::deserialize_from(&mut reader)?;
+        if version != FORMAT_VERSION {
+            let err = format!("{outdated} (version {version} instead of {FORMAT_VERSION})");
+            return Err(err.into());
+        }
+
+        Ok(bincode::deserialize_from(reader)?)
+    }
+
+    /// Writes the index along with its format version
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), Box<dyn Error + Send + Sync>> {
+        writer.write_all(MAGIC)?;
+        bincode::serialize_into(&mut writer, &FORMAT_VERSION)?;
+        bincode::serialize_into(writer, self)?;
+        Ok(())
     }
 
     /// Returns an iterator over all items in the index
@@ -28,11 +105,78 @@ impl RegexSearchIndex {
         self.data.get(&character)
     }
 
+    /// Returns a HashSet with all words (seq_ids) having a term with `len` characters
+    #[inline(always)]
+    pub fn get_words_with_len(&self, len: usize) -> Option<&HashSet<u32>> {
+        self.lengths.get(&len)
+    }
+
+    /// Returns a HashSet with all words (seq_ids) containing a character of the given script
+    #[inline(always)]
+    pub fn get_words_with_script(&self, script: Script) -> Option<&HashSet<u32>> {
+        self.scripts.get(&script)
+    }
+
     /// Adds a new term to the index
     #[inline]
     pub fn add_term(&mut self, term: &str, seq_id: u32) {
+        let mut len = 0;
         for c in term.chars() {
             self.data.entry(c).or_default().insert(seq_id);
+            if let Some(script) = Script::of(c) {
+                self.scripts.entry(script).or_default().insert(seq_id);
+            }
+            len += 1;
         }
+        self.lengths.entry(len).or_default().insert(seq_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_script() {
+        assert_eq!(Script::of('日'), Some(Script::Kanji));
+        assert_eq!(Script::of('々'), Some(Script::Kanji));
+        assert_eq!(Script::of('か'), Some(Script::Hiragana));
+        assert_eq!(Script::of('カ'), Some(Script::Katakana));
+        assert_eq!(Script::of('ー'), Some(Script::Katakana));
+        assert_eq!(Script::of('a'), None);
+    }
+
+    #[test]
+    fn test_add_term() {
+        let mut index = RegexSearchIndex::new();
+        index.add_term("日本", 1);
+        index.add_term("にほん", 1);
+        index.add_term("テレビ", 2);
+
+        assert!(index.get_words_with_len(2).unwrap().contains(&1));
+        assert!(index.get_words_with_len(3).unwrap().contains(&2));
+        assert!(index.get_words_with_len(4).is_none());
+        assert_eq!(
+            index.get_words_with_script(Script::Katakana).unwrap().len(),
+            1
+        );
+        assert_eq!(index.get_words_with_script(Script::Kanji).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_versioned() {
+        let mut index = RegexSearchIndex::new();
+        index.add_term("日本", 1);
+
+        let mut data = vec![];
+        index.write(&mut data).unwrap();
+        let loaded = RegexSearchIndex::read(data.as_slice()).unwrap();
+        assert!(loaded.get_words_with('日').unwrap().contains(&1));
+        assert!(loaded.get_words_with_len(2).unwrap().contains(&1));
+
+        // Indexes without the lengths and scripts
+        let legacy: HashMap<char, HashSet<u32>> = index.data.clone();
+        let data = bincode::serialize(&legacy).unwrap();
+        assert!(RegexSearchIndex::read(data.as_slice()).is_err());
     }
 }
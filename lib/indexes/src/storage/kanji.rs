@@ -1,27 +1,49 @@
-use crate::kanji::reading_freq::FrequencyIndex;
+use super::utils;
+use crate::kanji::{meaning, reading_freq::FrequencyIndex};
+use log::warn;
 use std::{error::Error, fs::File, io::BufReader, path::Path};
 
 pub const K_READINGS_FREQ_FILE: &str = "kreading_freq_index";
+pub const K_MEANING_FILE: &str = "kanji_meaning_index";
 
-/// Store for name indexes
+/// Store for kanji indexes
 pub struct KanjiStore {
     kread_frequency: FrequencyIndex,
+    meaning: Option<meaning::Index>,
 }
 
 impl KanjiStore {
-    pub fn new(kread_frequency: FrequencyIndex) -> Self {
-        Self { kread_frequency }
+    pub fn new(kread_frequency: FrequencyIndex, meaning: Option<meaning::Index>) -> Self {
+        Self {
+            kread_frequency,
+            meaning,
+        }
     }
 
     #[inline(always)]
     pub fn reading_freq(&self) -> &FrequencyIndex {
         &self.kread_frequency
     }
+
+    /// Returns the kanji meaning index or `None` if it hasn't been built yet
+    #[inline(always)]
+    pub fn meaning(&self) -> Option<&meaning::Index> {
+        self.meaning.as_ref()
+    }
 }
 
 pub(crate) fn load<P: AsRef<Path>>(path: P) -> Result<KanjiStore, Box<dyn Error + Send + Sync>> {
     let kread_file = Path::new(path.as_ref()).join(K_READINGS_FREQ_FILE);
     let kread_frequency: FrequencyIndex =
         bincode::deserialize_from(BufReader::new(File::open(kread_file)?))?;
-    Ok(KanjiStore::new(kread_frequency))
+
+    // Older index folders don't contain the meaning index
+    let meaning = if path.as_ref().join(K_MEANING_FILE).exists() {
+        Some(utils::deser_file(path.as_ref(), K_MEANING_FILE)?)
+    } else {
+        warn!("Kanji meaning index not found. Falling back to plain meaning search");
+        None
+    };
+
+    Ok(KanjiStore::new(kread_frequency, meaning))
 }
@@ -6,15 +6,15 @@ pub mod suggestions;
 pub(crate) mod utils;
 pub mod word;
 
-use once_cell::sync::OnceCell;
-use std::{error::Error, path::Path};
+use ::utils::swap_cell::SwapCell;
+use std::{error::Error, path::Path, sync::Arc};
 use {
     kanji::KanjiStore, name::NameStore, radical::RadicalStore, sentence::SentenceStore,
     word::WordStore,
 };
 
-/// In-memory store for all indexes
-pub(crate) static INDEX_STORE: OnceCell<IndexStore> = OnceCell::new();
+/// In-memory store for all indexes. Can be replaced at runtime using [`swap`]
+pub(crate) static INDEX_STORE: SwapCell<IndexStore> = SwapCell::new();
 
 /// Store for all indexes
 pub struct IndexStore {
@@ -75,13 +75,19 @@ pub fn load<P: AsRef<Path>>(index_folder: P) -> Result<bool, Box<dyn Error + Sen
         return Ok(false);
     }
 
-    INDEX_STORE.set(store).ok();
+    INDEX_STORE.set(store);
 
     Ok(true)
 }
 
+/// Replaces the loaded indexes with `store` and returns the previous ones. Has to be called
+/// within [`::utils::swap_cell::swap_all`]
+pub fn swap(store: IndexStore) -> Option<Arc<IndexStore>> {
+    INDEX_STORE.swap(store)
+}
+
 pub fn is_loaded() -> bool {
-    INDEX_STORE.get().is_some()
+    INDEX_STORE.is_set()
 }
 
 /// Needed for tests only
@@ -4,7 +4,7 @@ use super::utils;
 use autocompletion::index::{basic::BasicIndex, japanese::JapaneseIndex};
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, error::Error, path::Path};
+use std::{collections::HashMap, error::Error, io::Write, path::Path};
 use types::jotoba::language::Language;
 
 pub const K_MEANING_NGRAM: usize = 3;
@@ -83,6 +83,12 @@ impl SuggestionStorage {
         &self.hashtag
     }
 
+    /// Replaces the hashtag index
+    #[inline]
+    pub fn set_hashtags(&mut self, hashtag: HashTagIndex) {
+        self.hashtag = hashtag;
+    }
+
     pub fn check(&self) -> bool {
         utils::check_lang_map(&self.foreign_words)
     }
@@ -94,6 +100,15 @@ pub fn load_raw<P: AsRef<Path>>(
     utils::deser_file(file, "")
 }
 
+/// Serializes a SuggestionStorage into `output`
+pub fn store<W: Write>(
+    output: W,
+    storage: &SuggestionStorage,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    bincode::serialize_into(output, storage)?;
+    Ok(())
+}
+
 pub fn load<P: AsRef<Path>>(path: P) -> Result<bool, Box<dyn Error + Sync + Send>> {
     let store = load_raw(path)?;
     Ok(SUGGESTION_STORE.set(store).is_ok())
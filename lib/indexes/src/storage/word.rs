@@ -5,7 +5,7 @@ use crate::{
     words::{ForeignIndex, NativeIndex},
 };
 use log::debug;
-use std::{collections::HashMap, error::Error, path::Path, str::FromStr};
+use std::{collections::HashMap, error::Error, fs::File, io::BufReader, path::Path, str::FromStr};
 use types::jotoba::language::Language;
 
 pub const FOREIGN_PREFIX: &str = "word_index_";
@@ -69,7 +69,7 @@ pub(crate) fn load<P: AsRef<Path>>(path: P) -> Result<WordStore, Box<dyn Error +
     let start = std::time::Instant::now();
     let foreign = load_foreign(path.as_ref())?;
     let native = utils::deser_file(path.as_ref(), NATIVE_FILE)?;
-    let regex = utils::deser_file(path.as_ref(), REGEX_FILE)?;
+    let regex = load_regex(path.as_ref())?;
     let k_reading = utils::deser_file(path.as_ref(), KANJI_READING_INDEX)?;
     debug!("Loading indexes sync took: {:?}", start.elapsed());
     Ok(WordStore::new(foreign, native, regex, k_reading))
@@ -92,7 +92,7 @@ pub(crate) fn load<P: AsRef<Path> + Send + Sync>(
             native = Some(utils::deser_file(path.as_ref(), NATIVE_FILE));
         });
         s.spawn(|_| {
-            regex = Some(utils::deser_file(path.as_ref(), REGEX_FILE));
+            regex = Some(load_regex(path.as_ref()));
         });
         s.spawn(|_| {
             k_reading = Some(utils::deser_file(path.as_ref(), KANJI_READING_INDEX));
@@ -106,6 +106,11 @@ pub(crate) fn load<P: AsRef<Path> + Send + Sync>(
     Ok(WordStore::new(foreign, native, regex, k_reading))
 }
 
+fn load_regex<P: AsRef<Path>>(path: P) -> Result<RegexSearchIndex, Box<dyn Error + Send + Sync>> {
+    let file = File::open(path.as_ref().join(REGEX_FILE))?;
+    RegexSearchIndex::read(BufReader::new(file))
+}
+
 fn load_foreign<P: AsRef<Path>>(
     path: P,
 ) -> Result<HashMap<Language, ForeignIndex>, Box<dyn Error + Send + Sync>> {
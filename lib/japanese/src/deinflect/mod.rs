@@ -0,0 +1,227 @@
+//! Rule based deinflection of japanese verbs and adjectives.
+//!
+//! Unlike the sentence reader this doesn't require any morphological analyzer. An inflected term
+//! gets deinflected by repeatedly replacing known inflection suffixes using a rule table, similar
+//! to the deinflectors of Yomichan and Rikaichan. Since the rules don't know about the actual
+//! words, the resulting candidates have to be validated against a dictionary using their
+//! [`WordClass`].
+
+pub mod rules;
+
+use once_cell::sync::Lazy;
+use rules::Rule;
+use std::ops::BitOr;
+
+/// Rule table used for deinflection
+static RULES: Lazy<Vec<Rule>> = Lazy::new(rules::build);
+
+/// Maximum amount of rules applied to a single term
+const MAX_DEPTH: usize = 6;
+
+/// Set of word classes a term conjugates like
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct WordClass(u8);
+
+impl WordClass {
+    pub const NONE: WordClass = WordClass(0);
+    /// Ichidan verb
+    pub const V1: WordClass = WordClass(1);
+    /// Godan verb
+    pub const V5: WordClass = WordClass(1 << 1);
+    /// Suru verb
+    pub const VS: WordClass = WordClass(1 << 2);
+    /// Kuru verb
+    pub const VK: WordClass = WordClass(1 << 3);
+    /// I-adjective
+    pub const ADJ_I: WordClass = WordClass(1 << 4);
+    /// Te form followed by an auxiliary. Never a dictionary form
+    pub const TE: WordClass = WordClass(1 << 5);
+
+    /// Returns `true` if both sets share at least one word class
+    #[inline]
+    pub fn intersects(&self, other: WordClass) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Returns `true` if no word class is set
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for WordClass {
+    type Output = WordClass;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        WordClass(self.0 | rhs.0)
+    }
+}
+
+/// A single inflection step undone by a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reason {
+    Negative,
+    Polite,
+    Past,
+    TeForm,
+    Potential,
+    Passive,
+    Causative,
+    CausativePassive,
+    PotentialOrPassive,
+    Imperative,
+    Tai,
+    TeIru,
+    TeAru,
+    TeMiru,
+    TeShimau,
+    Chau,
+    TeOku,
+    Toku,
+    Tara,
+    Tari,
+    Ba,
+}
+
+/// A candidate dictionary form of an inflected term
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deinflection {
+    /// The deinflected term
+    pub term: String,
+    /// Word class the dictionary form has to belong to
+    pub class: WordClass,
+    /// Applied inflections, ordered from the innermost to the outermost one
+    pub reasons: Vec<Reason>,
+}
+
+impl Deinflection {
+    /// Returns `true` if a word of `class` can be the dictionary form of this deinflection
+    #[inline]
+    pub fn matches(&self, class: WordClass) -> bool {
+        self.class.intersects(class)
+    }
+
+    /// Returns the noun of a suru verb (eg. 勉強 for 勉強する) which is listed as noun in
+    /// dictionaries
+    pub fn suru_noun(&self) -> Option<&str> {
+        if self.class != WordClass::VS {
+            return None;
+        }
+        self.term.strip_suffix("する").filter(|i| !i.is_empty())
+    }
+}
+
+/// Returns all possible dictionary forms of `term`. The same term can be returned multiple times
+/// with different word classes or inflections. Terms that aren't inflected aren't returned
+pub fn deinflect(term: &str) -> Vec<Deinflection> {
+    let mut out: Vec<Deinflection> = vec![];
+    let mut queue = vec![(term.to_string(), WordClass::NONE, vec![])];
+
+    for _ in 0..MAX_DEPTH {
+        let mut next = vec![];
+
+        for (term, class, reasons) in queue {
+            for rule in RULES.iter() {
+                if !applies(rule, &term, class) {
+                    continue;
+                }
+
+                let stem = &term[..term.len() - rule.from.len()];
+                let new_term = format!("{stem}{}", rule.to);
+
+                let mut new_reasons = rule.reasons.to_vec();
+                new_reasons.extend(reasons.iter().copied());
+
+                let item = (new_term, rule.rules_out, new_reasons);
+                if !next.contains(&item) {
+                    next.push(item);
+                }
+            }
+        }
+
+        for (term, class, reasons) in next.iter() {
+            if *class == WordClass::TE || reasons.is_empty() {
+                continue;
+            }
+
+            let item = Deinflection {
+                term: term.clone(),
+                class: *class,
+                reasons: reasons.clone(),
+            };
+            if !out.contains(&item) {
+                out.push(item);
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+        queue = next;
+    }
+
+    out
+}
+
+/// Returns `true` if `rule` can be applied to `term` of the word class `class`. Rules for te
+/// stems only apply to terms in the te class, all other rules apply to uninflected input
+#[inline]
+fn applies(rule: &Rule, term: &str, class: WordClass) -> bool {
+    if term.len() <= rule.from.len() || !term.ends_with(&rule.from) {
+        return false;
+    }
+
+    if class.is_empty() {
+        return rule.rules_in != WordClass::TE;
+    }
+
+    class.intersects(rule.rules_in)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    fn find(term: &str, dict_form: &str, class: WordClass) -> Option<Vec<Reason>> {
+        deinflect(term)
+            .into_iter()
+            .find(|i| i.term == dict_form && i.matches(class))
+            .map(|i| i.reasons)
+    }
+
+    #[test_case("食べなかった", "食べる", WordClass::V1, &[Reason::Negative, Reason::Past]; "ichidan negative past")]
+    #[test_case("書かない", "書く", WordClass::V5, &[Reason::Negative]; "godan negative")]
+    #[test_case("読んだ", "読む", WordClass::V5, &[Reason::Past]; "godan past")]
+    #[test_case("行って", "行く", WordClass::V5, &[Reason::TeForm]; "iku te form")]
+    #[test_case("食べています", "食べる", WordClass::V1, &[Reason::TeIru, Reason::Polite]; "te iru polite")]
+    #[test_case("食べてた", "食べる", WordClass::V1, &[Reason::TeIru, Reason::Past]; "contracted te iru")]
+    #[test_case("飲んじゃった", "飲む", WordClass::V5, &[Reason::Chau, Reason::Past]; "chau past")]
+    #[test_case("食べさせられた", "食べる", WordClass::V1, &[Reason::CausativePassive, Reason::Past]; "causative passive")]
+    #[test_case("書けない", "書く", WordClass::V5, &[Reason::Potential, Reason::Negative]; "potential negative")]
+    #[test_case("食べたくない", "食べる", WordClass::V1, &[Reason::Tai, Reason::Negative]; "tai negative")]
+    #[test_case("高かった", "高い", WordClass::ADJ_I, &[Reason::Past]; "adjective past")]
+    #[test_case("勉強しました", "勉強する", WordClass::VS, &[Reason::Polite, Reason::Past]; "suru polite past")]
+    #[test_case("来なかった", "来る", WordClass::VK, &[Reason::Negative, Reason::Past]; "kuru negative past")]
+    #[test_case("読めば", "読む", WordClass::V5, &[Reason::Ba]; "conditional")]
+    fn test_deinflect(term: &str, dict_form: &str, class: WordClass, reasons: &[Reason]) {
+        assert_eq!(find(term, dict_form, class).as_deref(), Some(reasons));
+    }
+
+    #[test]
+    fn test_not_inflected() {
+        assert!(find("食べる", "食べる", WordClass::V1).is_none());
+        assert!(deinflect("").is_empty());
+    }
+
+    #[test]
+    fn test_suru_noun() {
+        let d = deinflect("勉強した")
+            .into_iter()
+            .find(|i| i.class == WordClass::VS)
+            .unwrap();
+        assert_eq!(d.suru_noun(), Some("勉強"));
+    }
+}
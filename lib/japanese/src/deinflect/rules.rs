@@ -0,0 +1,235 @@
+use super::{Reason, WordClass};
+
+/// A single deinflection rule replacing the suffix `from` with `to`
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub from: String,
+    pub to: String,
+    /// Word class the inflected form conjugates like
+    pub rules_in: WordClass,
+    /// Word class of the deinflected term
+    pub rules_out: WordClass,
+    /// Inflections applied by this rule, ordered from the innermost to the outermost one
+    pub reasons: &'static [Reason],
+}
+
+/// Godan endings with their a, i and e stem as well as their te and ta form
+const GODAN: &[[&str; 6]] = &[
+    ["う", "わ", "い", "え", "って", "った"],
+    ["く", "か", "き", "け", "いて", "いた"],
+    ["ぐ", "が", "ぎ", "げ", "いで", "いだ"],
+    ["す", "さ", "し", "せ", "して", "した"],
+    ["つ", "た", "ち", "て", "って", "った"],
+    ["ぬ", "な", "に", "ね", "んで", "んだ"],
+    ["ぶ", "ば", "び", "べ", "んで", "んだ"],
+    ["む", "ま", "み", "め", "んで", "んだ"],
+    ["る", "ら", "り", "れ", "って", "った"],
+];
+
+const A: usize = 1;
+const I: usize = 2;
+const E: usize = 3;
+
+/// Builds the full deinflection rule table
+#[rustfmt::skip]
+pub(crate) fn build() -> Vec<Rule> {
+    let mut t = Table::default();
+    let any = WordClass::NONE;
+    let adj = WordClass::ADJ_I;
+    let v1 = WordClass::V1;
+    let v5 = WordClass::V5;
+    let te = WordClass::TE;
+
+    // Negative conjugates like an i-adjective
+    t.stem(A, "ない", &["ない"], &["しない"], &["こない"], adj, &[Reason::Negative]);
+    t.adj("くない", adj, adj, &[Reason::Negative]);
+
+    // Polite
+    t.stem(I, "ます", &["ます"], &["します"], &["きます"], any, &[Reason::Polite]);
+    t.stem(I, "ました", &["ました"], &["しました"], &["きました"], any, POLITE_PAST);
+    t.stem(I, "ません", &["ません"], &["しません"], &["きません"], any, POLITE_NEG);
+    t.stem(I, "ませんでした", &["ませんでした"], &["しませんでした"], &["きませんでした"], any, POLITE_NEG_PAST);
+
+    // Forms derived from the te and ta form
+    t.te_ta("た", false, any, &[Reason::Past]);
+    t.adj("かった", any, adj, &[Reason::Past]);
+    t.te_ta("て", true, any, &[Reason::TeForm]);
+    t.adj("くて", any, adj, &[Reason::TeForm]);
+    t.te_ta("たら", false, any, &[Reason::Tara]);
+    t.adj("かったら", any, adj, &[Reason::Tara]);
+    t.te_ta("たり", false, any, &[Reason::Tari]);
+    t.adj("かったり", any, adj, &[Reason::Tari]);
+
+    // Te stems of auxiliary constructions. These don't add an inflection on their own and only
+    // apply to terms of the te class
+    t.te_ta("て", true, te, &[]);
+
+    // Auxiliaries following the te form
+    t.te_aux("いる", v1, &[Reason::TeIru]);
+    t.te_aux("る", v1, &[Reason::TeIru]);
+    t.te_aux("ある", v5, &[Reason::TeAru]);
+    t.te_aux("みる", v1, &[Reason::TeMiru]);
+    t.te_aux("しまう", v5, &[Reason::TeShimau]);
+    t.te_aux("おく", v5, &[Reason::TeOku]);
+    t.push("ちゃう", "て", v5, te, &[Reason::Chau]);
+    t.push("じゃう", "で", v5, te, &[Reason::Chau]);
+    t.push("とく", "て", v5, te, &[Reason::Toku]);
+    t.push("どく", "で", v5, te, &[Reason::Toku]);
+
+    // Conditional
+    t.stem(E, "ば", &["れば"], &["すれば"], &["くれば"], any, &[Reason::Ba]);
+    t.adj("ければ", any, adj, &[Reason::Ba]);
+
+    // Tai conjugates like an i-adjective
+    t.stem(I, "たい", &["たい"], &["したい"], &["きたい"], adj, &[Reason::Tai]);
+
+    // Potential and passive forms conjugate like ichidan verbs
+    t.stem(E, "る", &["れる"], &[], &["これる"], v1, &[Reason::Potential]);
+    t.stem(A, "れる", &[], &["される"], &[], v1, &[Reason::Passive]);
+    t.stem(A, "", &["られる"], &[], &["こられる"], v1, &[Reason::PotentialOrPassive]);
+    t.stem(A, "せる", &["させる"], &["させる"], &["こさせる"], v1, &[Reason::Causative]);
+    t.stem(A, "せられる", &["させられる"], &["させられる"], &["こさせられる"], v1, &[Reason::CausativePassive]);
+    t.godan_short_causative_passive();
+
+    // Imperative
+    t.stem(E, "", &["ろ", "よ"], &["しろ", "せよ"], &["こい"], any, &[Reason::Imperative]);
+
+    t.rules
+}
+
+const POLITE_PAST: &[Reason] = &[Reason::Polite, Reason::Past];
+const POLITE_NEG: &[Reason] = &[Reason::Polite, Reason::Negative];
+const POLITE_NEG_PAST: &[Reason] = &[Reason::Polite, Reason::Negative, Reason::Past];
+
+#[derive(Default)]
+struct Table {
+    rules: Vec<Rule>,
+}
+
+impl Table {
+    fn push(
+        &mut self,
+        from: &str,
+        to: &str,
+        rules_in: WordClass,
+        rules_out: WordClass,
+        reasons: &'static [Reason],
+    ) {
+        self.rules.push(Rule {
+            from: from.to_string(),
+            to: to.to_string(),
+            rules_in,
+            rules_out,
+            reasons,
+        });
+    }
+
+    /// Adds rules for forms built from a verb stem. `godan` gets appended to the godan stem of
+    /// `row`, `v1`, `vs` and `vk` are full replacements of the ichidan る, する and くる. An empty
+    /// `godan` suffix only gets added if the stem itself isn't empty
+    #[allow(clippy::too_many_arguments)]
+    fn stem(
+        &mut self,
+        row: usize,
+        godan: &str,
+        v1: &[&str],
+        vs: &[&str],
+        vk: &[&str],
+        rules_in: WordClass,
+        reasons: &'static [Reason],
+    ) {
+        if !godan.is_empty() || row == E {
+            for ending in GODAN {
+                let from = format!("{}{godan}", ending[row]);
+                self.push(&from, ending[0], rules_in, WordClass::V5, reasons);
+            }
+        }
+
+        for from in v1 {
+            self.push(from, "る", rules_in, WordClass::V1, reasons);
+        }
+
+        for from in vs {
+            self.push(from, "する", rules_in, WordClass::VS, reasons);
+        }
+
+        for from in vk {
+            self.kuru(from, rules_in, reasons);
+        }
+    }
+
+    /// Adds rules for forms built from the te (`te = true`) or ta form followed by the rest of
+    /// `suffix`
+    fn te_ta(&mut self, suffix: &str, te: bool, rules_in: WordClass, reasons: &'static [Reason]) {
+        let form = suffix.trim_start_matches(['た', 'て']);
+        let (col, base) = if te { (4, "て") } else { (5, "た") };
+
+        for ending in GODAN {
+            let from = format!("{}{form}", ending[col]);
+            self.push(&from, ending[0], rules_in, WordClass::V5, reasons);
+        }
+
+        // 行く is irregular
+        for iku in ["行", "い"] {
+            let from = format!("{iku}っ{base}{form}");
+            self.push(&from, &format!("{iku}く"), rules_in, WordClass::V5, reasons);
+        }
+
+        self.push(
+            &format!("{base}{form}"),
+            "る",
+            rules_in,
+            WordClass::V1,
+            reasons,
+        );
+        self.push(
+            &format!("し{base}{form}"),
+            "する",
+            rules_in,
+            WordClass::VS,
+            reasons,
+        );
+        self.kuru(&format!("き{base}{form}"), rules_in, reasons);
+    }
+
+    /// Adds a rule for an auxiliary following the te form. The auxiliary conjugates like
+    /// `rules_in`
+    fn te_aux(&mut self, aux: &str, rules_in: WordClass, reasons: &'static [Reason]) {
+        self.push(&format!("て{aux}"), "て", rules_in, WordClass::TE, reasons);
+        self.push(&format!("で{aux}"), "で", rules_in, WordClass::TE, reasons);
+    }
+
+    /// Adds a rule for an i-adjective
+    fn adj(
+        &mut self,
+        from: &str,
+        rules_in: WordClass,
+        rules_out: WordClass,
+        reasons: &'static [Reason],
+    ) {
+        self.push(from, "い", rules_in, rules_out, reasons);
+    }
+
+    /// Adds a rule for くる written in kana (`from`) and with its kanji
+    fn kuru(&mut self, from: &str, rules_in: WordClass, reasons: &'static [Reason]) {
+        self.push(from, "くる", rules_in, WordClass::VK, reasons);
+
+        let kanji: String = "来".chars().chain(from.chars().skip(1)).collect();
+        self.push(&kanji, "来る", rules_in, WordClass::VK, reasons);
+    }
+
+    /// Adds the contracted causative passive of godan verbs (eg. 書かされる). Godan verbs ending
+    /// in す don't have this form
+    fn godan_short_causative_passive(&mut self) {
+        for ending in GODAN.iter().filter(|i| i[0] != "す") {
+            let from = format!("{}される", ending[A]);
+            self.push(
+                &from,
+                ending[0],
+                WordClass::V1,
+                WordClass::V5,
+                &[Reason::CausativePassive],
+            );
+        }
+    }
+}
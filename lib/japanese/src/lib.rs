@@ -1,3 +1,4 @@
+pub mod deinflect;
 pub mod furigana;
 pub mod guessing;
 pub mod radicals;
@@ -0,0 +1,186 @@
+//! Prometheus metrics of Jotoba. All metrics are registered in a custom registry which gets
+//! exposed by the `/api/internal/metrics` endpoint, which requires the internal API key.
+
+use once_cell::sync::Lazy;
+use prometheus::{
+    Encoder, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder,
+};
+use std::time::Duration;
+
+/// Buckets for durations in seconds
+const DURATION_BUCKETS: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Buckets for result counts
+const RESULT_BUCKETS: &[f64] = &[0.0, 1.0, 2.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 1000.0];
+
+static REGISTRY: Lazy<Registry> =
+    Lazy::new(|| Registry::new_custom(Some("jotoba".into()), None).unwrap());
+
+static HTTP_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    let opts = Opts::new("http_requests_total", "Amount of handled HTTP requests");
+    register(IntCounterVec::new(opts, &["route", "method", "status"]).unwrap())
+});
+
+static HTTP_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    let opts = HistogramOpts::new("http_request_duration_seconds", "HTTP request latency")
+        .buckets(DURATION_BUCKETS.to_vec());
+    register(HistogramVec::new(opts, &["route", "method"]).unwrap())
+});
+
+static SEARCH_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    let opts = HistogramOpts::new(
+        "search_duration_seconds",
+        "Search latency per search target",
+    )
+    .buckets(DURATION_BUCKETS.to_vec());
+    register(HistogramVec::new(opts, &["target"]).unwrap())
+});
+
+static SEARCH_RESULTS: Lazy<HistogramVec> = Lazy::new(|| {
+    let opts = HistogramOpts::new("search_results", "Amount of results per search")
+        .buckets(RESULT_BUCKETS.to_vec());
+    register(HistogramVec::new(opts, &["target"]).unwrap())
+});
+
+static SEARCH_ZERO_RESULTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    let opts = Opts::new(
+        "search_zero_results_total",
+        "Amount of searches without results",
+    );
+    register(IntCounterVec::new(opts, &["target"]).unwrap())
+});
+
+static SEARCH_TIMEOUTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    let opts = Opts::new(
+        "search_timeouts_total",
+        "Amount of searches which exceeded their time budget",
+    );
+    register(IntCounterVec::new(opts, &["target"]).unwrap())
+});
+
+static PRODUCER_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    let opts = HistogramOpts::new(
+        "producer_duration_seconds",
+        "Time spent in search producers",
+    )
+    .buckets(DURATION_BUCKETS.to_vec());
+    register(HistogramVec::new(opts, &["producer"]).unwrap())
+});
+
+static SEARCH_CACHE: Lazy<IntCounterVec> = Lazy::new(|| {
+    let opts = Opts::new(
+        "search_cache_lookups_total",
+        "Search result cache lookups by outcome",
+    );
+    register(IntCounterVec::new(opts, &["result"]).unwrap())
+});
+
+static LOAD_DURATION: Lazy<GaugeVec> = Lazy::new(|| {
+    let opts = Opts::new(
+        "load_duration_seconds",
+        "Time it took to load data on startup",
+    );
+    register(GaugeVec::new(opts, &["component"]).unwrap())
+});
+
+/// Registers a metric in the registry and returns it
+fn register<M: prometheus::core::Collector + Clone + 'static>(metric: M) -> M {
+    REGISTRY.register(Box::new(metric.clone())).unwrap();
+    metric
+}
+
+/// Records a handled HTTP request. `route` should be the matched route pattern to keep the
+/// amount of label values low
+pub fn observe_request(route: &str, method: &str, status: u16, duration: Duration) {
+    let status = status.to_string();
+    HTTP_REQUESTS
+        .with_label_values(&[route, method, &status])
+        .inc();
+    HTTP_DURATION
+        .with_label_values(&[route, method])
+        .observe(duration.as_secs_f64());
+}
+
+/// Records a finished search
+pub fn observe_search(target: &str, duration: Duration, results: usize) {
+    SEARCH_DURATION
+        .with_label_values(&[target])
+        .observe(duration.as_secs_f64());
+    SEARCH_RESULTS
+        .with_label_values(&[target])
+        .observe(results as f64);
+    if results == 0 {
+        SEARCH_ZERO_RESULTS.with_label_values(&[target]).inc();
+    }
+}
+
+/// Records a search which got stopped because it exceeded its time budget
+pub fn observe_timeout(target: &str) {
+    SEARCH_TIMEOUTS.with_label_values(&[target]).inc();
+}
+
+/// Records the time a single producer took
+pub fn observe_producer(producer: &str, duration: Duration) {
+    PRODUCER_DURATION
+        .with_label_values(&[producer])
+        .observe(duration.as_secs_f64());
+}
+
+/// Records a lookup in the search result cache
+pub fn observe_cache(hit: bool) {
+    let result = if hit { "hit" } else { "miss" };
+    SEARCH_CACHE.with_label_values(&[result]).inc();
+}
+
+/// Sets the time it took to load `component`
+pub fn set_load_duration(component: &str, duration: Duration) {
+    LOAD_DURATION
+        .with_label_values(&[component])
+        .set(duration.as_secs_f64());
+}
+
+/// Encodes all metrics in the prometheus text format
+pub fn encode() -> String {
+    // Make sure all metrics are registered, even if they haven't been used yet
+    Lazy::force(&HTTP_REQUESTS);
+    Lazy::force(&HTTP_DURATION);
+    Lazy::force(&SEARCH_DURATION);
+    Lazy::force(&SEARCH_RESULTS);
+    Lazy::force(&SEARCH_ZERO_RESULTS);
+    Lazy::force(&SEARCH_TIMEOUTS);
+    Lazy::force(&PRODUCER_DURATION);
+    Lazy::force(&SEARCH_CACHE);
+    Lazy::force(&LOAD_DURATION);
+
+    let mut buf = vec![];
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buf)
+        .unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+/// Content type of the encoded metrics
+pub fn content_type() -> String {
+    TextEncoder::new().format_type().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        observe_search("Words", Duration::from_millis(5), 0);
+        observe_producer("NativeProducer", Duration::from_millis(2));
+        observe_cache(true);
+
+        let out = encode();
+        assert!(out.contains("jotoba_search_zero_results_total{target=\"Words\"} 1"));
+        assert!(
+            out.contains("jotoba_producer_duration_seconds_count{producer=\"NativeProducer\"} 1")
+        );
+        assert!(out.contains("jotoba_search_cache_lookups_total{result=\"hit\"} 1"));
+    }
+}
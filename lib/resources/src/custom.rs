@@ -0,0 +1,246 @@
+//! User supplied dictionaries which get merged into the word storage at startup.
+//!
+//! Custom dictionaries are either JSON files containing an array of [`CustomEntry`] or TSV files
+//! with one entry per line in the format `<kana>\t<kanji>\t<glosses>\t<language>\t<pos>`. Glosses
+//! are separated by `;`, part of speech tags (JMdict codes) by `,`. All columns except the first
+//! and third one can be left empty.
+
+use crate::{retrieve::kanji::KanjiRetrieve, ResourceStorage};
+use serde::Deserialize;
+use std::{
+    error::Error,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+};
+use types::jotoba::{
+    language::Language,
+    words::{
+        part_of_speech::PartOfSpeech,
+        reading::Reading,
+        sense::{Gloss, Sense},
+        Dict, Word, CUSTOM_SEQ_START,
+    },
+};
+
+/// A single entry of a custom dictionary
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct CustomEntry {
+    pub kana: String,
+    #[serde(default)]
+    pub kanji: Option<String>,
+    pub glosses: Vec<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub pos: Vec<String>,
+    #[serde(default)]
+    pub common: bool,
+}
+
+/// Loads all custom dictionaries in `files` and adds their entries to the word storage of
+/// `storage`. Returns the sequence ids of the added words
+pub fn merge<P: AsRef<Path>>(
+    storage: &mut ResourceStorage,
+    files: &[P],
+) -> Result<Vec<u32>, Box<dyn Error>> {
+    let mut seq = next_sequence(storage);
+    let mut words = vec![];
+
+    for file in files {
+        let kanji = KanjiRetrieve::new(&storage.kanji);
+        for entry in load(file)? {
+            if let Some(word) = convert(entry, seq, kanji) {
+                words.push(word);
+                seq += 1;
+            }
+        }
+    }
+
+    let ids = words.iter().map(|i| i.sequence).collect();
+    storage.words.add_words(words);
+    Ok(ids)
+}
+
+/// Loads the entries of a custom dictionary. Files ending with `.json` are parsed as JSON,
+/// everything else as TSV
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<CustomEntry>, Box<dyn Error>> {
+    let path = path.as_ref();
+    let reader = BufReader::new(File::open(path)?);
+
+    let is_json = path
+        .extension()
+        .map(|i| i.eq_ignore_ascii_case("json"))
+        .unwrap_or(false);
+
+    if is_json {
+        return Ok(serde_json::from_reader(reader)?);
+    }
+
+    let mut out = vec![];
+    for line in reader.lines() {
+        if let Some(entry) = parse_tsv_line(&line?) {
+            out.push(entry);
+        }
+    }
+    Ok(out)
+}
+
+/// Converts a custom entry into a word with the sequence id `seq`. Returns `None` if the entry
+/// has no reading or no glosses
+pub fn convert(entry: CustomEntry, seq: u32, kanji: KanjiRetrieve<'_>) -> Option<Word> {
+    let kana = entry.kana.trim();
+    if kana.is_empty() {
+        return None;
+    }
+
+    let glosses: Vec<Gloss> = entry
+        .glosses
+        .iter()
+        .map(|i| i.trim())
+        .filter(|i| !i.is_empty())
+        .enumerate()
+        .map(|(id, gloss)| Gloss {
+            id: id as u8,
+            gloss: gloss.to_string(),
+            g_type: None,
+        })
+        .collect();
+    if glosses.is_empty() {
+        return None;
+    }
+
+    let language = entry
+        .language
+        .as_deref()
+        .and_then(|i| Language::from_str(i).ok())
+        .unwrap_or(Language::English);
+
+    let part_of_speech = entry
+        .pos
+        .iter()
+        .filter_map(|i| PartOfSpeech::try_from(i.trim()).ok())
+        .collect();
+
+    let kanji_reading = entry
+        .kanji
+        .as_deref()
+        .map(|i| i.trim())
+        .filter(|i| !i.is_empty());
+
+    let furigana = kanji_reading.map(|k| japanese::furigana::generate::checked(kanji, k, kana));
+
+    let reading = Reading {
+        kana: to_dict(kana, false),
+        kanji: kanji_reading.map(|i| to_dict(i, true)),
+        alternative: vec![],
+    };
+
+    Some(Word {
+        sequence: seq,
+        common: entry.common,
+        reading,
+        senses: vec![Sense {
+            glosses,
+            part_of_speech,
+            language,
+            ..Sense::default()
+        }],
+        furigana,
+        ..Word::default()
+    })
+}
+
+/// Parses a single line of a TSV dictionary
+fn parse_tsv_line(line: &str) -> Option<CustomEntry> {
+    if line.trim().is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let mut split = line.split('\t').map(|i| i.trim());
+    let kana = split.next()?.to_string();
+    let kanji = split
+        .next()
+        .filter(|i| !i.is_empty())
+        .map(|i| i.to_string());
+    let glosses = split
+        .next()?
+        .split(';')
+        .map(|i| i.trim().to_string())
+        .collect();
+    let language = split
+        .next()
+        .filter(|i| !i.is_empty())
+        .map(|i| i.to_string());
+    let pos = split
+        .next()
+        .map(|i| {
+            i.split(',')
+                .filter(|i| !i.trim().is_empty())
+                .map(|i| i.trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(CustomEntry {
+        kana,
+        kanji,
+        glosses,
+        language,
+        pos,
+        common: false,
+    })
+}
+
+fn to_dict(reading: &str, kanji: bool) -> Dict {
+    Dict {
+        reading: reading.to_string(),
+        kanji,
+        is_main: true,
+        ..Dict::default()
+    }
+}
+
+/// Returns the next free sequence id within the custom range
+fn next_sequence(storage: &ResourceStorage) -> u32 {
+    storage
+        .words
+        .words
+        .iter()
+        .map(|i| i.1.sequence)
+        .filter(|i| *i >= CUSTOM_SEQ_START)
+        .max()
+        .map(|i| i + 1)
+        .unwrap_or(CUSTOM_SEQ_START)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_tsv_line() {
+        let entry = parse_tsv_line("てすと\t手州\ttest; trial \teng\tn,vs").unwrap();
+        assert_eq!(entry.kana, "てすと");
+        assert_eq!(entry.kanji.as_deref(), Some("手州"));
+        assert_eq!(entry.glosses, vec!["test", "trial"]);
+        assert_eq!(entry.language.as_deref(), Some("eng"));
+        assert_eq!(entry.pos, vec!["n", "vs"]);
+    }
+
+    #[test]
+    fn test_parse_tsv_line_empty_columns() {
+        let entry = parse_tsv_line("てすと\t\ttest").unwrap();
+        assert_eq!(entry.kanji, None);
+        assert_eq!(entry.language, None);
+        assert!(entry.pos.is_empty());
+    }
+
+    #[test]
+    fn test_parse_tsv_line_skip() {
+        assert_eq!(parse_tsv_line(""), None);
+        assert_eq!(parse_tsv_line("# comment"), None);
+        assert_eq!(parse_tsv_line("てすと"), None);
+    }
+}
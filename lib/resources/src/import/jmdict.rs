@@ -0,0 +1,285 @@
+use super::xml::{attribute, XmlEvent, XmlReader};
+use crate::retrieve::kanji::KanjiRetrieve;
+use std::{collections::HashMap, error::Error, io::BufRead, path::Path, str::FromStr};
+use types::{
+    jotoba::{
+        language::Language,
+        words::{
+            priority::Priority,
+            reading::Reading,
+            sense::{Gairaigo, Gloss, Sense},
+            Dict, Word,
+        },
+    },
+    raw::jmdict::{Entry, EntryElement, EntrySense, GlossValue},
+};
+
+/// Returns an iterator over all entries of a JMdict XML file. Entries get parsed while iterating
+pub fn parse<P: AsRef<Path>>(
+    path: P,
+) -> Result<impl Iterator<Item = Result<Entry, Box<dyn Error>>>, Box<dyn Error>> {
+    Ok(entries(XmlReader::open(path)?))
+}
+
+fn entries<R: BufRead>(
+    reader: XmlReader<R>,
+) -> impl Iterator<Item = Result<Entry, Box<dyn Error>>> {
+    reader
+        .elements("entry")
+        .filter_map(|i| i.map(parse_entry).transpose())
+}
+
+fn parse_entry(events: Vec<XmlEvent>) -> Option<Entry> {
+    let mut entry = Entry::default();
+    let mut element = EntryElement::default();
+    let mut sense = EntrySense::default();
+    let mut gloss_lang = Language::English;
+    let mut ls_attributes = vec![];
+    let mut stack: Vec<String> = vec![];
+
+    for event in events {
+        match event {
+            XmlEvent::Start { name, attributes } => {
+                match name.as_str() {
+                    "k_ele" | "r_ele" => element = EntryElement::default(),
+                    "sense" => {
+                        sense.clear();
+                        sense.id = entry.senses.len() as u8;
+                    }
+                    "gloss" => {
+                        gloss_lang = attribute(&attributes, "xml:lang")
+                            .and_then(|i| Language::from_str(i).ok())
+                            .unwrap_or(Language::English);
+                        sense.glosses.push(GlossValue {
+                            language: gloss_lang,
+                            g_type: attribute(&attributes, "g_type").and_then(|i| i.parse().ok()),
+                            value: String::new(),
+                        });
+                    }
+                    "lsource" => ls_attributes = attributes,
+                    "re_nokanji" => element.no_true_reading = true,
+                    _ => (),
+                }
+                stack.push(name);
+            }
+            XmlEvent::End(name) => {
+                match name.as_str() {
+                    "k_ele" | "r_ele" => {
+                        element.kanji = name == "k_ele";
+                        entry.elements.push(std::mem::take(&mut element));
+                    }
+                    "sense" => entry.senses.push(sense.clone()),
+                    _ => (),
+                }
+                stack.pop();
+            }
+            XmlEvent::Text(text) => {
+                let curr = stack.last().map(|i| i.as_str()).unwrap_or_default();
+                match curr {
+                    "ent_seq" => entry.sequence = text.parse().ok()?,
+                    "keb" | "reb" => element.value = text,
+                    "ke_pri" | "re_pri" => {
+                        if let Ok(prio) = Priority::try_from(text.as_str()) {
+                            element.priorities.push(prio);
+                        }
+                    }
+                    "ke_inf" | "re_inf" => {
+                        if let Ok(info) = text.parse() {
+                            element.reading_info.push(info);
+                        }
+                    }
+                    "pos" => {
+                        if let Ok(pos) = text.as_str().try_into() {
+                            sense.part_of_speech.push(pos);
+                        }
+                    }
+                    "misc" => sense.misc = sense.misc.or_else(|| text.parse().ok()),
+                    "field" => sense.field = sense.field.or_else(|| text.parse().ok()),
+                    "dial" => sense.dialect = sense.dialect.or_else(|| text.parse().ok()),
+                    "xref" => sense.xref = Some(text),
+                    "ant" => sense.antonym = Some(text),
+                    "s_inf" => sense.information = Some(text),
+                    "lsource" => {
+                        let language = attribute(&ls_attributes, "xml:lang")
+                            .unwrap_or("eng")
+                            .parse()
+                            .unwrap_or_default();
+                        let fully_derived = attribute(&ls_attributes, "ls_type") != Some("part");
+                        sense.gairaigo = Some(Gairaigo {
+                            language,
+                            fully_derived,
+                            original: text,
+                        });
+                    }
+                    "ex_srce" => sense.example_sentence = text.parse().ok(),
+                    "gloss" => {
+                        if let Some(gloss) = sense.glosses.last_mut() {
+                            gloss.language = gloss_lang;
+                            gloss.value = text;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    (entry.sequence != 0).then(|| entry)
+}
+
+/// Converts a raw JMdict entry into a `Word`. Kanji are used to generate furigana
+pub fn convert(entry: Entry, kanji: KanjiRetrieve<'_>) -> Option<Word> {
+    let reading = convert_reading(&entry.elements)?;
+
+    let common = entry
+        .elements
+        .iter()
+        .flat_map(|i| i.priorities.iter())
+        .any(is_common_priority);
+
+    let furigana = reading
+        .kanji
+        .as_ref()
+        .map(|k| japanese::furigana::generate::checked(kanji, &k.reading, &reading.kana.reading));
+
+    Some(Word {
+        sequence: entry.sequence,
+        common,
+        reading,
+        senses: convert_senses(entry.senses),
+        furigana,
+        ..Word::default()
+    })
+}
+
+fn convert_reading(elements: &[EntryElement]) -> Option<Reading> {
+    let kana_pos = elements.iter().position(|i| !i.kanji)?;
+    let kanji_pos = elements.iter().position(|i| i.kanji);
+
+    let mut alternative = vec![];
+    for (pos, element) in elements.iter().enumerate() {
+        if pos != kana_pos && Some(pos) != kanji_pos {
+            alternative.push(to_dict(element, false));
+        }
+    }
+
+    Some(Reading {
+        kana: to_dict(&elements[kana_pos], true),
+        kanji: kanji_pos.map(|i| to_dict(&elements[i], true)),
+        alternative,
+    })
+}
+
+fn to_dict(element: &EntryElement, is_main: bool) -> Dict {
+    Dict {
+        reading: element.value.clone(),
+        kanji: element.kanji,
+        no_kanji: element.no_true_reading,
+        priorities: (!element.priorities.is_empty()).then(|| element.priorities.clone()),
+        reading_info: (!element.reading_info.is_empty()).then(|| element.reading_info.clone()),
+        is_main,
+    }
+}
+
+/// Splits senses into one sense per language. Part of speech tags apply to all following
+/// senses until new ones are set
+fn convert_senses(senses: Vec<EntrySense>) -> Vec<Sense> {
+    let mut out = vec![];
+    let mut last_pos = vec![];
+
+    for sense in senses {
+        if !sense.part_of_speech.is_empty() {
+            last_pos = sense.part_of_speech.clone();
+        }
+
+        let mut by_lang: HashMap<Language, Vec<Gloss>> = HashMap::new();
+        let mut languages = vec![];
+        for gloss in sense.glosses.iter() {
+            let glosses = by_lang.entry(gloss.language).or_default();
+            if glosses.is_empty() {
+                languages.push(gloss.language);
+            }
+            glosses.push(Gloss {
+                id: glosses.len() as u8,
+                gloss: gloss.value.clone(),
+                g_type: gloss.g_type,
+            });
+        }
+
+        for language in languages {
+            out.push(Sense {
+                id: sense.id,
+                misc: sense.misc,
+                field: sense.field,
+                dialect: sense.dialect,
+                glosses: by_lang.remove(&language).unwrap_or_default(),
+                xref: sense.xref.clone(),
+                antonym: sense.antonym.clone(),
+                information: sense.information.clone(),
+                part_of_speech: last_pos.clone(),
+                language,
+                example_sentence: sense.example_sentence,
+                gairaigo: sense.gairaigo.clone(),
+            });
+        }
+    }
+
+    out
+}
+
+/// Returns `true` if the priority marks a word as common, as defined by JMdict
+#[inline]
+fn is_common_priority(prio: &Priority) -> bool {
+    matches!(
+        prio,
+        Priority::News(1) | Priority::Ichi(1) | Priority::Spec(1 | 2) | Priority::Gai(1)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::jotoba::words::part_of_speech::{GodanVerbEnding, PartOfSpeech, VerbType};
+
+    const ENTRY: &str = r#"<entry>
+<ent_seq>1578850</ent_seq>
+<k_ele><keb>行く</keb><ke_pri>ichi1</ke_pri></k_ele>
+<r_ele><reb>いく</reb><re_pri>ichi1</re_pri></r_ele>
+<sense><pos>&v5k-s;</pos><pos>&vi;</pos><gloss>to go</gloss><gloss>to move</gloss></sense>
+<sense><gloss xml:lang="ger">gehen</gloss></sense>
+</entry>"#;
+
+    fn parse_str(s: &str) -> Vec<Entry> {
+        entries(XmlReader::new(s.as_bytes()))
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        let entries = parse_str(ENTRY);
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.sequence, 1578850);
+        assert_eq!(entry.elements.len(), 2);
+        assert!(entry.elements[0].kanji);
+        assert_eq!(entry.elements[1].value, "いく");
+        assert_eq!(entry.senses.len(), 2);
+        assert_eq!(
+            entry.senses[0].part_of_speech[0],
+            PartOfSpeech::Verb(VerbType::Godan(GodanVerbEnding::IkuYuku))
+        );
+        assert_eq!(entry.senses[1].glosses[0].language, Language::German);
+    }
+
+    #[test]
+    fn test_convert_senses() {
+        let entry = parse_str(ENTRY).remove(0);
+        let senses = convert_senses(entry.senses);
+        assert_eq!(senses.len(), 2);
+        assert_eq!(senses[0].glosses.len(), 2);
+        // Part of speech gets inherited
+        assert_eq!(senses[1].part_of_speech, senses[0].part_of_speech);
+        assert_eq!(senses[1].language, Language::German);
+    }
+}
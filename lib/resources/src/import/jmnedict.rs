@@ -0,0 +1,77 @@
+use super::xml::{XmlEvent, XmlReader};
+use std::{error::Error, io::BufRead, path::Path, str::FromStr};
+use types::{
+    jotoba::names::{name_type::NameType, Name},
+    raw::jmnedict::NameEntry,
+};
+
+/// Returns an iterator over all entries of a JMnedict XML file. Entries get parsed while
+/// iterating
+pub fn parse<P: AsRef<Path>>(
+    path: P,
+) -> Result<impl Iterator<Item = Result<NameEntry, Box<dyn Error>>>, Box<dyn Error>> {
+    Ok(entries(XmlReader::open(path)?))
+}
+
+fn entries<R: BufRead>(
+    reader: XmlReader<R>,
+) -> impl Iterator<Item = Result<NameEntry, Box<dyn Error>>> {
+    reader
+        .elements("entry")
+        .filter_map(|i| i.map(parse_entry).transpose())
+}
+
+fn parse_entry(events: Vec<XmlEvent>) -> Option<NameEntry> {
+    let mut entry = NameEntry::default();
+    let mut translations: Vec<String> = vec![];
+    let mut name_types: Vec<NameType> = vec![];
+    let mut stack: Vec<String> = vec![];
+
+    for event in events {
+        match event {
+            XmlEvent::Start { name, .. } => stack.push(name),
+            XmlEvent::End(_) => {
+                stack.pop();
+            }
+            XmlEvent::Text(text) => {
+                let curr = stack.last().map(|i| i.as_str()).unwrap_or_default();
+                match curr {
+                    "ent_seq" => entry.sequence = text.parse().ok()?,
+                    // Only the first kanji and kana element gets used
+                    "keb" if entry.kanji_element.is_none() => entry.kanji_element = Some(text),
+                    "reb" if entry.kana_element.is_empty() => entry.kana_element = text,
+                    "name_type" => {
+                        if let Ok(name_type) = NameType::from_str(&text) {
+                            if !name_types.contains(&name_type) {
+                                name_types.push(name_type);
+                            }
+                        }
+                    }
+                    "xref" if entry.xref.is_none() => entry.xref = Some(text),
+                    "trans_det" => translations.push(text),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    if entry.kana_element.is_empty() {
+        return None;
+    }
+
+    entry.transcription = translations.join("; ");
+    entry.name_type = (!name_types.is_empty()).then(|| name_types);
+    Some(entry)
+}
+
+/// Converts a raw JMnedict entry into a `Name`
+pub fn convert(entry: NameEntry) -> Name {
+    Name {
+        sequence: entry.sequence as u32,
+        kana: entry.kana_element,
+        kanji: entry.kanji_element,
+        transcription: entry.transcription,
+        name_type: entry.name_type,
+        xref: entry.xref,
+    }
+}
@@ -0,0 +1,179 @@
+use super::xml::{attribute, XmlEvent, XmlReader};
+use crate::storage::kanji::KanjiStorage;
+use std::{error::Error, io::BufRead, path::Path};
+use types::{
+    jotoba::{
+        kanji::{radical::DetailedRadical, Kanji},
+        language::Language,
+    },
+    raw::kanjidict::Character,
+};
+
+/// Returns an iterator over all characters of a KANJIDIC2 XML file. Characters get parsed while
+/// iterating
+pub fn parse<P: AsRef<Path>>(
+    path: P,
+) -> Result<impl Iterator<Item = Result<Character, Box<dyn Error>>>, Box<dyn Error>> {
+    Ok(characters(XmlReader::open(path)?))
+}
+
+fn characters<R: BufRead>(
+    reader: XmlReader<R>,
+) -> impl Iterator<Item = Result<Character, Box<dyn Error>>> {
+    reader
+        .elements("character")
+        .filter_map(|i| i.map(parse_character).transpose())
+}
+
+fn parse_character(events: Vec<XmlEvent>) -> Option<Character> {
+    let mut character = Character::default();
+    // Current element and its attributes
+    let mut stack: Vec<(String, Vec<(String, String)>)> = vec![];
+
+    for event in events {
+        match event {
+            XmlEvent::Start { name, attributes } => stack.push((name, attributes)),
+            XmlEvent::End(_) => {
+                stack.pop();
+            }
+            XmlEvent::Text(text) => {
+                let (curr, attributes) = match stack.last() {
+                    Some((name, attributes)) => (name.as_str(), attributes.as_slice()),
+                    None => continue,
+                };
+                let attr = |name: &str| attribute(attributes, name);
+
+                match curr {
+                    "literal" => character.literal = text.chars().next()?,
+                    "grade" => character.grade = text.parse().ok(),
+                    // The first stroke count is the accepted one, following ones are common miscounts
+                    "stroke_count" if character.stroke_count == 0 => {
+                        character.stroke_count = text.parse().unwrap_or_default()
+                    }
+                    "freq" => character.frequency = text.parse().ok(),
+                    "jlpt" => character.jlpt = text.parse().ok(),
+                    "variant" => character.variant.push(text),
+                    "rad_value" if attr("rad_type") == Some("classical") => {
+                        character.radical = text.parse().ok()
+                    }
+                    "reading" => match attr("r_type") {
+                        Some("ja_on") => character.on_readings.push(text),
+                        Some("ja_kun") => character.kun_readings.push(text),
+                        Some("pinyin") => character.chinese_readings.push(text),
+                        Some("korean_r") => character.korean_romanized.push(text),
+                        Some("korean_h") => character.korean_hangul.push(text),
+                        Some("vietnam") => character.vietnamese.push(text),
+                        _ => (),
+                    },
+                    // Meanings without `m_lang` are english
+                    "meaning" => match attr("m_lang") {
+                        None => character.meaning.push(text),
+                        Some(lang) => {
+                            if let Some(lang) = meaning_language(lang) {
+                                add_foreign_meaning(&mut character, lang, text);
+                            }
+                        }
+                    },
+                    "nanori" => character.natori.push(text),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    (character.literal != char::default()).then(|| character)
+}
+
+/// Maps the ISO 639-1 codes used by KANJIDIC to supported languages
+fn meaning_language(m_lang: &str) -> Option<Language> {
+    Some(match m_lang {
+        "fr" => Language::French,
+        "es" => Language::Spanish,
+        "de" => Language::German,
+        "ru" => Language::Russian,
+        "nl" => Language::Dutch,
+        "sv" => Language::Swedish,
+        "hu" => Language::Hungarian,
+        "sl" => Language::Slovenian,
+        _ => return None,
+    })
+}
+
+fn add_foreign_meaning(character: &mut Character, lang: Language, meaning: String) {
+    match character.foreign_meanings.iter_mut().find(|i| i.0 == lang) {
+        Some((_, meanings)) => meanings.push(meaning),
+        None => character.foreign_meanings.push((lang, vec![meaning])),
+    }
+}
+
+/// Converts a raw KANJIDIC character into `Kanji`. Data which isn't part of KANJIDIC (eg.
+/// similar kanji or parts) gets taken from `base` if available
+pub fn convert(character: Character, base: &KanjiStorage) -> Kanji {
+    let prev = base.literal_index.get(character.literal as u32);
+
+    let radical = character
+        .radical
+        .and_then(|rad| base.radical_data.values().find(|i| i.id as i32 == rad))
+        .cloned()
+        .or_else(|| prev.map(|i| i.radical.clone()))
+        .unwrap_or_else(DetailedRadical::default);
+
+    Kanji {
+        literal: character.literal,
+        grade: character.grade,
+        stroke_count: character.stroke_count,
+        frequency: character.frequency,
+        jlpt: character.jlpt,
+        variant: character.variant,
+        onyomi: character.on_readings,
+        nanori: character.natori,
+        kunyomi: character.kun_readings,
+        chinese: character.chinese_readings,
+        korean_r: character.korean_romanized,
+        korean_h: character.korean_hangul,
+        vietnamese: character.vietnamese,
+        kun_dicts: prev.map(|i| i.kun_dicts.clone()).unwrap_or_default(),
+        on_dicts: prev.map(|i| i.on_dicts.clone()).unwrap_or_default(),
+        similar_kanji: prev.map(|i| i.similar_kanji.clone()).unwrap_or_default(),
+        meanings: character.meaning,
+        foreign_meanings: character.foreign_meanings,
+        radical,
+        parts: prev.map(|i| i.parts.clone()).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_character() {
+        let xml = r#"<character>
+<literal>猫</literal>
+<radical><rad_value rad_type="classical">94</rad_value></radical>
+<misc><grade>8</grade><stroke_count>11</stroke_count><stroke_count>12</stroke_count><jlpt>2</jlpt></misc>
+<reading_meaning><rmgroup>
+<reading r_type="ja_on">ビョウ</reading>
+<reading r_type="ja_kun">ねこ</reading>
+<meaning>cat</meaning>
+<meaning m_lang="fr">chat</meaning>
+</rmgroup></reading_meaning>
+</character>"#;
+
+        let characters: Vec<_> = characters(XmlReader::new(xml.as_bytes()))
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(characters.len(), 1);
+        let character = &characters[0];
+        assert_eq!(character.literal, '猫');
+        assert_eq!(character.radical, Some(94));
+        assert_eq!(character.stroke_count, 11);
+        assert_eq!(character.on_readings, vec!["ビョウ".to_string()]);
+        assert_eq!(character.meaning, vec!["cat".to_string()]);
+        assert_eq!(
+            character.foreign_meanings,
+            vec![(Language::French, vec!["chat".to_string()])]
+        );
+    }
+}
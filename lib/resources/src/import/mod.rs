@@ -0,0 +1,121 @@
+//! Builds a [`ResourceStorage`] from the raw EDRDG dictionary files (JMdict, JMnedict and
+//! KANJIDIC2).
+//!
+//! Data that isn't part of those dictionaries (sentences, radicals, pitch accents, ...) can be
+//! taken over from an existing storage passed as `base`.
+
+pub mod jmdict;
+pub mod jmnedict;
+pub mod kanjidict;
+pub(crate) mod xml;
+
+use crate::{retrieve::kanji::KanjiRetrieve, storage::word::WordStorage, ResourceStorage};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::File,
+    io::{BufRead, BufReader},
+    num::NonZeroU8,
+    path::{Path, PathBuf},
+};
+use types::jotoba::words::Word;
+
+/// Source files for building a [`ResourceStorage`]
+#[derive(Default)]
+pub struct ImportSources {
+    pub jmdict: PathBuf,
+    pub jmnedict: Option<PathBuf>,
+    pub kanjidic: Option<PathBuf>,
+    /// TSV file mapping word sequence ids to JLPT levels (`<sequence>\t<level>`)
+    pub jlpt: Option<PathBuf>,
+}
+
+/// Builds a new [`ResourceStorage`] out of `sources`. Everything that can't be built from the
+/// source files gets taken from `base`
+pub fn build(
+    sources: &ImportSources,
+    base: Option<ResourceStorage>,
+) -> Result<ResourceStorage, Box<dyn Error>> {
+    let mut storage = base.unwrap_or_default();
+
+    if let Some(ref kanjidic) = sources.kanjidic {
+        let kanji = kanjidict::parse(kanjidic)?
+            .map(|i| i.map(|i| kanjidict::convert(i, &storage.kanji)))
+            .collect::<Result<_, _>>()?;
+        storage.kanji.insert_kanji(kanji);
+    }
+
+    if let Some(ref jmnedict) = sources.jmnedict {
+        let names = jmnedict::parse(jmnedict)?
+            .map(|i| i.map(jmnedict::convert))
+            .collect::<Result<_, _>>()?;
+        storage.names.insert_names(names);
+    }
+
+    let jlpt = match sources.jlpt {
+        Some(ref path) => Some(load_jlpt(path)?),
+        None => None,
+    };
+
+    let kanji = KanjiRetrieve::new(&storage.kanji);
+    let mut words: Vec<Word> = vec![];
+
+    for entry in jmdict::parse(&sources.jmdict)? {
+        let mut word = match jmdict::convert(entry?, kanji) {
+            Some(word) => word,
+            None => continue,
+        };
+
+        if let Some(prev) = storage.words.words.get(word.sequence) {
+            take_over(&mut word, prev);
+        }
+
+        if let Some(ref jlpt) = jlpt {
+            word.jlpt_lvl = jlpt.get(&word.sequence).and_then(|i| NonZeroU8::new(*i));
+        }
+
+        words.push(word);
+    }
+
+    insert_words(&mut storage.words, words);
+
+    Ok(storage)
+}
+
+/// Inserts `words` into `storage` and builds all tag mappings
+fn insert_words(storage: &mut WordStorage, words: Vec<Word>) {
+    storage.clear_tags();
+    storage.index_tags(&words);
+    storage.insert_words(words);
+    storage.update_sentence_mapping();
+}
+
+/// Takes over all data of `prev` which can't be built from the dictionary files
+fn take_over(word: &mut Word, prev: &Word) {
+    word.jlpt_lvl = prev.jlpt_lvl;
+    word.collocations = prev.collocations.clone();
+    word.transive_version = prev.transive_version;
+    word.intransive_version = prev.intransive_version;
+    word.sentences_available = prev.sentences_available;
+    word.accents = prev.accents.clone();
+}
+
+/// Loads a JLPT mapping file with one `<sequence>\t<level>` entry per line
+fn load_jlpt(path: &Path) -> Result<HashMap<u32, u8>, Box<dyn Error>> {
+    let mut out = HashMap::new();
+
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        let mut split = line.trim().split('\t');
+        let (seq, lvl) = match (split.next(), split.next()) {
+            (Some(seq), Some(lvl)) => (seq, lvl),
+            _ => continue,
+        };
+
+        if let (Ok(seq), Ok(lvl)) = (seq.parse(), lvl.parse()) {
+            out.insert(seq, lvl);
+        }
+    }
+
+    Ok(out)
+}
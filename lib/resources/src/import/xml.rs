@@ -0,0 +1,224 @@
+use quick_xml::{events::Event, Reader};
+use std::{
+    error::Error,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// A simplified XML event
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum XmlEvent {
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    End(String),
+    Text(String),
+}
+
+/// Streaming XML reader for the EDRDG dictionary files
+pub(crate) struct XmlReader<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    /// End event of a self closing element which has to be returned next
+    pending_end: Option<String>,
+}
+
+impl XmlReader<BufReader<File>> {
+    /// Opens the XML file at `path`
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: BufRead> XmlReader<R> {
+    pub fn new(reader: R) -> Self {
+        let mut reader = Reader::from_reader(reader);
+        reader.trim_text(true);
+        Self {
+            reader,
+            buf: vec![],
+            pending_end: None,
+        }
+    }
+
+    /// Returns the next event or `None` if the end of the file was reached
+    pub fn next_event(&mut self) -> Result<Option<XmlEvent>, Box<dyn Error>> {
+        if let Some(end) = self.pending_end.take() {
+            return Ok(Some(XmlEvent::End(end)));
+        }
+
+        loop {
+            self.buf.clear();
+            let event = match self.reader.read_event_into(&mut self.buf)? {
+                Event::Start(e) => XmlEvent::Start {
+                    name: decode(e.name().as_ref())?,
+                    attributes: attributes(e.attributes())?,
+                },
+                Event::Empty(e) => {
+                    let name = decode(e.name().as_ref())?;
+                    self.pending_end = Some(name.clone());
+                    XmlEvent::Start {
+                        name,
+                        attributes: attributes(e.attributes())?,
+                    }
+                }
+                Event::End(e) => XmlEvent::End(decode(e.name().as_ref())?),
+                Event::Text(e) => XmlEvent::Text(unescape(&decode(&e)?)),
+                Event::CData(e) => XmlEvent::Text(decode(&e)?),
+                Event::Eof => return Ok(None),
+                // Declarations, doctype, comments
+                _ => continue,
+            };
+
+            return Ok(Some(event));
+        }
+    }
+
+    /// Returns an iterator over the content of all elements with the name `tag`. Elements are
+    /// read while iterating, so only a single element is kept in memory at a time
+    pub fn elements(self, tag: &str) -> Elements<R> {
+        Elements {
+            reader: self,
+            tag: tag.to_string(),
+        }
+    }
+
+    /// Reads the next element with the name `tag` and returns its content
+    fn next_element(&mut self, tag: &str) -> Result<Option<Vec<XmlEvent>>, Box<dyn Error>> {
+        let mut elem: Option<Vec<XmlEvent>> = None;
+
+        while let Some(event) = self.next_event()? {
+            match event {
+                XmlEvent::Start { ref name, .. } if name == tag => {
+                    elem = Some(vec![]);
+                }
+                XmlEvent::End(ref name) if name == tag => {
+                    if let Some(elem) = elem.take() {
+                        return Ok(Some(elem));
+                    }
+                }
+                event => {
+                    if let Some(ref mut elem) = elem {
+                        elem.push(event);
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Iterator over the content of all elements with a given name. Returned by
+/// [`XmlReader::elements`]
+pub(crate) struct Elements<R: BufRead> {
+    reader: XmlReader<R>,
+    tag: String,
+}
+
+impl<R: BufRead> Iterator for Elements<R> {
+    type Item = Result<Vec<XmlEvent>, Box<dyn Error>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reader.next_element(&self.tag).transpose()
+    }
+}
+
+/// Returns the value of the attribute `name`
+pub(crate) fn attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|i| i.0 == name)
+        .map(|i| i.1.as_str())
+}
+
+fn attributes(
+    attrs: quick_xml::events::attributes::Attributes,
+) -> Result<Vec<(String, String)>, Box<dyn Error>> {
+    let mut out = vec![];
+    for attr in attrs {
+        let attr = attr?;
+        out.push((decode(attr.key.as_ref())?, unescape(&decode(&attr.value)?)));
+    }
+    Ok(out)
+}
+
+#[inline]
+fn decode(b: &[u8]) -> Result<String, Box<dyn Error>> {
+    Ok(std::str::from_utf8(b)?.to_string())
+}
+
+/// Unescapes XML text. JMdict uses custom entities (eg. `&v5r;`) for its tags which can't be
+/// resolved without parsing the DTD. Those get replaced with their name.
+fn unescape(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => {
+                out.push('&');
+                continue;
+            }
+        };
+
+        match &rest[..end] {
+            "lt" => out.push('<'),
+            "gt" => out.push('>'),
+            "amp" => out.push('&'),
+            "quot" => out.push('"'),
+            "apos" => out.push('\''),
+            entity => out.push_str(entity),
+        }
+        rest = &rest[end + 1..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape("&v5r;"), "v5r");
+        assert_eq!(unescape("a &amp; b"), "a & b");
+        assert_eq!(unescape("no entities"), "no entities");
+    }
+
+    #[test]
+    fn test_empty_element() {
+        let mut reader = XmlReader::new("<r_ele><reb>か</reb><re_nokanji/></r_ele>".as_bytes());
+        let mut events = vec![];
+        while let Some(event) = reader.next_event().unwrap() {
+            events.push(event);
+        }
+        assert_eq!(events.len(), 7);
+        assert_eq!(events[5], XmlEvent::End("re_nokanji".to_string()));
+    }
+
+    #[test]
+    fn test_elements() {
+        let xml = "<root><entry><a>1</a></entry><other/><entry><a>2</a></entry></root>";
+        let mut elements = XmlReader::new(xml.as_bytes()).elements("entry");
+
+        let first = elements.next().unwrap().unwrap();
+        assert_eq!(first[1], XmlEvent::Text("1".to_string()));
+        let second = elements.next().unwrap().unwrap();
+        assert_eq!(second[1], XmlEvent::Text("2".to_string()));
+        assert!(elements.next().is_none());
+    }
+}
@@ -1,15 +1,20 @@
+pub mod custom;
+#[cfg(feature = "import")]
+pub mod import;
 pub mod retrieve;
 pub mod storage;
 
 pub use storage::{feature::Feature, ResourceStorage};
 
-use once_cell::sync::{Lazy, OnceCell};
+use once_cell::sync::Lazy;
 use std::{
     error::Error,
     fs::File,
-    io::{BufReader, Write},
+    io::{BufRead, BufReader, Write},
     path::Path,
+    sync::Arc,
 };
+use utils::swap_cell::SwapCell;
 
 /// Static git hash of current build
 pub const GIT_HASH: &str = env!("GIT_HASH");
@@ -24,8 +29,15 @@ pub const REQUIRED_FEATURES: &[Feature] = &[
     // Feature::RadicalData,
 ];
 
-/// InMemory storage for all data
-static STORAGE: OnceCell<ResourceStorage> = OnceCell::new();
+/// Marks resource files starting with the version of their layout. Files without it have the
+/// layout of version 0
+const MAGIC: &[u8] = b"JOTOBA_RESOURCES";
+
+/// Version of the current resource file layout
+pub const FORMAT_VERSION: u32 = 1;
+
+/// InMemory storage for all data. Can be replaced at runtime using [`swap`]
+static STORAGE: SwapCell<ResourceStorage> = SwapCell::new();
 
 /// Lazy resource storage for tests
 pub static LAZY_STORAGE: Lazy<ResourceStorage> = Lazy::new(|| {
@@ -38,20 +50,41 @@ pub static LAZY_STORAGE: Lazy<ResourceStorage> = Lazy::new(|| {
 pub fn get() -> &'static ResourceStorage {
     // Safety:
     // The STORAGE cell gets initialized once at the beginning which is absolutely necessary for
-    // the program to work. It won't be unset so its always safe
+    // the program to work. It can be swapped but never be unset so its always safe. References
+    // into a swapped storage are only valid while the caller holds a `utils::swap_cell::ReadPin`
     unsafe { STORAGE.get_unchecked() }
 }
 
 /// Returns `true` if the storage is loaded
 #[inline(always)]
 pub fn is_loaded() -> bool {
-    STORAGE.get().is_some()
+    STORAGE.is_set()
 }
 
 /// Load the resource storage and returns it
 pub fn load_raw<P: AsRef<Path>>(path: P) -> Result<ResourceStorage, Box<dyn Error>> {
-    let mut reader = BufReader::new(File::open(path)?);
-    Ok(bincode::deserialize_from(&mut reader)?)
+    read(BufReader::new(File::open(path)?))
+}
+
+/// Reads a resource storage. Storages of older layouts get migrated to the current one
+fn read<R: BufRead>(mut reader: R) -> Result<ResourceStorage, Box<dyn Error>> {
+    if !reader.fill_buf()?.starts_with(MAGIC) {
+        let legacy: storage::legacy::ResourceStorage = bincode::deserialize_from(&mut reader)?;
+        let mut storage: ResourceStorage = legacy.into();
+        storage.build_lookups();
+        return Ok(storage);
+    }
+    reader.consume(MAGIC.len());
+
+    let version: u32 = bincode::deserialize_from(&mut reader)?;
+    if version != FORMAT_VERSION {
+        let err = format!("Resources have format version {version} instead of {FORMAT_VERSION}");
+        return Err(err.into());
+    }
+
+    let mut storage: ResourceStorage = bincode::deserialize_from(&mut reader)?;
+    storage.build_lookups();
+    Ok(storage)
 }
 
 /// Load the resource storage from a file. Returns `true` if it wasn't loaded before
@@ -59,17 +92,25 @@ pub fn load<P: AsRef<Path>>(path: P) -> Result<bool, Box<dyn Error>> {
     if is_loaded() {
         return Ok(true);
     }
-    Ok(STORAGE.set(load_raw(path)?).is_ok())
+    Ok(STORAGE.set(load_raw(path)?))
 }
 
 /// Serializes a ResourceStorage into `output`
-pub fn store<W: Write>(output: W, storage: &ResourceStorage) -> Result<(), Box<dyn Error>> {
+pub fn store<W: Write>(mut output: W, storage: &ResourceStorage) -> Result<(), Box<dyn Error>> {
+    output.write_all(MAGIC)?;
+    bincode::serialize_into(&mut output, &FORMAT_VERSION)?;
     bincode::serialize_into(output, storage)?;
     Ok(())
 }
 
 pub fn set(res_storage: ResourceStorage) {
-    STORAGE.set(res_storage).ok();
+    STORAGE.set(res_storage);
+}
+
+/// Replaces the loaded storage with `res_storage` and returns the previous one. Has to be called
+/// within [`utils::swap_cell::swap_all`]
+pub fn swap(res_storage: ResourceStorage) -> Option<Arc<ResourceStorage>> {
+    STORAGE.swap(res_storage)
 }
 
 pub fn wait() {
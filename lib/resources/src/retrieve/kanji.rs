@@ -1,5 +1,6 @@
 use ids_parser::IDS;
 use sorted_intersection::SortedIntersection;
+use std::ops::RangeInclusive;
 use types::jotoba::kanji::{radical::DetailedRadical, Kanji};
 
 use super::super::storage::kanji::KanjiStorage;
@@ -64,6 +65,34 @@ impl<'a> KanjiRetrieve<'a> {
         self.storage.genki_levels.get(&genki_lektion)
     }
 
+    /// Returns all kanji of the given school grade
+    #[inline]
+    pub fn by_grade(&self, grade: u8) -> Option<&'a Vec<char>> {
+        self.storage.grade_map.get(&grade)
+    }
+
+    /// Returns all kanji with the given stroke count
+    #[inline]
+    pub fn by_stroke_count(&self, stroke_count: u8) -> Option<&'a Vec<char>> {
+        self.storage.stroke_map.get(&stroke_count)
+    }
+
+    /// Returns all kanji having `radical` as radical or as its alternative form
+    #[inline]
+    pub fn by_main_radical(&self, radical: char) -> Option<&'a Vec<char>> {
+        self.storage.radical_kanji_map.get(&radical)
+    }
+
+    /// Returns all kanji with a frequency rank within `ranks` ordered by their rank
+    pub fn by_frequency(&self, ranks: RangeInclusive<u16>) -> &'a [char] {
+        let list = &self.storage.by_frequency;
+        let rank = |literal: &char| self.by_literal(*literal).and_then(|i| i.frequency);
+
+        let start = list.partition_point(|i| rank(i) < Some(*ranks.start()));
+        let end = list.partition_point(|i| rank(i) <= Some(*ranks.end()));
+        &list[start..end.max(start)]
+    }
+
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &'a Kanji> {
         self.storage.literal_index.iter().map(|i| i.1)
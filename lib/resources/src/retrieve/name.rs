@@ -1,5 +1,5 @@
 use super::super::storage::name::NameStorage;
-use types::jotoba::names::Name;
+use types::jotoba::names::{name_type::NameType, Name};
 
 #[derive(Clone, Copy)]
 pub struct NameRetrieve<'a> {
@@ -29,4 +29,14 @@ impl<'a> NameRetrieve<'a> {
     pub fn iter(&self) -> impl Iterator<Item = &'a Name> {
         self.storage.names.iter().map(|i| i.1)
     }
+
+    /// Returns all names having every type of `name_types` ordered by their sequence id
+    #[inline]
+    pub fn by_types(&self, name_types: &[NameType]) -> impl Iterator<Item = &'a Name> {
+        let names = &self.storage.names;
+        self.storage
+            .of_types(name_types)
+            .into_iter()
+            .filter_map(move |seq| names.get(&seq))
+    }
 }
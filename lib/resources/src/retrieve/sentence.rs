@@ -1,4 +1,5 @@
 use super::super::storage::sentence::SentenceStorage;
+use std::ops::RangeInclusive;
 use types::jotoba::sentences::{tag::Tag, Sentence};
 
 #[derive(Clone, Copy)]
@@ -51,6 +52,13 @@ impl<'a> SentenceRetrieve<'a> {
             .filter_map(move |i| self.by_id(*i))
     }
 
+    /// Returns an iterator over all sentences with a level within `range`
+    #[inline]
+    pub fn by_level(&self, range: RangeInclusive<i8>) -> impl Iterator<Item = &'a Sentence> {
+        self.iter()
+            .filter(move |i| i.level().map_or(false, |l| range.contains(&l)))
+    }
+
     #[inline]
     pub fn count(&self) -> usize {
         self.storage.sentences.len()
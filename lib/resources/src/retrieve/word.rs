@@ -1,5 +1,5 @@
 use super::super::storage::word::WordStorage;
-use types::jotoba::words::{misc::Misc, part_of_speech::PosSimple, Word};
+use types::jotoba::words::{misc::Misc, part_of_speech::PosSimple, pitch::PitchPattern, Word};
 
 #[derive(Clone, Copy)]
 pub struct WordRetrieve<'a> {
@@ -116,6 +116,52 @@ impl<'a> WordRetrieve<'a> {
         self.storage.misc_map.get(&(*misc as u8)).map(|i| i.len())
     }
 
+    /// Returns an iterator over all words with a pitch dropping after `drop` morae
+    #[inline]
+    pub fn by_pitch_drop<'b>(
+        &'b self,
+        drop: u8,
+    ) -> impl Iterator<Item = &'a Word> + 'b + DoubleEndedIterator {
+        self.storage
+            .pitch_drop_map
+            .get(&drop)
+            .into_iter()
+            .flatten()
+            .filter_map(move |i| self.by_sequence(*i))
+    }
+
+    /// Returns the amount of words with a pitch dropping after `drop` morae
+    #[inline]
+    pub fn pitch_drop_len(&self, drop: u8) -> usize {
+        self.storage
+            .pitch_drop_map
+            .get(&drop)
+            .map_or(0, |i| i.len())
+    }
+
+    /// Returns an iterator over all words with a pitch of the given `pattern`
+    #[inline]
+    pub fn by_pitch_pattern<'b>(
+        &'b self,
+        pattern: PitchPattern,
+    ) -> impl Iterator<Item = &'a Word> + 'b + DoubleEndedIterator {
+        self.storage
+            .pitch_pattern_map
+            .get(&(pattern as u8))
+            .into_iter()
+            .flatten()
+            .filter_map(move |i| self.by_sequence(*i))
+    }
+
+    /// Returns the amount of words with a pitch of the given `pattern`
+    #[inline]
+    pub fn pitch_pattern_len(&self, pattern: PitchPattern) -> usize {
+        self.storage
+            .pitch_pattern_map
+            .get(&(pattern as u8))
+            .map_or(0, |i| i.len())
+    }
+
     /// Returns the total count of words
     #[inline]
     pub fn count(&self) -> usize {
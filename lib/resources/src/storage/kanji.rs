@@ -27,6 +27,24 @@ pub struct KanjiStorage {
     pub ids_index: HashMap<char, IDS>,
 
     has_similar_kanji: bool,
+
+    /// Kanji of each school grade. Built when loading the kanji instead of being stored
+    #[serde(skip)]
+    pub(crate) grade_map: HashMap<u8, Vec<char>>,
+
+    /// Kanji of each stroke count. Built when loading the kanji instead of being stored
+    #[serde(skip)]
+    pub(crate) stroke_map: HashMap<u8, Vec<char>>,
+
+    /// Kanji by their radical or its alternative form. Built when loading the kanji instead of
+    /// being stored
+    #[serde(skip)]
+    pub(crate) radical_kanji_map: HashMap<char, Vec<char>>,
+
+    /// All kanji with a frequency rank ordered by it. Built when loading the kanji instead of
+    /// being stored
+    #[serde(skip)]
+    pub(crate) by_frequency: Vec<char>,
 }
 
 impl KanjiStorage {
@@ -49,6 +67,49 @@ impl KanjiStorage {
             }
             self.literal_index.insert(kanji.literal as u32, kanji);
         }
+
+        self.index_tags();
+    }
+
+    /// Builds the lookups of kanji by their grade, stroke count, radical and frequency
+    pub(crate) fn index_tags(&mut self) {
+        self.grade_map.clear();
+        self.stroke_map.clear();
+        self.radical_kanji_map.clear();
+        self.by_frequency.clear();
+
+        for (_, kanji) in self.literal_index.iter() {
+            let literal = kanji.literal;
+
+            if let Some(grade) = kanji.grade {
+                self.grade_map.entry(grade).or_default().push(literal);
+            }
+
+            let strokes = self.stroke_map.entry(kanji.stroke_count).or_default();
+            strokes.push(literal);
+
+            let radical = &kanji.radical;
+            for rad in std::iter::once(radical.literal).chain(radical.alternative) {
+                self.radical_kanji_map.entry(rad).or_default().push(literal);
+            }
+
+            if kanji.frequency.is_some() {
+                self.by_frequency.push(literal);
+            }
+        }
+
+        let maps = self
+            .grade_map
+            .values_mut()
+            .chain(self.stroke_map.values_mut());
+        for v in maps.chain(self.radical_kanji_map.values_mut()) {
+            v.sort_unstable();
+            v.dedup();
+        }
+
+        let index = &self.literal_index;
+        self.by_frequency
+            .sort_by_key(|i| index.get(*i as u32).and_then(|i| i.frequency));
     }
 
     /// Insert radical detail data
@@ -89,3 +150,63 @@ impl KanjiStorage {
         out
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn kanji(literal: char, grade: Option<u8>, stroke_count: u8, frequency: Option<u16>) -> Kanji {
+        Kanji {
+            literal,
+            grade,
+            stroke_count,
+            frequency,
+            jlpt: None,
+            variant: vec![],
+            onyomi: vec![],
+            nanori: vec![],
+            kunyomi: vec![],
+            chinese: vec![],
+            korean_r: vec![],
+            korean_h: vec![],
+            vietnamese: vec![],
+            kun_dicts: vec![],
+            on_dicts: vec![],
+            similar_kanji: vec![],
+            meanings: vec![],
+            foreign_meanings: vec![],
+            radical: DetailedRadical {
+                literal: '水',
+                alternative: Some('氵'),
+                ..DetailedRadical::default()
+            },
+            parts: vec![],
+        }
+    }
+
+    #[test]
+    fn test_index_tags() {
+        let mut storage = KanjiStorage::new();
+        storage.insert_kanji(vec![
+            kanji('海', Some(2), 9, Some(200)),
+            kanji('池', Some(2), 6, Some(1000)),
+            kanji('泳', Some(3), 8, None),
+            kanji('汁', None, 5, Some(50)),
+        ]);
+
+        assert_eq!(storage.grade_map.get(&2), Some(&vec!['池', '海']));
+        assert_eq!(storage.stroke_map.get(&8), Some(&vec!['泳']));
+        assert_eq!(
+            storage.radical_kanji_map.get(&'氵').map(|i| i.len()),
+            Some(4)
+        );
+        assert_eq!(storage.by_frequency, vec!['汁', '海', '池']);
+
+        // The lookups aren't stored and have to be rebuilt after loading
+        let mut loaded: KanjiStorage =
+            bincode::deserialize(&bincode::serialize(&storage).unwrap()).unwrap();
+        assert!(loaded.grade_map.is_empty());
+        loaded.index_tags();
+        assert_eq!(loaded.grade_map.get(&3), Some(&vec!['泳']));
+    }
+}
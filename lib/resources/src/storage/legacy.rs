@@ -0,0 +1,147 @@
+//! Layout of resource files written before the format got versioned (version 0). Such files get
+//! migrated to the current layout when loaded.
+
+use super::{name::NameStorage, sentence::SentenceStorage, word::WordStorage};
+use ids_parser::IDS;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use types::jotoba::kanji::radical::DetailedRadical;
+
+#[derive(Serialize, Deserialize, Default)]
+pub(crate) struct ResourceStorage {
+    words: WordStorage,
+    kanji: KanjiStorage,
+    names: NameStorage,
+    sentences: SentenceStorage,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct KanjiStorage {
+    literal_index: intmap::IntMap<Kanji>,
+    radical_map: HashMap<char, Vec<char>>,
+    radical_data: HashMap<char, DetailedRadical>,
+    jlpt_data: HashMap<u8, Vec<char>>,
+    genki_levels: HashMap<u8, Vec<char>>,
+    ids_index: HashMap<char, IDS>,
+    has_similar_kanji: bool,
+}
+
+/// Kanji without meanings in other languages than english
+#[derive(Serialize, Deserialize, Clone, Default)]
+struct Kanji {
+    literal: char,
+    grade: Option<u8>,
+    stroke_count: u8,
+    frequency: Option<u16>,
+    jlpt: Option<u8>,
+    variant: Vec<String>,
+    onyomi: Vec<String>,
+    nanori: Vec<String>,
+    kunyomi: Vec<String>,
+    chinese: Vec<String>,
+    korean_r: Vec<String>,
+    korean_h: Vec<String>,
+    vietnamese: Vec<String>,
+    kun_dicts: Vec<u32>,
+    on_dicts: Vec<u32>,
+    similar_kanji: Vec<char>,
+    meanings: Vec<String>,
+    radical: DetailedRadical,
+    parts: Vec<char>,
+}
+
+impl From<ResourceStorage> for super::ResourceStorage {
+    fn from(legacy: ResourceStorage) -> Self {
+        let kanji = legacy.kanji;
+
+        let mut kanji_storage = super::kanji::KanjiStorage::new();
+        kanji_storage.insert_kanji(
+            kanji
+                .literal_index
+                .iter()
+                .map(|i| i.1.clone().into())
+                .collect(),
+        );
+        kanji_storage.radical_map = kanji.radical_map;
+        kanji_storage.radical_data = kanji.radical_data;
+        kanji_storage.genki_levels = kanji.genki_levels;
+        kanji_storage.ids_index = kanji.ids_index;
+
+        Self {
+            words: legacy.words,
+            kanji: kanji_storage,
+            names: legacy.names,
+            sentences: legacy.sentences,
+        }
+    }
+}
+
+impl From<Kanji> for types::jotoba::kanji::Kanji {
+    fn from(k: Kanji) -> Self {
+        Self {
+            literal: k.literal,
+            grade: k.grade,
+            stroke_count: k.stroke_count,
+            frequency: k.frequency,
+            jlpt: k.jlpt,
+            variant: k.variant,
+            onyomi: k.onyomi,
+            nanori: k.nanori,
+            kunyomi: k.kunyomi,
+            chinese: k.chinese,
+            korean_r: k.korean_r,
+            korean_h: k.korean_h,
+            vietnamese: k.vietnamese,
+            kun_dicts: k.kun_dicts,
+            on_dicts: k.on_dicts,
+            similar_kanji: k.similar_kanji,
+            meanings: k.meanings,
+            foreign_meanings: vec![],
+            radical: k.radical,
+            parts: k.parts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_migrate() {
+        let mut legacy = ResourceStorage::default();
+        let kanji = Kanji {
+            literal: '猫',
+            jlpt: Some(2),
+            meanings: vec!["cat".to_string()],
+            ..Kanji::default()
+        };
+        legacy.kanji.literal_index.insert('猫' as u32, kanji);
+        legacy.kanji.radical_map.insert('犭', vec!['猫']);
+
+        let data = bincode::serialize(&legacy).unwrap();
+        let storage = crate::read(Cursor::new(data)).unwrap();
+
+        let kanji = storage.kanji.literal_index.get('猫' as u32).unwrap();
+        assert_eq!(kanji.meanings, vec!["cat".to_string()]);
+        assert!(kanji.foreign_meanings.is_empty());
+        assert_eq!(storage.kanji.jlpt_data.get(&2), Some(&vec!['猫']));
+        assert_eq!(storage.kanji.radical_map.get(&'犭'), Some(&vec!['猫']));
+    }
+
+    #[test]
+    fn test_versioned() {
+        let mut storage = super::super::ResourceStorage::default();
+        let kanji = Kanji {
+            literal: '猫',
+            ..Kanji::default()
+        };
+        storage.kanji.insert_kanji(vec![kanji.into()]);
+
+        let mut data = vec![];
+        crate::store(&mut data, &storage).unwrap();
+        let loaded = crate::read(Cursor::new(data)).unwrap();
+        assert!(loaded.kanji.literal_index.get('猫' as u32).is_some());
+    }
+}
@@ -1,5 +1,6 @@
 pub mod feature;
 pub mod kanji;
+pub(crate) mod legacy;
 pub mod name;
 pub mod sentence;
 pub mod word;
@@ -30,6 +31,13 @@ impl ResourceStorage {
         Self::default()
     }
 
+    /// Builds lookups of the loaded data which aren't part of resource files
+    pub(crate) fn build_lookups(&mut self) {
+        self.names.index_types();
+        self.words.index_pitch();
+        self.kanji.index_tags();
+    }
+
     /// Returns `true` if all necessary features are present
     pub fn check(&self) -> bool {
         self.missing_but_required().is_empty()
@@ -2,13 +2,18 @@ use std::collections::HashMap;
 
 use super::feature::Feature;
 use serde::{Deserialize, Serialize};
-use types::jotoba::names::Name;
+use types::jotoba::names::{name_type::NameType, Name};
 
 /// Storage containing all data related to names
 #[derive(Serialize, Deserialize, Default, Clone)]
 pub struct NameStorage {
     /// Index mapping name id to its `Name` value
     pub names: HashMap<u32, Name>,
+
+    /// Sequence ids of the names of each type in ascending order. Built when loading the names
+    /// instead of being stored
+    #[serde(skip)]
+    pub(crate) by_type: HashMap<u8, Vec<u32>>,
 }
 
 impl NameStorage {
@@ -23,6 +28,53 @@ impl NameStorage {
         for name in names {
             self.names.insert(name.sequence, name);
         }
+
+        self.index_types();
+    }
+
+    /// Builds the lookup of names by their type
+    pub(crate) fn index_types(&mut self) {
+        self.by_type.clear();
+
+        for name in self.names.values() {
+            for name_type in name.name_type.iter().flatten() {
+                self.by_type
+                    .entry(*name_type as u8)
+                    .or_default()
+                    .push(name.sequence);
+            }
+        }
+
+        for v in self.by_type.values_mut() {
+            v.sort_unstable();
+            v.dedup();
+        }
+    }
+
+    /// Returns the sequence ids of all names of `name_type` in ascending order
+    #[inline]
+    pub(crate) fn of_type(&self, name_type: NameType) -> &[u32] {
+        self.by_type
+            .get(&(name_type as u8))
+            .map(|i| i.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the sequence ids of all names having every type of `name_types` in ascending order.
+    /// The lists of the types get intersected starting with the shortest one
+    pub(crate) fn of_types(&self, name_types: &[NameType]) -> Vec<u32> {
+        let mut lists: Vec<&[u32]> = name_types.iter().map(|i| self.of_type(*i)).collect();
+        lists.sort_by_key(|i| i.len());
+
+        let Some((shortest, others)) = lists.split_first() else {
+            return vec![];
+        };
+
+        shortest
+            .iter()
+            .copied()
+            .filter(|seq| others.iter().all(|i| i.binary_search(seq).is_ok()))
+            .collect()
     }
 
     pub fn get_features(&self) -> Vec<Feature> {
@@ -33,3 +85,65 @@ impl NameStorage {
         out
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn name(sequence: u32, name_type: Vec<NameType>) -> Name {
+        Name {
+            sequence,
+            kana: String::new(),
+            kanji: None,
+            transcription: String::new(),
+            name_type: Some(name_type),
+            xref: None,
+        }
+    }
+
+    #[test]
+    fn test_index_types() {
+        let mut storage = NameStorage::new();
+        storage.insert_names(vec![
+            name(3, vec![NameType::Surname]),
+            name(1, vec![NameType::Surname, NameType::Female]),
+            name(2, vec![NameType::Place]),
+        ]);
+
+        assert_eq!(storage.of_type(NameType::Surname), &[1, 3]);
+        assert_eq!(storage.of_type(NameType::Female), &[1]);
+        assert!(storage.of_type(NameType::Company).is_empty());
+
+        // The lookup isn't stored and has to be rebuilt after loading
+        let mut loaded: NameStorage =
+            bincode::deserialize(&bincode::serialize(&storage).unwrap()).unwrap();
+        assert!(loaded.of_type(NameType::Surname).is_empty());
+        loaded.index_types();
+        assert_eq!(loaded.of_type(NameType::Surname), &[1, 3]);
+    }
+
+    #[test]
+    fn test_of_types() {
+        // More names of the first type than the search produces per tag (1000), with the names
+        // having both types at the end
+        let names = (0..1500)
+            .map(|seq| match seq {
+                0..=1199 => name(seq, vec![NameType::Female]),
+                _ => name(seq, vec![NameType::Female, NameType::Given]),
+            })
+            .chain([name(2000, vec![NameType::Given])])
+            .collect();
+        let mut storage = NameStorage::new();
+        storage.insert_names(names);
+
+        let both = storage.of_types(&[NameType::Female, NameType::Given]);
+        assert_eq!(both, (1200..1500).collect::<Vec<_>>());
+        assert_eq!(storage.of_types(&[NameType::Given, NameType::Female]), both);
+
+        assert_eq!(storage.of_types(&[NameType::Given]).len(), 301);
+        assert!(storage
+            .of_types(&[NameType::Female, NameType::Company])
+            .is_empty());
+        assert!(storage.of_types(&[]).is_empty());
+    }
+}
@@ -1,7 +1,10 @@
 use intmap::IntMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use types::jotoba::words::Word;
+use types::jotoba::words::{
+    part_of_speech::{GodanVerbEnding, PartOfSpeech, VerbType},
+    Word,
+};
 
 use super::feature::Feature;
 
@@ -18,6 +21,12 @@ pub struct WordStorage {
     pub misc_map: HashMap<u8, Vec<u32>>,
     pub katakana_words: Vec<u32>,
 
+    // Pitch lookups. Built when loading the words instead of being stored
+    #[serde(skip)]
+    pub(crate) pitch_drop_map: HashMap<u8, Vec<u32>>,
+    #[serde(skip)]
+    pub(crate) pitch_pattern_map: HashMap<u8, Vec<u32>>,
+
     // Feature information
     has_accents: bool,
     has_sentence_mapping: bool,
@@ -38,25 +47,92 @@ impl WordStorage {
     /// Inserts words into the WordStorage
     pub fn insert_words(&mut self, words: Vec<Word>) {
         self.clear_words();
+        self.insert_words_keep(words);
+    }
 
-        for word in words {
-            if let Some(jlpt) = word.get_jlpt_lvl() {
-                self.jlpt_word_map
-                    .entry(jlpt)
-                    .or_default()
-                    .push(word.sequence);
-                self.has_jlpt = true;
+    /// Adds words to the WordStorage without removing the existing ones. Search tags and pitch
+    /// lookups of the new words get indexed as well
+    pub fn add_words(&mut self, words: Vec<Word>) {
+        self.index_tags(&words);
+        self.insert_words_keep(words);
+    }
+
+    /// Indexes the part of speech, misc, katakana and irregular ichidan tags of `words`
+    pub(crate) fn index_tags(&mut self, words: &[Word]) {
+        for word in words.iter() {
+            let seq = word.sequence;
+
+            let mut pos: Vec<u8> = word
+                .get_pos()
+                .flat_map(|i| i.to_pos_simple())
+                .map(|i| i as u8)
+                .collect();
+            pos.sort_unstable();
+            pos.dedup();
+            for pos in pos {
+                self.pos_map.entry(pos).or_default().push(seq);
+            }
+
+            let mut misc: Vec<u8> = word
+                .senses
+                .iter()
+                .filter_map(|i| i.misc)
+                .map(|i| i as u8)
+                .collect();
+            misc.sort_unstable();
+            misc.dedup();
+            for misc in misc {
+                self.misc_map.entry(misc).or_default().push(seq);
             }
 
-            if !self.has_accents && word.accents.count() > 0 {
-                self.has_accents = true;
+            if word.is_katakana_word() {
+                self.katakana_words.push(seq);
             }
 
-            self.words.insert(word.sequence, word);
+            if is_irregular_ichidan(word) {
+                self.irregular_ichidan.push(seq);
+            }
         }
 
-        for (_, v) in self.jlpt_word_map.iter_mut() {
-            v.sort();
+        for v in self.pos_map.values_mut().chain(self.misc_map.values_mut()) {
+            v.sort_unstable();
+            v.dedup();
+        }
+        self.katakana_words.sort_unstable();
+        self.katakana_words.dedup();
+        self.irregular_ichidan.sort_unstable();
+        self.irregular_ichidan.dedup();
+    }
+
+    /// Removes all search tags built by [`Self::index_tags`]
+    pub fn clear_tags(&mut self) {
+        self.pos_map.clear();
+        self.misc_map.clear();
+        self.katakana_words.clear();
+        self.irregular_ichidan.clear();
+    }
+
+    /// Builds the lookups of words by their pitch drops and pitch patterns
+    pub(crate) fn index_pitch(&mut self) {
+        self.pitch_drop_map.clear();
+        self.pitch_pattern_map.clear();
+
+        let mut drops: HashMap<u8, Vec<u32>> = HashMap::new();
+        let mut patterns: HashMap<u8, Vec<u32>> = HashMap::new();
+        for (_, word) in self.words.iter() {
+            add_pitch(&mut drops, &mut patterns, word);
+        }
+
+        self.pitch_drop_map = drops;
+        self.pitch_pattern_map = patterns;
+        self.sort_pitch();
+    }
+
+    fn sort_pitch(&mut self) {
+        let maps = self.pitch_drop_map.values_mut();
+        for v in maps.chain(self.pitch_pattern_map.values_mut()) {
+            v.sort_unstable();
+            v.dedup();
         }
     }
 
@@ -94,10 +170,127 @@ impl WordStorage {
         out
     }
 
+    fn insert_words_keep(&mut self, words: Vec<Word>) {
+        for word in words {
+            self.insert_word(word);
+        }
+
+        for (_, v) in self.jlpt_word_map.iter_mut() {
+            v.sort();
+            v.dedup();
+        }
+        self.sort_pitch();
+    }
+
+    fn insert_word(&mut self, word: Word) {
+        if let Some(jlpt) = word.get_jlpt_lvl() {
+            self.jlpt_word_map
+                .entry(jlpt)
+                .or_default()
+                .push(word.sequence);
+            self.has_jlpt = true;
+        }
+
+        if !self.has_accents && word.accents.count() > 0 {
+            self.has_accents = true;
+        }
+
+        add_pitch(&mut self.pitch_drop_map, &mut self.pitch_pattern_map, &word);
+
+        self.words.insert(word.sequence, word);
+    }
+
     fn clear_words(&mut self) {
         self.words.clear();
         self.jlpt_word_map.clear();
+        self.pitch_drop_map.clear();
+        self.pitch_pattern_map.clear();
         self.has_accents = false;
         self.has_sentence_mapping = false;
     }
 }
+
+/// Adds `word` to the lookups of each of its pitch drops and pitch patterns
+fn add_pitch(drops: &mut HashMap<u8, Vec<u32>>, patterns: &mut HashMap<u8, Vec<u32>>, word: &Word) {
+    for drop in word.accents.iter() {
+        drops.entry(drop).or_default().push(word.sequence);
+    }
+
+    for pattern in word.pitch_patterns() {
+        patterns
+            .entry(pattern as u8)
+            .or_default()
+            .push(word.sequence);
+    }
+}
+
+/// Returns `true` if `word` is a godan verb ending in いる/える which look like ichidan verbs
+fn is_irregular_ichidan(word: &Word) -> bool {
+    let kana = &word.reading.kana.reading;
+    (kana.ends_with("いる") || kana.ends_with("える"))
+        && word
+            .get_pos()
+            .any(|i| *i == PartOfSpeech::Verb(VerbType::Godan(GodanVerbEnding::Ru)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::jotoba::words::{
+        pitch::{raw_data::PitchValues, PitchPattern},
+        CUSTOM_SEQ_START,
+    };
+
+    fn word(sequence: u32, kana: &str, drops: &[u8]) -> Word {
+        let mut word = Word::default();
+        word.sequence = sequence;
+        word.reading.kana.reading = kana.to_string();
+        word.accents = PitchValues::new(drops);
+        word
+    }
+
+    #[test]
+    fn test_index_pitch() {
+        let mut storage = WordStorage::new();
+        storage.insert_words(vec![
+            word(3, "はし", &[2]),
+            word(1, "はし", &[1]),
+            word(2, "さくら", &[0, 2]),
+        ]);
+
+        let odaka = PitchPattern::Odaka as u8;
+        assert_eq!(storage.pitch_drop_map.get(&2), Some(&vec![2, 3]));
+        assert_eq!(storage.pitch_pattern_map.get(&odaka), Some(&vec![3]));
+
+        // The lookups aren't stored and have to be rebuilt after loading
+        let mut loaded: WordStorage =
+            bincode::deserialize(&bincode::serialize(&storage).unwrap()).unwrap();
+        assert!(loaded.pitch_drop_map.is_empty());
+        loaded.index_pitch();
+        assert_eq!(loaded.pitch_drop_map.get(&0), Some(&vec![2]));
+        assert_eq!(loaded.pitch_pattern_map.get(&odaka), Some(&vec![3]));
+    }
+
+    #[test]
+    fn test_add_words_pitch() {
+        let mut storage = WordStorage::new();
+        storage.insert_words(vec![word(1, "はし", &[1])]);
+        let mut loaded: WordStorage =
+            bincode::deserialize(&bincode::serialize(&storage).unwrap()).unwrap();
+        loaded.index_pitch();
+
+        // Words added after the lookups were built, like the ones of custom dictionaries
+        loaded.add_words(vec![
+            word(CUSTOM_SEQ_START, "はし", &[2]),
+            word(5, "はし", &[1]),
+        ]);
+
+        let odaka = PitchPattern::Odaka as u8;
+        assert_eq!(loaded.pitch_drop_map.get(&1), Some(&vec![1, 5]));
+        assert_eq!(loaded.pitch_drop_map.get(&2), Some(&vec![CUSTOM_SEQ_START]));
+        assert_eq!(
+            loaded.pitch_pattern_map.get(&odaka),
+            Some(&vec![CUSTOM_SEQ_START])
+        );
+    }
+}
@@ -0,0 +1,299 @@
+//! In-memory LRU cache for search results. Entries are keyed by the parsed [`Query`] together
+//! with the user settings and paging information which aren't covered by its `Hash` impl and
+//! expire after a configurable time. The cache is disabled until [`init`] gets called.
+
+use crate::query::Query;
+use once_cell::sync::Lazy;
+use std::{
+    any::{Any, TypeId},
+    collections::{BTreeMap, HashMap},
+    hash::{Hash, Hasher},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+static CACHE: Lazy<Mutex<Cache>> = Lazy::new(|| Mutex::new(Cache::new(0, Duration::ZERO)));
+
+/// Enables the cache with the given max amount of entries and time to live. A capacity of 0
+/// disables caching
+pub fn init(capacity: usize, ttl: Duration) {
+    *CACHE.lock().unwrap() = Cache::new(capacity, ttl);
+}
+
+/// Returns `true` if results get cached
+#[inline]
+pub fn is_enabled() -> bool {
+    CACHE.lock().unwrap().capacity > 0
+}
+
+/// Returns a cached result of type `T` for `query`
+pub fn get<T: Clone + 'static>(query: &Query) -> Option<T> {
+    let mut cache = CACHE.lock().unwrap();
+    if cache.capacity == 0 {
+        return None;
+    }
+    let hit = cache.get::<T>(&Key::new::<T>(query));
+    drop(cache);
+
+    metrics::observe_cache(hit.is_some());
+    hit
+}
+
+/// Caches `value` as result for `query`
+pub fn insert<T: Clone + Send + Sync + 'static>(query: &Query, value: T) {
+    CACHE
+        .lock()
+        .unwrap()
+        .insert(Key::new::<T>(query), Box::new(value));
+}
+
+/// Removes all cached results. Has to be called whenever the underlying data changes. Clearing
+/// within [`utils::swap_cell::swap_all`] ensures no running search can insert results of the
+/// replaced data afterwards
+pub fn clear() {
+    let mut cache = CACHE.lock().unwrap();
+    cache.entries.clear();
+    cache.lru.clear();
+}
+
+/// Returns the hit/miss statistics of the cache
+pub fn stats() -> Stats {
+    let cache = CACHE.lock().unwrap();
+    Stats {
+        hits: cache.hits,
+        misses: cache.misses,
+        entries: cache.entries.len(),
+        capacity: cache.capacity,
+    }
+}
+
+/// Usage statistics of the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+impl Stats {
+    /// Returns the ratio of lookups which were answered by the cache
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+}
+
+struct Cache {
+    capacity: usize,
+    ttl: Duration,
+    entries: HashMap<Key, Entry>,
+    /// Keys ordered by their last access. The first entry is the least recently used one
+    lru: BTreeMap<u64, Key>,
+    /// Incremented on each access to track the least recently used entry
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+struct Entry {
+    value: Box<dyn Any + Send + Sync>,
+    created: Instant,
+    last_used: u64,
+}
+
+impl Cache {
+    fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: HashMap::new(),
+            lru: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn get<T: Clone + 'static>(&mut self, key: &Key) -> Option<T> {
+        if self.capacity == 0 {
+            return None;
+        }
+
+        self.tick += 1;
+
+        let expired = match self.entries.get_mut(key) {
+            Some(entry) if entry.created.elapsed() <= self.ttl => {
+                let key = self
+                    .lru
+                    .remove(&entry.last_used)
+                    .unwrap_or_else(|| key.clone());
+                self.lru.insert(self.tick, key);
+                entry.last_used = self.tick;
+                let value = entry.value.downcast_ref::<T>().cloned();
+                self.hits += 1;
+                return value;
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        if expired {
+            self.remove(key);
+        }
+        self.misses += 1;
+        None
+    }
+
+    fn insert(&mut self, key: Key, value: Box<dyn Any + Send + Sync>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            self.evict();
+        }
+
+        self.tick += 1;
+        let entry = Entry {
+            value,
+            created: Instant::now(),
+            last_used: self.tick,
+        };
+        self.lru.insert(self.tick, key.clone());
+        if let Some(replaced) = self.entries.insert(key, entry) {
+            self.lru.remove(&replaced.last_used);
+        }
+    }
+
+    /// Removes the least recently used entry
+    fn evict(&mut self) {
+        if let Some((_, key)) = self.lru.pop_first() {
+            self.entries.remove(&key);
+        }
+    }
+
+    fn remove(&mut self, key: &Key) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.lru.remove(&entry.last_used);
+        }
+    }
+}
+
+/// Cache key. `UserSettings` only hashes and compares the fields relevant for the search language
+/// so all other fields affecting the result have to be stored separately
+#[derive(Clone, PartialEq)]
+struct Key {
+    /// Type of the cached result to separate equal queries of different searches
+    kind: TypeId,
+    query: Query,
+    page_size: u32,
+    english_on_top: bool,
+    show_example_sentences: bool,
+    sentence_furigana: bool,
+}
+
+impl Key {
+    fn new<T: 'static>(query: &Query) -> Self {
+        let settings = &query.settings;
+        Self {
+            kind: TypeId::of::<T>(),
+            query: query.clone(),
+            page_size: settings.page_size,
+            english_on_top: settings.english_on_top,
+            show_example_sentences: settings.show_example_sentences,
+            sentence_furigana: settings.sentence_furigana,
+        }
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.query.hash(state);
+        self.page_size.hash(state);
+        self.english_on_top.hash(state);
+        self.show_example_sentences.hash(state);
+        self.sentence_furigana.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn query(q: &str) -> Query {
+        Query {
+            query_str: q.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut cache = Cache::new(2, Duration::from_secs(60));
+        cache.insert(Key::new::<u32>(&query("a")), Box::new(1u32));
+        cache.insert(Key::new::<u32>(&query("b")), Box::new(2u32));
+
+        // Use "a" so "b" becomes the least recently used entry
+        assert_eq!(cache.get::<u32>(&Key::new::<u32>(&query("a"))), Some(1));
+        cache.insert(Key::new::<u32>(&query("c")), Box::new(3u32));
+
+        assert_eq!(cache.get::<u32>(&Key::new::<u32>(&query("b"))), None);
+        assert_eq!(cache.get::<u32>(&Key::new::<u32>(&query("a"))), Some(1));
+        assert_eq!(cache.get::<u32>(&Key::new::<u32>(&query("c"))), Some(3));
+        assert_eq!((cache.hits, cache.misses), (3, 1));
+    }
+
+    #[test]
+    fn test_key_settings() {
+        let mut cache = Cache::new(10, Duration::from_secs(60));
+        let q = query("a");
+        cache.insert(Key::new::<u32>(&q), Box::new(1u32));
+
+        let mut other = q.clone();
+        other.settings.page_size += 1;
+        assert_eq!(cache.get::<u32>(&Key::new::<u32>(&other)), None);
+
+        let mut other = q.clone();
+        other.page += 1;
+        assert_eq!(cache.get::<u32>(&Key::new::<u32>(&other)), None);
+
+        // Same query with a different result type
+        assert_eq!(cache.get::<u64>(&Key::new::<u64>(&q)), None);
+        assert_eq!(cache.get::<u32>(&Key::new::<u32>(&q)), Some(1));
+    }
+
+    #[test]
+    fn test_ttl() {
+        let mut cache = Cache::new(10, Duration::ZERO);
+        let key = Key::new::<u32>(&query("a"));
+        cache.insert(key.clone(), Box::new(1u32));
+        std::thread::sleep(Duration::from_millis(2));
+        assert_eq!(cache.get::<u32>(&key), None);
+        assert!(cache.entries.is_empty());
+        assert!(cache.lru.is_empty());
+    }
+
+    #[test]
+    fn test_reinsert() {
+        let mut cache = Cache::new(2, Duration::from_secs(60));
+        let a = Key::new::<u32>(&query("a"));
+        cache.insert(a.clone(), Box::new(1u32));
+        cache.insert(Key::new::<u32>(&query("b")), Box::new(2u32));
+
+        // Replacing "a" makes "b" the least recently used entry
+        cache.insert(a.clone(), Box::new(3u32));
+        assert_eq!(cache.lru.len(), 2);
+        cache.insert(Key::new::<u32>(&query("c")), Box::new(4u32));
+
+        assert_eq!(cache.get::<u32>(&a), Some(3));
+        assert_eq!(cache.get::<u32>(&Key::new::<u32>(&query("b"))), None);
+        assert_eq!(cache.entries.len(), cache.lru.len());
+    }
+}
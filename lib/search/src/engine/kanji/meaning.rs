@@ -0,0 +1,59 @@
+use index_framework::{
+    retrieve::{retriever::default::DefaultRetrieve, Retrieve},
+    traits::{backend::Backend, dictionary::IndexDictionary},
+};
+use indexes::kanji::meaning::{self, format_meaning, stem, NGRAM};
+use ngindex::{item::IndexItem, termset::TermSet, utils::padded, NGIndex, Wordgrams};
+use types::jotoba::{kanji::Kanji, language::Language};
+
+pub struct Engine;
+
+impl engine::Engine<'static> for Engine {
+    type B = NGIndex<NGRAM, Self::Document>;
+    type DictItem = String;
+    type Document = IndexItem<u32>;
+    type Retriever = DefaultRetrieve<'static, Self::B, Self::DictItem, Self::Document>;
+    type Output = &'static Kanji;
+    type Query = TermSet;
+
+    fn make_query<S: AsRef<str>>(inp: S, lang: Option<Language>) -> Option<Self::Query> {
+        let fmt = stem(&format_meaning(inp.as_ref()));
+
+        let dict = Self::get_index(lang).dict();
+        let mut tids: Vec<_> = Wordgrams::new(&padded(&fmt, NGRAM - 1), NGRAM)
+            .filter_map(|i| dict.get_id(i))
+            .collect();
+        tids.sort_unstable();
+        tids.dedup();
+        if tids.is_empty() {
+            return None;
+        }
+        Some(TermSet::new(tids))
+    }
+
+    #[inline]
+    fn doc_to_output(input: &Self::Document) -> Option<Vec<Self::Output>> {
+        let literal = char::from_u32(*input.item())?;
+        resources::get()
+            .kanji()
+            .by_literal(literal)
+            .map(|i| vec![i])
+    }
+
+    #[inline]
+    fn get_index(_: Option<Language>) -> &'static Self::B {
+        indexes::get().kanji().meaning().unwrap()
+    }
+
+    #[inline]
+    fn retrieve_for(
+        query: &Self::Query,
+        _q_str: &str,
+        lang: Option<Language>,
+    ) -> Retrieve<'static, Self::B, Self::DictItem, Self::Document> {
+        let posting = meaning::posting(lang.unwrap_or_default());
+        Self::retrieve(lang)
+            .by_term_ids(query.iter().copied())
+            .in_posting(posting)
+    }
+}
@@ -1,3 +1,4 @@
+pub mod kanji;
 pub mod names;
 pub mod radical;
 pub mod sentences;
@@ -1,10 +1,11 @@
-use crate::query::regex::RegexSQuery;
+use crate::query::regex::{RegexSQuery, Requirement};
 use engine::utils::page_from_pqueue;
 use indexes::regex::RegexSearchIndex;
 use intmap::int_set::IntSet;
 use itertools::Itertools;
 use order_struct::order_nh::OrderVal;
 use priority_container::StableUniquePrioContainerMax;
+use std::{collections::HashSet, time::Instant};
 use types::jotoba::words::Word;
 
 /// Result of a regex search
@@ -25,7 +26,7 @@ where
     let mut out_queue = StableUniquePrioContainerMax::new_allocated(queue_size, queue_size);
 
     let index = indexes::get().word().regex();
-    let possible_results = find_words(index, &query.get_chars());
+    let possible_results = find_words(index, query, None);
 
     for seq_id in possible_results.into_iter().sorted() {
         let word = word_resources.by_sequence(seq_id).unwrap();
@@ -51,38 +52,101 @@ where
     RegexSearchResult { items, item_len }
 }
 
-/// Get all indexed words using characters in `chars`
-pub(crate) fn find_words(index: &RegexSearchIndex, chars: &[char]) -> IntSet {
-    if chars.is_empty() {
+/// Get all indexed words which can match `query`. Returns no words if `deadline` passed before
+/// all requirements were intersected
+pub(crate) fn find_words(
+    index: &RegexSearchIndex,
+    query: &RegexSQuery,
+    deadline: Option<Instant>,
+) -> IntSet {
+    let passed = || deadline.map_or(false, |i| Instant::now() >= i);
+
+    let mut requirements: Vec<Vec<&HashSet<u32>>> = query
+        .requirements()
+        .into_iter()
+        .map(|req| req.into_iter().filter_map(|r| postings(index, r)).collect())
+        .collect();
+
+    // A requirement without any indexed word can't be fulfilled
+    if requirements.iter().any(|i| i.is_empty()) {
         return IntSet::new();
     }
 
+    // Start with the smallest requirement to keep `out` as small as possible
+    requirements.sort_by_key(|i| i.iter().map(|j| j.len()).sum::<usize>());
+
+    let mut req_iter = requirements.into_iter();
     let mut out = IntSet::new();
 
-    // Add words of first character to `out`
-    let mut chars_iter = chars.iter();
-
-    // We want to fill `out` with some values.
-    loop {
-        let first = match chars_iter.next() {
-            Some(f) => f,
-            None => break,
-        };
-
-        if let Some(v) = index.get_words_with(*first) {
-            out.reserve(v.len());
-            out.extend(v.iter().copied());
-            // exit first found character
-            break;
+    match req_iter.next() {
+        Some(first) => {
+            for v in first {
+                out.extend(v.iter().copied());
+            }
+        }
+        None => {
+            // Only length bounds known (eg. `???`)
+            let Some(len_bounds) = query.len_bounds() else {
+                return IntSet::new();
+            };
+            for v in len_bounds.filter_map(|len| index.get_words_with_len(len)) {
+                out.extend(v.iter().copied());
+            }
+            return out;
         }
     }
 
-    for v in chars_iter.filter_map(|c| index.get_words_with(*c)) {
-        out.retain(|i| v.contains(&i));
+    for postings in req_iter {
+        if passed() {
+            return IntSet::new();
+        }
+        out.retain(|i| postings.iter().any(|p| p.contains(&i)));
         if out.is_empty() {
             return IntSet::new();
         }
     }
 
+    if passed() {
+        return IntSet::new();
+    }
+
+    if let Some(len_bounds) = query.len_bounds() {
+        let lengths: Vec<_> = len_bounds
+            .filter_map(|len| index.get_words_with_len(len))
+            .collect();
+        out.retain(|i| lengths.iter().any(|p| p.contains(&i)));
+    }
+
     out
 }
+
+#[inline]
+fn postings(index: &RegexSearchIndex, requirement: Requirement) -> Option<&HashSet<u32>> {
+    match requirement {
+        Requirement::Char(c) => index.get_words_with(c),
+        Requirement::Script(script) => index.get_words_with_script(script),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_find_words_deadline() {
+        let mut index = RegexSearchIndex::new();
+        index.add_term("宇宙飛行士", 1);
+        index.add_term("飛行機", 2);
+        index.add_term("宇宙", 3);
+        let query = RegexSQuery::new("宇宙*行士").unwrap();
+
+        let future = Instant::now() + Duration::from_secs(60);
+        let found = find_words(&index, &query, Some(future));
+        assert_eq!(found.into_iter().collect::<Vec<_>>(), vec![1]);
+
+        // Stops intersecting the requirements once the deadline passed
+        let past = Instant::now() - Duration::from_millis(1);
+        assert!(find_words(&index, &query, Some(past)).is_empty());
+    }
+}
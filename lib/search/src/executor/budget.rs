@@ -0,0 +1,37 @@
+//! Time budgets of searches. A search which exceeds the budget of its target stops and returns
+//! the results found so far.
+
+use once_cell::sync::Lazy;
+use std::{sync::RwLock, time::Duration};
+use types::jotoba::search::SearchTarget;
+
+static BUDGETS: Lazy<RwLock<Vec<(SearchTarget, Duration)>>> = Lazy::new(|| RwLock::new(vec![]));
+
+/// Sets the time budget for searches of `target`
+pub fn set(target: SearchTarget, budget: Duration) {
+    let mut budgets = BUDGETS.write().unwrap();
+    budgets.retain(|i| i.0 != target);
+    budgets.push((target, budget));
+}
+
+/// Returns the time budget for searches of `target` or `None` if they are unlimited
+pub fn get(target: SearchTarget) -> Option<Duration> {
+    BUDGETS
+        .read()
+        .unwrap()
+        .iter()
+        .find(|i| i.0 == target)
+        .map(|i| i.1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_budget() {
+        set(SearchTarget::Names, Duration::from_millis(10));
+        set(SearchTarget::Names, Duration::from_millis(20));
+        assert_eq!(get(SearchTarget::Names), Some(Duration::from_millis(20)));
+    }
+}
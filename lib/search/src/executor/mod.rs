@@ -1,18 +1,28 @@
+pub mod budget;
 pub mod out_builder;
 pub mod producer;
 pub mod search_result;
 pub mod searchable;
 
-use std::time::Instant;
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
 
-use engine::{pushable::FilteredMaxCounter, utils::page_from_pqueue_with_max_dist};
+use engine::{
+    pushable::{FilteredMaxCounter, Pushable},
+    relevance::item::RelItem,
+    utils::page_from_pqueue_with_max_dist,
+};
 use log::debug;
 use out_builder::OutputBuilder;
-use search_result::SearchResult;
+use producer::{Dependency, Producer};
+use rayon::prelude::*;
+use search_result::{ItemExplanation, ProducerTiming, SearchResult};
 use searchable::Searchable;
 use types::jotoba::search::guess::{Guess, GuessType};
 
-use crate::executor::out_builder::OutputAddable;
+use crate::{cache, executor::out_builder::OutputAddable, report};
 
 /// Max items to count for estimation
 pub const MAX_ESTIMATE: usize = 100;
@@ -20,41 +30,212 @@ pub const MAX_ESTIMATE: usize = 100;
 /// Executes a search
 pub struct SearchExecutor<S: Searchable> {
     search: S,
+    deadline: Option<Instant>,
 }
 
 impl<S: Searchable> SearchExecutor<S> {
     /// Creates a new SearchExecutor
     #[inline]
     pub fn new(search: S) -> Self {
-        Self { search }
+        Self {
+            search,
+            deadline: None,
+        }
+    }
+
+    /// Sets the point in time at which the search stops. Overwrites the budget configured for the
+    /// searches target
+    #[inline]
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
     }
 
     /// Executes the search
+    #[inline]
     pub fn run(self) -> SearchResult<S::OutItem, S::ResAdd> {
+        self.execute(false, false, |_, _, _| {})
+    }
+
+    /// Executes the search and collects which producer found each item and how its relevance
+    /// has been calculated. Results aren't cached
+    #[inline]
+    pub fn run_explained(self) -> SearchResult<S::OutItem, S::ResAdd> {
+        self.execute(false, true, |_, _, _| {})
+    }
+
+    /// Executes the search or returns the cached result of an equal search
+    pub fn run_cached(self) -> SearchResult<S::OutItem, S::ResAdd>
+    where
+        S::OutItem: Clone + Send + Sync + 'static,
+        S::ResAdd: Clone + Send + Sync + 'static,
+    {
+        if !cache::is_enabled() {
+            return self.run();
+        }
+
+        let query = self.search.get_query();
+        if let Some(res) = cache::get(query) {
+            return res;
+        }
+
+        let query = query.clone();
+        let res = self.run();
+        // Don't keep partial results of searches that ran out of time
+        if !res.incomplete {
+            cache::insert(&query, res.clone());
+        }
+        res
+    }
+
+    /// Executes the search and calls `on_produced` after each producer that ran with the
+    /// producers name and the best new items it found. Items are reported only once, so the
+    /// final ordering has to be taken from the returned result
+    pub fn run_streamed<F>(self, mut on_produced: F) -> SearchResult<S::OutItem, S::ResAdd>
+    where
+        F: FnMut(&str, Vec<S::OutItem>),
+    {
+        let limit = self.search.get_query().settings.page_size as usize;
+        let mut reported: HashSet<S::Item> = HashSet::new();
+
+        self.execute(true, false, |search, name, out| {
+            let mut added = out.take_added();
+            added.sort_by(|a, b| b.relevance.total_cmp(&a.relevance));
+
+            let items: Vec<_> = added
+                .into_iter()
+                .filter(|i| reported.insert(i.item.clone()))
+                .take(limit)
+                .map(|i| search.to_output_item(i.item))
+                .collect();
+
+            on_produced(name, items);
+        })
+    }
+
+    fn execute<F>(
+        self,
+        track: bool,
+        explain: bool,
+        mut on_produced: F,
+    ) -> SearchResult<S::OutItem, S::ResAdd>
+    where
+        F: FnMut(&S, &str, &mut OutputBuilder<S::Item, S::ResAdd>),
+    {
+        let search_start = Instant::now();
         let query = self.search.get_query();
         let limit = query.settings.page_size as usize;
         let offset = query.page_offset;
 
+        let deadline = self
+            .deadline
+            .or_else(|| budget::get(query.target).map(|i| search_start + i));
+
         let mut out = OutputBuilder::new(|i| self.search.filter(i), limit + offset);
+        out.set_deadline(deadline);
+        if track {
+            out.track_added();
+        }
+        if explain {
+            out.explain();
+        }
 
-        for prod in self.search.get_producer() {
-            if !prod.should_run(out.p.total_pushed()) {
-                continue;
+        let producers = self.search.get_producer();
+        let mut timings = vec![];
+        let mut pos = 0;
+        while pos < producers.len() && !out.timed_out() {
+            let group = &producers[pos..pos + group_len(&producers[pos..])];
+            pos += group.len();
+
+            let found = out.p.total_pushed();
+            let runnable: Vec<_> = group.iter().filter(|i| i.should_run(found)).collect();
+
+            // Independent producers run concurrently into separate outputs which get merged in
+            // the order the producers are declared in
+            let detached: Vec<Option<Detached<S::Item>>> = if runnable.len() > 1 {
+                runnable
+                    .par_iter()
+                    .map(|prod| {
+                        Some(produce_detached(
+                            &self.search,
+                            &***prod,
+                            limit + offset,
+                            deadline,
+                            explain,
+                        ))
+                    })
+                    .collect()
+            } else {
+                vec![None]
+            };
+
+            for (prod, detached) in runnable.into_iter().zip(detached) {
+                let name = prod.name();
+                out.set_producer(&name);
+                let before = out.p.total_pushed();
+
+                let dur = match detached {
+                    Some(detached) => {
+                        if prod.dependency() == Dependency::Count && !prod.should_run(before) {
+                            continue;
+                        }
+                        out.merge(detached.items, detached.explanations);
+                        if detached.stopped {
+                            out.set_stopped();
+                        }
+                        detached.duration
+                    }
+                    None => {
+                        let start = Instant::now();
+                        prod.produce(&mut out);
+                        start.elapsed()
+                    }
+                };
+
+                let after = out.p.total_pushed();
+                debug!("{name}: {} Elements in {:?}", after - before, dur);
+                on_produced(&self.search, &name, &mut out);
+                metrics::observe_producer(&name, dur);
+                timings.push(ProducerTiming {
+                    name,
+                    duration: dur,
+                    found: after - before,
+                });
             }
-            let before = out.p.total_pushed();
-            let start = Instant::now();
-            prod.produce(&mut out);
-            let dur = start.elapsed();
-            let after = out.p.total_pushed();
-            let name = prod.name();
-            debug!("{name}: {} Elements in {:?}", after - before, dur);
         }
 
+        // Only searches of which a producer got stopped or skipped are incomplete
+        let incomplete = out.stopped();
         self.search.mod_output(&mut out);
 
+        let mut res = self.build_result(out, limit, offset);
+        res.timings = timings;
+        res.incomplete = incomplete;
+
+        let duration = search_start.elapsed();
+        let target = format!("{:?}", query.target);
+        if incomplete {
+            debug!(
+                "Search for {:?} timed out after {duration:?}",
+                query.raw_query
+            );
+            metrics::observe_timeout(&target);
+        }
+        metrics::observe_search(&target, duration, res.total);
+        report::report(query, duration, &res.timings, res.total);
+        res
+    }
+
+    /// Converts the collected output into the requested page of results
+    fn build_result(
+        &self,
+        mut out: OutputBuilder<S::Item, S::ResAdd>,
+        limit: usize,
+        offset: usize,
+    ) -> SearchResult<S::OutItem, S::ResAdd> {
         if out.is_empty() && out.output_add.is_empty() {
             let mut res = SearchResult::default();
-            res.other_data = out.output_add;
+            res.other_data = std::mem::take(&mut out.output_add);
             return res;
         }
 
@@ -72,20 +253,40 @@ impl<S: Searchable> SearchExecutor<S> {
         assert_eq!(out.p.total_pushed(), out.rel_list.len());
 
         let max_top_dist = self.search.max_top_dist().unwrap_or(0.0);
-        let items: Vec<_> =
-            page_from_pqueue_with_max_dist(limit, offset, max_top_dist, out.max, out.p)
-                .into_iter()
-                .map(|i| self.search.to_output_item(i.item))
-                .collect();
+        let page = page_from_pqueue_with_max_dist(limit, offset, max_top_dist, out.max, out.p);
+
+        let explanations = match out.explanations {
+            Some(mut explanations) => page
+                .iter()
+                .map(|i| {
+                    let mut explanation = explanations.remove(&i.item).unwrap_or_default();
+                    explanation.relevance = i.relevance;
+                    explanation
+                })
+                .collect(),
+            None => vec![],
+        };
+
+        let items: Vec<_> = page
+            .into_iter()
+            .map(|i| self.search.to_output_item(i.item))
+            .collect();
 
-        SearchResult::with_other_data(items, len, out.output_add)
+        let mut res = SearchResult::with_other_data(items, len, out.output_add);
+        res.explanations = explanations;
+        res
     }
 
     pub fn guess(&self) -> Option<Guess> {
         let start = Instant::now();
+        let query = self.search.get_query();
+        let deadline = self
+            .deadline
+            .or_else(|| budget::get(query.target).map(|i| start + i));
 
         let mut counter =
-            FilteredMaxCounter::<S::Item>::new(MAX_ESTIMATE + 1, |i| self.search.filter(i));
+            FilteredMaxCounter::<S::Item>::new(MAX_ESTIMATE + 1, |i| self.search.filter(i))
+                .with_deadline(deadline);
 
         // Keep track of real count to give `should_run` a correct value
         let mut c = 0;
@@ -100,15 +301,16 @@ impl<S: Searchable> SearchExecutor<S> {
             // Add たった今数えた量 to `c`
             c += counter.val() - old_counter;
 
-            if counter.is_full() {
+            if counter.is_full() || counter.timed_out() {
                 break;
             }
         }
 
         let sum = counter.val();
 
+        // Counting stopped early, so there might be more results
         let gt;
-        if sum > MAX_ESTIMATE {
+        if sum > MAX_ESTIMATE || counter.timed_out() {
             gt = GuessType::MoreThan;
         } else {
             gt = GuessType::Accurate;
@@ -118,3 +320,204 @@ impl<S: Searchable> SearchExecutor<S> {
         Some(Guess::new(sum.min(MAX_ESTIMATE) as u32, gt))
     }
 }
+
+/// Output of a producer which ran concurrently to others
+struct Detached<I> {
+    items: Vec<RelItem<I>>,
+    explanations: HashMap<I, ItemExplanation>,
+    duration: Duration,
+    /// `true` if the producer stopped because of the deadline
+    stopped: bool,
+}
+
+/// Returns the amount of producers at the beginning of `producers` which can run concurrently
+pub(crate) fn group_len<S: Searchable>(producers: &[Box<dyn Producer<Target = S> + '_>]) -> usize {
+    producers
+        .iter()
+        .take_while(|i| i.dependency() != Dependency::Sequential)
+        .count()
+        .max(1)
+}
+
+/// Runs `prod` into a separate output so it can be executed concurrently to other producers
+fn produce_detached<S: Searchable>(
+    search: &S,
+    prod: &(dyn Producer<Target = S> + '_),
+    len: usize,
+    deadline: Option<Instant>,
+    explain: bool,
+) -> Detached<S::Item> {
+    // Filter right away so filtered items can't take the place of the ones kept
+    let mut out = OutputBuilder::<S::Item, S::ResAdd>::new(|i| search.filter(i), len);
+    out.track_added();
+    out.set_deadline(deadline);
+    if explain {
+        out.explain();
+    }
+
+    let start = Instant::now();
+    prod.produce(&mut out);
+
+    Detached {
+        items: out.take_added(),
+        explanations: out.explanations.take().unwrap_or_default(),
+        duration: start.elapsed(),
+        stopped: out.stopped(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query::Query;
+    use std::thread;
+
+    struct TestSearch {
+        query: Query,
+        producer: Vec<Box<dyn Producer<Target = Self>>>,
+    }
+
+    impl TestSearch {
+        fn new(producer: Vec<TestProducer>) -> Self {
+            let producer = producer
+                .into_iter()
+                .map(|i| Box::new(i) as Box<dyn Producer<Target = Self>>)
+                .collect();
+            Self {
+                query: Query::default(),
+                producer,
+            }
+        }
+    }
+
+    impl Searchable for TestSearch {
+        type Item = u32;
+        type OutItem = u32;
+        type ResAdd = ();
+
+        fn get_producer<'s>(&'s self) -> &Vec<Box<dyn Producer<Target = Self> + 's>> {
+            &self.producer
+        }
+
+        fn get_query(&self) -> &Query {
+            &self.query
+        }
+
+        fn to_output_item(&self, item: Self::Item) -> Self::OutItem {
+            item
+        }
+
+        fn filter(&self, item: &Self::Item) -> bool {
+            *item >= FILTERED
+        }
+    }
+
+    /// Items from this value on are filtered
+    const FILTERED: u32 = 1000;
+
+    #[derive(Default)]
+    struct TestProducer {
+        items: Vec<(u32, f32)>,
+        sleep: Duration,
+        check_deadline: bool,
+        concurrent: bool,
+    }
+
+    impl Producer for TestProducer {
+        type Target = TestSearch;
+
+        fn produce(&self, out: &mut OutputBuilder<u32, ()>) {
+            thread::sleep(self.sleep);
+            for (item, relevance) in self.items.iter() {
+                if self.check_deadline && out.timed_out() {
+                    break;
+                }
+                out.push(RelItem::new(*item, *relevance));
+            }
+        }
+
+        fn dependency(&self) -> Dependency {
+            if self.concurrent {
+                Dependency::None
+            } else {
+                Dependency::Sequential
+            }
+        }
+    }
+
+    fn run_with_deadline(producer: Vec<TestProducer>) -> SearchResult<u32, ()> {
+        let deadline = Instant::now() + Duration::from_millis(10);
+        SearchExecutor::new(TestSearch::new(producer))
+            .with_deadline(deadline)
+            .run()
+    }
+
+    #[test]
+    fn test_finished_after_deadline() {
+        let slow = TestProducer {
+            items: vec![(1, 1.0)],
+            sleep: Duration::from_millis(30),
+            ..Default::default()
+        };
+        let res = run_with_deadline(vec![slow]);
+        assert!(!res.incomplete);
+        assert_eq!(res.items, vec![1]);
+    }
+
+    #[test]
+    fn test_skipped_after_deadline() {
+        let slow = TestProducer {
+            items: vec![(1, 1.0)],
+            sleep: Duration::from_millis(30),
+            ..Default::default()
+        };
+        let skipped = TestProducer {
+            items: vec![(2, 1.0)],
+            ..Default::default()
+        };
+        let res = run_with_deadline(vec![slow, skipped]);
+        assert!(res.incomplete);
+        assert_eq!(res.items, vec![1]);
+    }
+
+    #[test]
+    fn test_stopped_by_deadline() {
+        let stopped = TestProducer {
+            items: vec![(1, 1.0)],
+            sleep: Duration::from_millis(30),
+            check_deadline: true,
+            ..Default::default()
+        };
+        let res = run_with_deadline(vec![stopped]);
+        assert!(res.incomplete);
+        assert!(res.items.is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_equals_sequential() {
+        let run = |concurrent: bool| {
+            // Filtered items are rated best to make sure they can't push out any other item
+            let filtered = (0..20).map(|i| (FILTERED + i, 10.0));
+            let first = TestProducer {
+                items: filtered
+                    .chain((0..5).map(|i| (i, 1.0 + i as f32)))
+                    .collect(),
+                concurrent,
+                ..Default::default()
+            };
+            let second = TestProducer {
+                items: (5..8).map(|i| (i, i as f32 / 10.0)).collect(),
+                concurrent,
+                ..Default::default()
+            };
+            let search = TestSearch::new(vec![first, second]);
+            SearchExecutor::new(search).run()
+        };
+
+        let sequential = run(false);
+        let concurrent = run(true);
+        assert_eq!(sequential.items, vec![4, 3, 2, 1, 0, 7, 6, 5]);
+        assert_eq!(concurrent.items, sequential.items);
+        assert_eq!(concurrent.total, sequential.total);
+    }
+}
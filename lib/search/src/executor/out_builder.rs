@@ -1,6 +1,10 @@
-use engine::{pushable::Pushable, relevance::item::RelItem};
+use super::search_result::ItemExplanation;
+use engine::{
+    pushable::Pushable,
+    relevance::{explain::Explanation, item::RelItem},
+};
 use priority_container::StableUniquePrioContainerMax;
-use std::hash::Hash;
+use std::{cell::Cell, collections::HashMap, hash::Hash, time::Instant};
 
 pub struct OutputBuilder<'a, I, OA> {
     pub(crate) p: StableUniquePrioContainerMax<RelItem<I>>,
@@ -8,6 +12,16 @@ pub struct OutputBuilder<'a, I, OA> {
     pub(crate) output_add: OA,
     pub(crate) rel_list: Vec<f32>,
     pub(crate) max: f32,
+    /// Items added since the last call of `take_added`. Only collected if tracking is enabled
+    added: Option<Vec<RelItem<I>>>,
+    /// Point in time after which producers should stop searching
+    deadline: Option<Instant>,
+    /// Set once `timed_out` returned `true`, so a producer stopped because of the deadline
+    stopped: Cell<bool>,
+    /// How each item got found and scored. Only collected if explaining is enabled
+    pub(crate) explanations: Option<HashMap<I, ItemExplanation>>,
+    /// Name of the currently running producer
+    producer: String,
 }
 
 impl<'a, I: Eq + Hash + Clone, OA: OutputAddable> OutputBuilder<'a, I, OA> {
@@ -19,9 +33,68 @@ impl<'a, I: Eq + Hash + Clone, OA: OutputAddable> OutputBuilder<'a, I, OA> {
             output_add: OA::default(),
             rel_list: vec![],
             max: 0.0,
+            added: None,
+            deadline: None,
+            stopped: Cell::new(false),
+            explanations: None,
+            producer: String::new(),
         }
     }
 
+    /// Enables tracking of added items
+    #[inline]
+    pub(crate) fn track_added(&mut self) {
+        self.added.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns all items added since the last call. Returns an empty list if tracking is disabled
+    #[inline]
+    pub(crate) fn take_added(&mut self) -> Vec<RelItem<I>> {
+        self.added.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    /// Enables collecting explanations of the items scores
+    #[inline]
+    pub(crate) fn explain(&mut self) {
+        self.explanations.get_or_insert_with(HashMap::new);
+    }
+
+    /// Sets the name of the producer pushing the following items
+    #[inline]
+    pub(crate) fn set_producer(&mut self, name: &str) {
+        self.producer.clear();
+        self.producer.push_str(name);
+    }
+
+    /// Sets the deadline of the search
+    #[inline]
+    pub(crate) fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    /// Returns `true` if the deadline of the search has passed. Callers have to stop searching
+    /// if so, since the search gets reported as incomplete
+    #[inline]
+    pub fn timed_out(&self) -> bool {
+        let timed_out = self.deadline.map_or(false, |i| Instant::now() >= i);
+        if timed_out {
+            self.stopped.set(true);
+        }
+        timed_out
+    }
+
+    /// Returns `true` if a producer stopped or got skipped because the deadline has passed
+    #[inline]
+    pub(crate) fn stopped(&self) -> bool {
+        self.stopped.get()
+    }
+
+    /// Marks the output as stopped by the deadline
+    #[inline]
+    pub(crate) fn set_stopped(&mut self) {
+        self.stopped.set(true);
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.p.len()
@@ -35,14 +108,26 @@ impl<'a, I: Eq + Hash + Clone, OA: OutputAddable> OutputBuilder<'a, I, OA> {
     /// Pushes an element into the output and  returns `true` if it was not filtered out
     #[inline]
     pub fn push(&mut self, item: RelItem<I>) -> bool {
+        self.push_inner(item, None)
+    }
+
+    fn push_inner(&mut self, item: RelItem<I>, explanation: Option<Explanation>) -> bool {
         if !(self.filter)(&item.item) {
+            if self.explanations.is_some() {
+                self.add_explanation(&item, explanation);
+            }
+
             if self.max < item.relevance {
                 self.max = item.relevance;
             }
 
             let rel = item.relevance;
+            let tracked = self.added.is_some().then(|| item.clone());
             if self.p.insert(item) {
                 self.rel_list.push(rel);
+                if let (Some(added), Some(item)) = (&mut self.added, tracked) {
+                    added.push(item);
+                }
             }
 
             return true;
@@ -50,6 +135,35 @@ impl<'a, I: Eq + Hash + Clone, OA: OutputAddable> OutputBuilder<'a, I, OA> {
 
         false
     }
+
+    /// Pushes items collected in a separate output together with their explanations
+    pub(crate) fn merge(
+        &mut self,
+        items: Vec<RelItem<I>>,
+        mut explanations: HashMap<I, ItemExplanation>,
+    ) {
+        for item in items {
+            let score = explanations.remove(&item.item).and_then(|i| i.score);
+            self.push_inner(item, score);
+        }
+    }
+
+    /// Keeps the explanation of the highest rated occurrence of an item
+    fn add_explanation(&mut self, item: &RelItem<I>, score: Option<Explanation>) {
+        let explanations = self.explanations.as_mut().unwrap();
+        if let Some(prev) = explanations.get(&item.item) {
+            if prev.relevance >= item.relevance {
+                return;
+            }
+        }
+
+        let explanation = ItemExplanation {
+            producer: self.producer.clone(),
+            relevance: item.relevance,
+            score,
+        };
+        explanations.insert(item.item.clone(), explanation);
+    }
 }
 
 impl<'a, I: Eq + Hash + Clone, OA: OutputAddable> Pushable for OutputBuilder<'a, I, OA> {
@@ -60,6 +174,26 @@ impl<'a, I: Eq + Hash + Clone, OA: OutputAddable> Pushable for OutputBuilder<'a,
     fn push(&mut self, i: Self::Item) -> bool {
         self.push(i)
     }
+
+    #[inline]
+    fn push_explained(&mut self, i: Self::Item, explanation: Explanation) -> bool {
+        self.push_inner(i, Some(explanation))
+    }
+
+    #[inline]
+    fn wants_explanation(&self) -> bool {
+        self.explanations.is_some()
+    }
+
+    #[inline]
+    fn timed_out(&self) -> bool {
+        self.timed_out()
+    }
+
+    #[inline]
+    fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
 }
 
 pub trait OutputAddable: Default {
@@ -2,7 +2,22 @@ use super::{out_builder::OutputBuilder, searchable::Searchable};
 use engine::pushable::FilteredMaxCounter;
 use std::any::type_name;
 
-pub trait Producer {
+/// How a producer depends on the output of the producers running before it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dependency {
+    /// The producer reads or modifies the output beyond pushing items, eg. its `output_add` or
+    /// the best relevance found so far, and has to run after all previous producers finished
+    Sequential,
+    /// Only `should_run` depends on the amount of items found before. The producer may run
+    /// concurrently and its items get dropped if `should_run` returns `false` once all previous
+    /// producers finished. Requires `should_run` to never return `true` for a higher amount after
+    /// returning `false` for a lower one
+    Count,
+    /// The producer only pushes items and doesn't depend on previous producers at all
+    None,
+}
+
+pub trait Producer: Sync {
     type Target: Searchable;
 
     fn produce(
@@ -17,6 +32,11 @@ pub trait Producer {
         true
     }
 
+    /// Producers which don't depend on the output of previous ones get executed concurrently
+    fn dependency(&self) -> Dependency {
+        Dependency::Sequential
+    }
+
     fn estimate_to(&self, _out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {}
 
     fn name(&self) -> String {
@@ -1,4 +1,5 @@
-use std::ops::Deref;
+use engine::relevance::explain::Explanation;
+use std::{ops::Deref, time::Duration};
 
 /// The final result of a search
 #[derive(Clone, Debug)]
@@ -6,6 +7,33 @@ pub struct SearchResult<T, O = ()> {
     pub items: Vec<T>,
     pub total: usize,
     pub other_data: O,
+    /// Time spent in each producer that ran
+    pub timings: Vec<ProducerTiming>,
+    /// `true` if the search ran out of time and the result only contains the items found so far
+    pub incomplete: bool,
+    /// How each item got found and scored in the same order as `items`. Only filled by
+    /// `SearchExecutor::run_explained`
+    pub explanations: Vec<ItemExplanation>,
+}
+
+/// Time a single producer took to produce its items
+#[derive(Clone, Debug)]
+pub struct ProducerTiming {
+    pub name: String,
+    pub duration: Duration,
+    /// Amount of items the producer added
+    pub found: usize,
+}
+
+/// How a single item of a search result got found and scored
+#[derive(Clone, Debug, Default)]
+pub struct ItemExplanation {
+    /// Producer which found the item with the highest relevance
+    pub producer: String,
+    /// Final relevance of the item
+    pub relevance: f32,
+    /// Calculation of the score. `None` if the producer didn't use a relevance engine
+    pub score: Option<Explanation>,
 }
 
 impl<T> SearchResult<T, ()> {
@@ -17,6 +45,9 @@ impl<T> SearchResult<T, ()> {
             items,
             total,
             other_data: (),
+            timings: vec![],
+            incomplete: false,
+            explanations: vec![],
         }
     }
 
@@ -27,6 +58,9 @@ impl<T> SearchResult<T, ()> {
             items,
             total,
             other_data: (),
+            timings: vec![],
+            incomplete: false,
+            explanations: vec![],
         }
     }
 }
@@ -39,6 +73,9 @@ impl<T, O> SearchResult<T, O> {
             items,
             total,
             other_data,
+            timings: vec![],
+            incomplete: false,
+            explanations: vec![],
         }
     }
 
@@ -65,6 +102,9 @@ impl<T, O: Default> SearchResult<T, O> {
             items,
             total,
             other_data: O::default(),
+            timings: vec![],
+            incomplete: false,
+            explanations: vec![],
         }
     }
 }
@@ -79,6 +119,9 @@ where
             items: vec![],
             total: 0,
             other_data: O::default(),
+            timings: vec![],
+            incomplete: false,
+            explanations: vec![],
         }
     }
 }
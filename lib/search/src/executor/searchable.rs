@@ -5,8 +5,10 @@ use super::{
 use crate::query::Query;
 use std::{fmt::Debug, hash::Hash};
 
-pub trait Searchable {
-    type Item: Clone + Eq + Hash + Debug;
+/// A search executed by the [`SearchExecutor`](super::SearchExecutor). Has to be `Sync` since
+/// independent producers run concurrently and filter their items with [`Searchable::filter`]
+pub trait Searchable: Sync {
+    type Item: Clone + Eq + Hash + Debug + Send;
     type OutItem;
     type ResAdd: OutputAddable;
 
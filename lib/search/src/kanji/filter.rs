@@ -0,0 +1,68 @@
+use crate::query::{
+    expr::ExprEval,
+    tags::{Tag, FREQUENCY_BUCKET_SIZE},
+    Query,
+};
+use jp_utils::JapaneseExt;
+use types::jotoba::kanji::Kanji;
+
+/// Returns `true` if `kanji` matches all tags and the expression of `query`
+pub(crate) fn matches(query: &Query, kanji: &Kanji) -> bool {
+    let eval = KanjiExprEval { kanji };
+
+    query.tags.iter().all(|i| eval.eval_tag(i).unwrap_or(true)) && query.matches_expr(&eval)
+}
+
+/// Evaluates query expressions for a single kanji
+struct KanjiExprEval<'a> {
+    kanji: &'a Kanji,
+}
+
+impl<'a> ExprEval for KanjiExprEval<'a> {
+    fn eval_tag(&self, tag: &Tag) -> Option<bool> {
+        let k = self.kanji;
+        Some(match tag {
+            Tag::Jlpt(jlpt) => k.jlpt == Some(*jlpt),
+            Tag::GenkiLesson(lesson) => resources::get()
+                .kanji()
+                .by_genki_lesson(*lesson)
+                .map_or(false, |i| i.contains(&k.literal)),
+            Tag::StrokeCount(min, max) => (*min..=*max).contains(&k.stroke_count),
+            Tag::Grade(grade) => k.grade == Some(*grade),
+            Tag::Frequency(bucket) => k
+                .frequency
+                .map_or(false, |i| frequency_bucket(i) == *bucket),
+            Tag::Radical(radical) => {
+                k.radical.literal == *radical || k.radical.alternative == Some(*radical)
+            }
+            _ => return None,
+        })
+    }
+
+    fn eval_term(&self, term: &str) -> bool {
+        if term.is_japanese() {
+            return term.contains(self.kanji.literal) || self.kanji.has_reading(term);
+        }
+
+        self.kanji.meanings.iter().any(|i| i.to_lowercase() == term)
+    }
+}
+
+/// Returns the frequency bucket of a kanjis frequency rank
+#[inline]
+fn frequency_bucket(rank: u16) -> u8 {
+    (rank.saturating_sub(1) / FREQUENCY_BUCKET_SIZE + 1) as u8
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frequency_bucket() {
+        assert_eq!(frequency_bucket(1), 1);
+        assert_eq!(frequency_bucket(500), 1);
+        assert_eq!(frequency_bucket(501), 2);
+        assert_eq!(frequency_bucket(2500), 5);
+    }
+}
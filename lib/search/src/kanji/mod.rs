@@ -1,52 +1,155 @@
+mod filter;
 mod order;
 pub mod result;
 mod tag_only;
 
 use self::result::KanjiResult;
 use super::query::Query;
-use crate::{engine::words::native::Engine, query::QueryLang, word::order::native::NativeOrder};
+use crate::{
+    cache,
+    engine::{kanji::meaning, words::native::Engine},
+    executor::budget,
+    query::QueryLang,
+    report,
+    word::order::native::NativeOrder,
+};
 use engine::task::SearchTask;
 use error::Error;
+use itertools::Itertools;
 use jp_utils::JapaneseExt;
+use order::MeaningOrder;
 use result::Item;
+use std::{cell::Cell, collections::HashSet, time::Instant};
 use types::jotoba::{
     kanji::Kanji,
-    search::guess::{Guess, GuessType},
+    language::Language,
+    search::{
+        guess::{Guess, GuessType},
+        SearchTarget,
+    },
 };
 
+/// Max amount of kanji to find by meaning per language
+const MEANING_LIMIT: usize = 100;
+
+/// Min relevance of kanji found by meaning
+const MEANING_THRESHOLD: f32 = 0.2;
+
 /// The entry of a kanji search
 pub fn search(query: &Query) -> Result<KanjiResult, Error> {
+    if let Some(res) = cache::get(query) {
+        return Ok(res);
+    }
+
+    let start = Instant::now();
+    let deadline = Deadline::new(start);
+    let mut res = search_kanji(query, &deadline)?;
+    res.incomplete = deadline.stopped();
+
+    // Don't keep partial results of searches that ran out of time
+    if cache::is_enabled() && !res.incomplete {
+        cache::insert(query, res.clone());
+    }
+
+    let duration = start.elapsed();
+    if res.incomplete {
+        log::debug!(
+            "Kanji search for {:?} timed out after {duration:?}",
+            query.raw_query
+        );
+        metrics::observe_timeout("Kanji");
+    }
+    metrics::observe_search("Kanji", duration, res.total_len);
+    report::report(query, duration, &[], res.total_len);
+    Ok(res)
+}
+
+/// Point in time after which a kanji search stops and returns the kanji found so far
+struct Deadline {
+    at: Option<Instant>,
+    /// Set once the deadline was checked after it had passed
+    stopped: Cell<bool>,
+}
+
+impl Deadline {
+    fn new(start: Instant) -> Self {
+        Self {
+            at: budget::get(SearchTarget::Kanji).map(|i| start + i),
+            stopped: Cell::new(false),
+        }
+    }
+
+    /// Returns `true` if the deadline has passed and the search has to stop
+    fn passed(&self) -> bool {
+        let passed = self.at.map_or(false, |i| Instant::now() >= i);
+        if passed {
+            self.stopped.set(true);
+        }
+        passed
+    }
+
+    /// Returns `true` if the search stopped because of the deadline
+    fn stopped(&self) -> bool {
+        self.stopped.get()
+    }
+}
+
+fn search_kanji(query: &Query, deadline: &Deadline) -> Result<KanjiResult, Error> {
     if query.form.is_tag_only() {
-        return tag_only::search(query);
+        return tag_only::search(query, deadline);
     }
 
     let query_str = format_query(&query.query_str);
 
-    let res = match query.q_lang {
-        QueryLang::Japanese => by_japanese_query(&query.query_str),
-        QueryLang::Korean => by_korean_reading(&query.query_str),
-        QueryLang::Foreign | QueryLang::Undetected => by_meaning(&query.query_str),
-    };
-
-    // TODO: don't use this items in v2 since compound words don't need to be loaded
-    // here anymore
-    let mut items = to_item(res, &query);
+    let mut res = by_text_alternatives(query, deadline);
+    res.retain(|i| filter::matches(query, i));
 
-    if !query_str.is_japanese() {
-        items.sort_by(order::default);
+    // Results found by meaning are already ranked
+    if query.q_lang == QueryLang::Korean && !query_str.is_japanese() {
+        res.sort_by(order::default);
     }
 
-    let total_len = items.len();
+    let total_len = res.len();
 
     let page_size = query.settings.page_size as usize;
 
-    let items = items
+    let res = res
         .into_iter()
         .skip(query.page_offset(page_size))
         .take(page_size)
         .collect::<Vec<_>>();
 
-    Ok(KanjiResult { items, total_len })
+    // TODO: don't use this items in v2 since compound words don't need to be loaded
+    // here anymore
+    let items = to_item(res, &query);
+
+    Ok(KanjiResult {
+        items,
+        total_len,
+        incomplete: false,
+    })
+}
+
+/// Finds kanji for the text of every branch of the query. Kanji found by multiple branches are
+/// only kept once, at the position of the first branch finding them
+fn by_text_alternatives(query: &Query, deadline: &Deadline) -> Vec<Kanji> {
+    let mut seen = HashSet::new();
+    let mut res = vec![];
+
+    for q_str in query.text_alternatives() {
+        if deadline.passed() {
+            break;
+        }
+
+        let found = match query.q_lang {
+            QueryLang::Japanese => by_japanese_query(&q_str),
+            QueryLang::Korean => by_korean_reading(&q_str, deadline),
+            QueryLang::Foreign | QueryLang::Undetected => by_meaning(query, &q_str, deadline),
+        };
+        res.extend(found.into_iter().filter(|i| seen.insert(i.literal)));
+    }
+
+    res
 }
 
 /// Find a kanji by its literal
@@ -80,10 +183,11 @@ fn kana_search(query: &str) -> Vec<Kanji> {
         .collect()
 }
 
-fn by_korean_reading(query: &str) -> Vec<Kanji> {
+fn by_korean_reading(query: &str, deadline: &Deadline) -> Vec<Kanji> {
     resources::get()
         .kanji()
         .iter()
+        .take_while(|_| !deadline.passed())
         .filter(|k| k.korean_h.iter().any(|kw| kw == query))
         .cloned()
         .collect()
@@ -104,13 +208,13 @@ fn kanji_from_str(text: &str) -> Vec<Kanji> {
 
 /// Guesses the amount of results a search would return with given `query`
 pub fn guess_result(query: &Query) -> Option<Guess> {
-    let query_str = &query.query_str;
-
     let kanji_storage = resources::get().kanji();
-    let guess = query_str
-        .chars()
-        .into_iter()
+    let guess = query
+        .text_alternatives()
+        .iter()
+        .flat_map(|i| i.chars())
         .filter(|i| i.is_kanji())
+        .unique()
         .filter_map(|literal| kanji_storage.by_literal(literal))
         .take(15)
         .count();
@@ -118,16 +222,53 @@ pub fn guess_result(query: &Query) -> Option<Guess> {
     Some(Guess::new(guess as u32, GuessType::Accurate))
 }
 
-/// Find kanji by mits meaning
-fn by_meaning(meaning: &str) -> Vec<Kanji> {
-    // TODO: implement proper algo kek
+/// Find kanji by their meaning in the users language and in english if enabled
+fn by_meaning(query: &Query, query_str: &str, deadline: &Deadline) -> Vec<Kanji> {
+    if indexes::get().kanji().meaning().is_none() {
+        return by_meaning_plain(query_str, deadline);
+    }
+
+    let mut languages = vec![query.lang()];
+    if query.lang() != Language::English && query.show_english() {
+        languages.push(Language::English);
+    }
+
+    let weights = query.ranking().kanji;
+    let mut found: Vec<_> = languages
+        .into_iter()
+        .take_while(|_| !deadline.passed())
+        .flat_map(|lang| {
+            SearchTask::<meaning::Engine>::with_language(query_str, lang)
+                .with_limit(MEANING_LIMIT)
+                .with_threshold(MEANING_THRESHOLD)
+                .with_custom_order(MeaningOrder::new(query_str, weights))
+                .find()
+                .into_inner()
+        })
+        .collect();
+
+    found.sort_by(|a, b| b.relevance.total_cmp(&a.relevance));
+
+    let mut seen = HashSet::new();
+    found
+        .into_iter()
+        .filter(|i| seen.insert(i.item.literal))
+        .map(|i| i.item.clone())
+        .collect()
+}
+
+/// Find kanji having `meaning` as english meaning. Used if the meaning index isn't available
+fn by_meaning_plain(meaning: &str, deadline: &Deadline) -> Vec<Kanji> {
     let meaning = meaning.to_lowercase();
-    resources::get()
+    let mut kanji: Vec<_> = resources::get()
         .kanji()
         .iter()
+        .take_while(|_| !deadline.passed())
         .filter(|i| i.meanings.contains(&meaning))
         .cloned()
-        .collect::<Vec<_>>()
+        .collect();
+    kanji.sort_by(order::default);
+    kanji
 }
 
 #[inline]
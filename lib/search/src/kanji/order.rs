@@ -1,13 +1,17 @@
-use super::result::Item;
+use crate::ranking::KanjiWeights;
+use engine::relevance::{data::SortData, explain, RelevanceEngine};
+use indexes::kanji::meaning::{format_meaning, stem};
+use ngindex::{item::IndexItem, termset::TermSet};
 use std::cmp::Ordering;
+use types::jotoba::{kanji::Kanji, language::Language};
 use utils::option_order;
 
-/// Order kanji results which were found by the kanjis meaning appropriately
-#[inline]
-pub(crate) fn default(a: &Item, b: &Item) -> Ordering {
-    let a = &a.kanji;
-    let b = &b.kanji;
+/// Highest frequency rank of kanji within KANJIDIC
+const MAX_FREQUENCY: f32 = 2500.0;
 
+/// Order kanji results by their grade, frequency and JLPT level
+#[inline]
+pub(crate) fn default(a: &Kanji, b: &Kanji) -> Ordering {
     if let Some(o) = option_order(&a.grade, &b.grade) {
         return o;
     }
@@ -22,3 +26,81 @@ pub(crate) fn default(a: &Item, b: &Item) -> Ordering {
 
     Ordering::Equal
 }
+
+/// Relevance of kanji found by their meaning
+pub(crate) struct MeaningOrder {
+    weights: KanjiWeights,
+    /// Stemmed query
+    query: String,
+}
+
+impl MeaningOrder {
+    #[inline]
+    pub(crate) fn new(query: &str, weights: KanjiWeights) -> Self {
+        let query = stem(&format_meaning(query));
+        Self { weights, query }
+    }
+
+    fn text_score(&self, kanji: &Kanji, language: Language) -> f32 {
+        let meanings: Vec<_> = kanji
+            .meanings_in(language)
+            .iter()
+            .map(|i| stem(&format_meaning(i)))
+            .collect();
+
+        if meanings.iter().any(|i| *i == self.query) {
+            return 1.0;
+        }
+
+        explain::factor("meaning mismatch", self.weights.meaning_mismatch);
+        let mut score = self.weights.meaning_mismatch;
+
+        let prefix = meanings
+            .iter()
+            .flat_map(|i| i.split(' '))
+            .any(|i| i.starts_with(self.query.as_str()));
+        if !prefix {
+            explain::factor("no prefix match", self.weights.no_prefix_match);
+            score *= self.weights.no_prefix_match;
+        }
+
+        score
+    }
+
+    fn popularity(&self, kanji: &Kanji) -> f32 {
+        let freq = kanji.frequency.map_or(MAX_FREQUENCY, |i| i as f32);
+        let mut score = 1.0 - self.weights.frequency * (freq / MAX_FREQUENCY).min(1.0);
+        explain::factor("frequency", score);
+
+        if kanji.grade.is_none() {
+            explain::factor("no grade", self.weights.no_grade);
+            score *= self.weights.no_grade;
+        }
+
+        if kanji.jlpt.is_none() {
+            explain::factor("no jlpt", self.weights.no_jlpt);
+            score *= self.weights.no_jlpt;
+        }
+
+        score
+    }
+}
+
+impl RelevanceEngine for MeaningOrder {
+    type OutItem = &'static Kanji;
+    type IndexItem = IndexItem<u32>;
+    type Query = TermSet;
+
+    fn score<'item, 'query>(
+        &self,
+        item: &SortData<'item, 'query, Self::OutItem, Self::IndexItem, Self::Query>,
+    ) -> f32 {
+        let kanji = *item.item();
+        let language = item.language().unwrap_or_default();
+
+        let sim = item.index_item().dice(item.query());
+        explain::index_relevance(sim);
+
+        sim * self.text_score(kanji, language) * self.popularity(kanji)
+    }
+}
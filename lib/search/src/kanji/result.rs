@@ -6,10 +6,12 @@ use types::jotoba::{
 };
 
 // The final result of a Kanji search
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct KanjiResult {
     pub items: Vec<Item>,
     pub total_len: usize,
+    /// `true` if the search ran out of time and the result only contains the kanji found so far
+    pub incomplete: bool,
 }
 
 #[derive(Debug, PartialEq, Clone)]
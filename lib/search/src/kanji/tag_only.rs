@@ -1,34 +1,33 @@
-use super::KanjiResult;
-use crate::query::{tags::Tag, Query};
+use super::{filter, order, Deadline, KanjiResult};
+use crate::query::{
+    tags::{Tag, FREQUENCY_BUCKET_SIZE},
+    Query,
+};
 use error::Error;
+use itertools::Itertools;
+use types::jotoba::kanji::Kanji;
+
+pub(super) fn search(query: &Query, deadline: &Deadline) -> Result<KanjiResult, Error> {
+    // Each branch uses the kanji of its shortest tag list which get filtered by all other tags.
+    // Branches without kanji tags can't select any kanji
+    let lists: Vec<_> = query
+        .branches()
+        .iter()
+        .filter_map(|i| i.tags.iter().filter_map(by_tag).min_by_key(|i| i.len()))
+        .collect();
 
-pub fn search(query: &Query) -> Result<KanjiResult, Error> {
-    let single_tag = query.tags.iter().find(|i| i.is_producer());
-
-    if single_tag.is_none() {
+    if lists.is_empty() {
         return Ok(KanjiResult::default());
     }
 
-    match single_tag.unwrap() {
-        Tag::Jlpt(jlpt) => jlpt_search(query, *jlpt),
-        Tag::GenkiLesson(genki_lesson) => genki_search(query, *genki_lesson),
-        _ => return Ok(KanjiResult::default()),
-    }
-}
-
-fn genki_search(query: &Query, genki_lesson: u8) -> Result<KanjiResult, Error> {
-    let kanji_retrieve = resources::get().kanji();
-
-    let genki_lesson = match kanji_retrieve.by_genki_lesson(genki_lesson) {
-        Some(gl) => gl,
-        None => return Ok(KanjiResult::default()),
-    };
-
-    let kanji = genki_lesson
-        .iter()
-        .filter_map(|literal| kanji_retrieve.by_literal(*literal))
-        .cloned()
-        .collect::<Vec<_>>();
+    let mut kanji: Vec<_> = lists
+        .into_iter()
+        .flatten()
+        .unique_by(|i| i.literal)
+        .take_while(|_| !deadline.passed())
+        .filter(|i| filter::matches(query, i))
+        .collect();
+    kanji.sort_by(|a, b| order::default(a, b));
 
     let total_len = kanji.len();
 
@@ -39,37 +38,45 @@ fn genki_search(query: &Query, genki_lesson: u8) -> Result<KanjiResult, Error> {
         .into_iter()
         .skip(page_offset)
         .take(page_size)
+        .cloned()
         .collect::<Vec<_>>();
 
-    let items = super::to_item(kanji, query);
-
-    Ok(KanjiResult { items, total_len })
+    Ok(KanjiResult {
+        items: super::to_item(kanji, query),
+        total_len,
+        incomplete: false,
+    })
 }
 
-fn jlpt_search(query: &Query, jlpt: u8) -> Result<KanjiResult, Error> {
+/// Returns all kanji with `tag` or `None` if `tag` isn't a kanji tag
+fn by_tag(tag: &Tag) -> Option<Vec<&'static Kanji>> {
     let kanji_retrieve = resources::get().kanji();
 
-    let jlpt_kanji = match kanji_retrieve.by_jlpt(jlpt) {
-        Some(jlpt) => jlpt,
-        None => return Ok(KanjiResult::default()),
+    let lists: Vec<&[char]> = match tag {
+        Tag::Jlpt(jlpt) => vec![list(kanji_retrieve.by_jlpt(*jlpt))],
+        Tag::GenkiLesson(lesson) => vec![list(kanji_retrieve.by_genki_lesson(*lesson))],
+        Tag::Grade(grade) => vec![list(kanji_retrieve.by_grade(*grade))],
+        Tag::StrokeCount(min, max) => (*min..=*max)
+            .map(|i| list(kanji_retrieve.by_stroke_count(i)))
+            .collect(),
+        Tag::Frequency(bucket) => {
+            let last = *bucket as u16 * FREQUENCY_BUCKET_SIZE;
+            let first = last - FREQUENCY_BUCKET_SIZE + 1;
+            vec![kanji_retrieve.by_frequency(first..=last)]
+        }
+        Tag::Radical(radical) => vec![list(kanji_retrieve.by_main_radical(*radical))],
+        _ => return None,
     };
 
-    let total_len = jlpt_kanji.len();
-
-    let page_size = query.settings.page_size as usize;
-
-    let page_offset = query.page_offset(page_size);
-
-    let jlpt_kanji = jlpt_kanji
+    let kanji = lists
         .into_iter()
-        .skip(page_offset)
-        .take(page_size)
+        .flatten()
         .filter_map(|literal| kanji_retrieve.by_literal(*literal))
-        .cloned()
-        .collect::<Vec<_>>();
+        .collect();
+    Some(kanji)
+}
 
-    Ok(KanjiResult {
-        items: super::to_item(jlpt_kanji, query),
-        total_len,
-    })
+#[inline]
+fn list(literals: Option<&Vec<char>>) -> &[char] {
+    literals.map(|i| i.as_slice()).unwrap_or_default()
 }
@@ -1,9 +1,12 @@
+pub mod cache;
 pub mod engine;
 pub mod executor;
 pub mod kanji;
 pub mod name;
 pub mod query;
 pub mod radical;
+pub mod ranking;
+pub mod report;
 pub mod sentence;
 pub mod word;
 
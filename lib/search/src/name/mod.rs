@@ -7,7 +7,7 @@ use crate::{
 };
 use producer::{
     foreign::ForeignProducer, kanji_reading::KreadingProducer, native::NativeProducer,
-    sequence::SeqProducer,
+    sequence::SeqProducer, tag::TagProducer,
 };
 use types::jotoba::names::Name;
 
@@ -26,6 +26,7 @@ impl<'a> Search<'a> {
         producer.push(Box::new(ForeignProducer::new(query)));
         producer.push(Box::new(NativeProducer::new(query)));
         producer.push(Box::new(SplitProducer::new(query)));
+        producer.push(Box::new(TagProducer::new(query)));
         Self { query, producer }
     }
 }
@@ -49,4 +50,9 @@ impl<'a> Searchable for Search<'a> {
     fn get_query(&self) -> &Query {
         self.query
     }
+
+    #[inline]
+    fn filter(&self, item: &Self::Item) -> bool {
+        !producer::filter::filter_name(self.query, item)
+    }
 }
@@ -1,8 +1,21 @@
+use super::type_factor;
+use crate::ranking::NameWeights;
 use engine::relevance::{data::SortData, RelevanceEngine};
 use ngindex::{item::IndexItem, termset::TermSet};
-use types::jotoba::names::Name;
+use types::jotoba::names::{name_type::NameType, Name};
 
-pub struct ForeignOrder;
+pub struct ForeignOrder {
+    weights: NameWeights,
+    /// Type of names to rank higher
+    preferred: Option<NameType>,
+}
+
+impl ForeignOrder {
+    #[inline]
+    pub fn new(weights: NameWeights, preferred: Option<NameType>) -> Self {
+        Self { weights, preferred }
+    }
+}
 
 impl RelevanceEngine for ForeignOrder {
     type OutItem = &'static Name;
@@ -14,6 +27,9 @@ impl RelevanceEngine for ForeignOrder {
         &self,
         item: &SortData<'item, 'query, Self::OutItem, Self::IndexItem, Self::Query>,
     ) -> f32 {
-        item.index_item().dice_weighted(item.query(), 0.1)
+        let score = item
+            .index_item()
+            .dice_weighted(item.query(), self.weights.dice_weight);
+        score * type_factor(item.item(), self.preferred, &self.weights)
     }
 }
@@ -1,2 +1,22 @@
 pub mod foreign;
 pub mod japanese;
+
+use crate::ranking::NameWeights;
+use engine::relevance::explain;
+use types::jotoba::names::{name_type::NameType, Name};
+
+/// Returns the factor to apply to the score of `name` if it has the `preferred` type
+#[inline]
+fn type_factor(name: &Name, preferred: Option<NameType>, weights: &NameWeights) -> f32 {
+    let preferred = match preferred {
+        Some(preferred) => preferred,
+        None => return 1.0,
+    };
+
+    if name.has_type(preferred) {
+        explain::factor("preferred type", weights.preferred_type);
+        return weights.preferred_type;
+    }
+
+    1.0
+}
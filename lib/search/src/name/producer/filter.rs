@@ -0,0 +1,36 @@
+use crate::query::{expr::ExprEval, Query, Tag};
+use jp_utils::JapaneseExt;
+use types::jotoba::names::Name;
+
+/// Returns `true` if `name` matches all name type tags and the expression of `query`
+pub(crate) fn filter_name(query: &Query, name: &Name) -> bool {
+    let eval = NameExprEval { name };
+
+    if !query.tags.iter().all(|i| eval.eval_tag(i).unwrap_or(true)) {
+        return false;
+    }
+
+    query.matches_expr(&eval)
+}
+
+/// Evaluates query expressions for a single name
+struct NameExprEval<'a> {
+    name: &'a Name,
+}
+
+impl<'a> ExprEval for NameExprEval<'a> {
+    fn eval_tag(&self, tag: &Tag) -> Option<bool> {
+        let name_type = tag.as_name_type()?;
+        Some(self.name.has_type(*name_type))
+    }
+
+    fn eval_term(&self, term: &str) -> bool {
+        let name = self.name;
+        if term.is_japanese() {
+            return name.kana.contains(term)
+                || name.kanji.as_ref().map_or(false, |i| i.contains(term));
+        }
+
+        name.transcription.to_lowercase().contains(term)
+    }
+}
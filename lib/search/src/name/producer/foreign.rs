@@ -2,7 +2,11 @@ use engine::{pushable::FilteredMaxCounter, task::SearchTask};
 
 use crate::{
     engine::names::foreign::Engine,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     name::{order::foreign::ForeignOrder, Search},
     query::{Query, QueryLang},
 };
@@ -16,10 +20,22 @@ impl<'a> ForeignProducer<'a> {
         Self { query }
     }
 
-    fn foreign_task(&self) -> SearchTask<'static, Engine> {
-        let query = format_word(&self.query.query_str);
+    /// Returns a task for each text alternative of the query
+    fn foreign_tasks(&self) -> Vec<SearchTask<'static, Engine>> {
+        self.query
+            .text_alternatives()
+            .iter()
+            .map(|q_str| self.foreign_task(q_str))
+            .collect()
+    }
+
+    fn foreign_task(&self, query_str: &str) -> SearchTask<'static, Engine> {
+        let query = format_word(query_str);
         SearchTask::<Engine>::new(&query)
-            .with_custom_order(ForeignOrder)
+            .with_custom_order(ForeignOrder::new(
+                self.query.ranking().names,
+                self.query.preferred_name_type(),
+            ))
             .with_threshold(0.5)
     }
 }
@@ -34,15 +50,23 @@ impl<'a> Producer for ForeignProducer<'a> {
             <Self::Target as Searchable>::ResAdd,
         >,
     ) {
-        self.foreign_task().find_to(out);
+        for mut task in self.foreign_tasks() {
+            task.find_to(out);
+        }
     }
 
     fn should_run(&self, _already_found: usize) -> bool {
         self.query.q_lang != QueryLang::Japanese
     }
 
+    fn dependency(&self) -> Dependency {
+        Dependency::None
+    }
+
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
-        self.foreign_task().estimate_to(out);
+        for mut task in self.foreign_tasks() {
+            task.estimate_to(out);
+        }
     }
 }
 
@@ -1,6 +1,10 @@
 use crate::{
     engine::names::native::Engine,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     name::Search,
     query::Query,
 };
@@ -50,6 +54,10 @@ impl<'a> Producer for KreadingProducer<'a> {
         self.query.form.is_kanji_reading()
     }
 
+    fn dependency(&self) -> Dependency {
+        Dependency::None
+    }
+
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
         if let Some(mut task) = self.search_task() {
             task.estimate_to(out);
@@ -1,4 +1,6 @@
+pub(crate) mod filter;
 pub mod foreign;
 pub mod kanji_reading;
 pub mod native;
 pub mod sequence;
+pub mod tag;
@@ -2,7 +2,11 @@ pub mod split;
 
 use crate::{
     engine::names::native::Engine,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     name::{order::japanese::NativeOrder, Search},
     query::{Query, QueryLang},
 };
@@ -18,10 +22,22 @@ impl<'a> NativeProducer<'a> {
         Self { query }
     }
 
+    /// Returns a task for each text alternative of the query
+    fn jp_tasks(&self) -> Vec<SearchTask<'static, Engine>> {
+        self.query
+            .text_alternatives()
+            .iter()
+            .map(|q_str| self.jp_task(q_str))
+            .collect()
+    }
+
     #[inline]
-    fn jp_task(&self) -> SearchTask<'static, Engine> {
-        SearchTask::<Engine>::new(&self.query.query_str)
-            .with_custom_order(NativeOrder)
+    fn jp_task(&self, query_str: &str) -> SearchTask<'static, Engine> {
+        SearchTask::<Engine>::new(query_str)
+            .with_custom_order(NativeOrder::new(
+                self.query.ranking().names,
+                self.query.preferred_name_type(),
+            ))
             .with_threshold(0.3)
     }
 }
@@ -36,14 +52,22 @@ impl<'a> Producer for NativeProducer<'a> {
             <Self::Target as Searchable>::ResAdd,
         >,
     ) {
-        self.jp_task().find_to(out);
+        for mut task in self.jp_tasks() {
+            task.find_to(out);
+        }
     }
 
     fn should_run(&self, _already_found: usize) -> bool {
         self.query.q_lang == QueryLang::Japanese
     }
 
+    fn dependency(&self) -> Dependency {
+        Dependency::None
+    }
+
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
-        self.jp_task().estimate_to(out);
+        for mut task in self.jp_tasks() {
+            task.estimate_to(out);
+        }
     }
 }
@@ -9,7 +9,11 @@ use types::jotoba::names::Name;
 
 use crate::{
     engine::names::native::Engine,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     name::{order::japanese::NativeOrder, Search},
     query::Query,
 };
@@ -24,12 +28,15 @@ impl<'a> SplitProducer<'a> {
     }
 
     fn queries(&self) -> Vec<String> {
-        let splitted = Parser::new(&self.query.query_str);
-        match splitted.parse() {
-            ParseResult::Sentence(s) => s.iter().map(|p| p.get_normalized()).collect(),
-            ParseResult::InflectedWord(w) => vec![w.get_normalized()],
-            ParseResult::None => vec![],
-        }
+        self.query
+            .text_alternatives()
+            .iter()
+            .flat_map(|q_str| match Parser::new(q_str).parse() {
+                ParseResult::Sentence(s) => s.iter().map(|p| p.get_normalized()).collect(),
+                ParseResult::InflectedWord(w) => vec![w.get_normalized()],
+                ParseResult::None => vec![],
+            })
+            .collect()
     }
 
     fn run<C, P, O>(&self, cb: C, out: &mut P)
@@ -39,10 +46,13 @@ impl<'a> SplitProducer<'a> {
     {
         let queries = self.queries();
         let query_count = queries.len();
+        let weights = self.query.ranking().names;
+        let preferred = self.query.preferred_name_type();
         for (pos, query) in queries.into_iter().enumerate() {
+            let order = SplitOrder::new(query_count, pos, NativeOrder::new(weights, preferred));
             let mut task = SearchTask::<Engine>::new(&query)
                 .with_limit(1)
-                .with_custom_order(SplitOrder::new(query_count, pos));
+                .with_custom_order(order);
 
             (cb)(&mut task, out);
         }
@@ -79,6 +89,10 @@ impl<'a> Producer for SplitProducer<'a> {
         //already_found == 0
     }
 
+    fn dependency(&self) -> Dependency {
+        Dependency::Count
+    }
+
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
         self.run(|engine, out| engine.estimate_to(out), out);
     }
@@ -87,12 +101,17 @@ impl<'a> Producer for SplitProducer<'a> {
 struct SplitOrder {
     q_count: usize,
     pos: usize,
+    order: NativeOrder,
 }
 
 impl SplitOrder {
     #[inline]
-    fn new(q_count: usize, pos: usize) -> Self {
-        Self { q_count, pos }
+    fn new(q_count: usize, pos: usize, order: NativeOrder) -> Self {
+        Self {
+            q_count,
+            pos,
+            order,
+        }
     }
 }
 
@@ -106,7 +125,7 @@ impl RelevanceEngine for SplitOrder {
         &self,
         item: &SortData<'item, 'query, Self::OutItem, Self::IndexItem, Self::Query>,
     ) -> f32 {
-        let sim = NativeOrder.score(item);
+        let sim = self.order.score(item);
         let rel = (self.q_count - self.pos) as f32;
         sim * rel * 0.001
     }
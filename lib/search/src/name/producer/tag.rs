@@ -0,0 +1,91 @@
+use crate::{
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
+    name::Search,
+    query::Query,
+};
+use engine::{
+    pushable::{FilteredMaxCounter, PushMod, Pushable},
+    relevance::item::RelItem,
+};
+use types::jotoba::names::{name_type::NameType, Name};
+
+/// Max amount of names to produce per tag
+const MAX_NAMES: usize = 1000;
+
+/// Produces names by their type for tag only queries
+pub struct TagProducer<'a> {
+    query: &'a Query,
+}
+
+impl<'a> TagProducer<'a> {
+    pub fn new(query: &'a Query) -> Self {
+        Self { query }
+    }
+
+    /// Returns the name type tags of each branch of the query having at least one
+    fn get_name_types(&self) -> Vec<Vec<NameType>> {
+        self.query
+            .branches()
+            .iter()
+            .map(|i| {
+                i.tags
+                    .iter()
+                    .filter_map(|i| i.as_name_type())
+                    .copied()
+                    .collect::<Vec<_>>()
+            })
+            .filter(|i| !i.is_empty())
+            .collect()
+    }
+
+    fn find_to<P>(&self, out: &mut P)
+    where
+        P: Pushable<Item = RelItem<&'static Name>>,
+    {
+        let preferred = self.query.preferred_name_type();
+        let names = resources::get().names();
+
+        for name_types in self.get_name_types() {
+            // Names need all types of the branch, so they're limited after intersecting the types
+            let found = names.by_types(&name_types).take(MAX_NAMES);
+            for (c, name) in found.enumerate() {
+                let mut rel = (MAX_NAMES - c) as f32;
+                if preferred.map_or(false, |i| name.has_type(i)) {
+                    rel += MAX_NAMES as f32;
+                }
+                out.push(RelItem::new(name, rel));
+            }
+        }
+    }
+}
+
+impl<'a> Producer for TagProducer<'a> {
+    type Target = Search<'a>;
+
+    fn produce(
+        &self,
+        out: &mut OutputBuilder<
+            <Self::Target as Searchable>::Item,
+            <Self::Target as Searchable>::ResAdd,
+        >,
+    ) {
+        self.find_to(out);
+    }
+
+    fn should_run(&self, _already_found: usize) -> bool {
+        self.query.form.is_tag_only() && !self.get_name_types().is_empty()
+    }
+
+    fn dependency(&self) -> Dependency {
+        Dependency::None
+    }
+
+    fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
+        let mut m = PushMod::new(out, |i: RelItem<&'static Name>| i.item);
+        self.find_to(&mut m);
+    }
+}
@@ -0,0 +1,183 @@
+//! Boolean query expressions
+//!
+//! # Example
+//!
+//! "#verb AND NOT #jlpt1" => And(Tag(Verb), Not(Tag(Jlpt(1))))
+//!
+//! "(猫 OR 犬) #n5" => And(Or(Text(猫), Text(犬)), Tag(Jlpt(5)))
+//!
+//! # Supported syntax
+//! `AND` - Both sides have to match. Also implied by juxtaposition
+//! `OR` / `|` - At least one side has to match
+//! `NOT` / `-` - Negates the following term. `-` only applies to tags, quoted terms and groups
+//! `( )` - Groups terms
+//!
+//! # Note
+//! Free text is used to retrieve results and can't be evaluated as a filter. Negated free text
+//! is treated like a negated quoted term instead.
+//!
+//! Words, sentences, names and kanji are searched for the text of every branch and the results
+//! get merged. The query language and form detection (eg. kanji reading searches) use the text of
+//! the first branch. Tags of all branches are used to produce results and the whole expression is
+//! always applied as filter.
+
+use super::Tag;
+
+/// Max amount of branches a query expression can be expanded to
+pub const MAX_BRANCHES: usize = 10;
+
+/// A parsed boolean query expression
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum QueryExpr {
+    /// Free text used to retrieve results
+    Text(String),
+    /// A quoted term the result has to contain
+    Term(String),
+    /// A hashtag
+    Tag(Tag),
+    Not(Box<QueryExpr>),
+    And(Vec<QueryExpr>),
+    Or(Vec<QueryExpr>),
+}
+
+/// A set of (non negated) text and tags of which all have to match. Queries with `OR` expand to
+/// multiple branches
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Branch {
+    pub text: Vec<String>,
+    pub tags: Vec<Tag>,
+}
+
+/// Evaluates the leaves of a [`QueryExpr`] for a given item
+pub trait ExprEval {
+    /// Should return `None` if the tag can't be applied to the item
+    fn eval_tag(&self, tag: &Tag) -> Option<bool>;
+
+    /// Should return `true` if the item contains the (lowercase) term
+    fn eval_term(&self, term: &str) -> bool;
+}
+
+impl QueryExpr {
+    /// Returns `true` if `item` matches the expression
+    #[inline]
+    pub fn matches<E: ExprEval>(&self, item: &E) -> bool {
+        self.eval(item).unwrap_or(true)
+    }
+
+    /// Evaluates the expression for `item`. Returns `None` if the expression contains nothing
+    /// that applies to `item`
+    pub fn eval<E: ExprEval>(&self, item: &E) -> Option<bool> {
+        match self {
+            QueryExpr::Text(_) => None,
+            QueryExpr::Term(term) => Some(item.eval_term(term)),
+            QueryExpr::Tag(tag) => item.eval_tag(tag),
+            QueryExpr::Not(inner) => inner.eval(item).map(|i| !i),
+            QueryExpr::And(exprs) => {
+                let mut res = None;
+                for i in exprs.iter().filter_map(|i| i.eval(item)) {
+                    if !i {
+                        return Some(false);
+                    }
+                    res = Some(true);
+                }
+                res
+            }
+            QueryExpr::Or(exprs) => {
+                let mut res = None;
+                for i in exprs.iter().map(|i| i.eval(item)) {
+                    match i {
+                        Some(true) => return Some(true),
+                        Some(false) => res = Some(false),
+                        // Text only branches always match since they're used for retrieving
+                        None => return None,
+                    }
+                }
+                res
+            }
+        }
+    }
+
+    /// Returns `true` if the expression is a [`QueryExpr::Text`]
+    #[inline]
+    pub fn is_text(&self) -> bool {
+        matches!(self, Self::Text(..))
+    }
+
+    /// Returns all tags that have to be matched by every result
+    pub fn required_tags(&self) -> Vec<Tag> {
+        match self {
+            QueryExpr::Tag(tag) => vec![*tag],
+            QueryExpr::And(exprs) => exprs.iter().flat_map(|i| i.required_tags()).collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns all quoted terms that have to be contained in every result
+    pub fn required_terms(&self) -> Vec<String> {
+        match self {
+            QueryExpr::Term(term) => vec![term.clone()],
+            QueryExpr::And(exprs) => exprs.iter().flat_map(|i| i.required_terms()).collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns all tags within the expression which are not negated
+    pub fn positive_tags(&self) -> Vec<Tag> {
+        match self {
+            QueryExpr::Tag(tag) => vec![*tag],
+            QueryExpr::And(exprs) | QueryExpr::Or(exprs) => {
+                exprs.iter().flat_map(|i| i.positive_tags()).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Expands the expression into its branches. Negated parts are ignored since they can't
+    /// produce any results. The amount of branches is limited by [`MAX_BRANCHES`]
+    pub fn branches(&self) -> Vec<Branch> {
+        match self {
+            QueryExpr::Text(text) => vec![Branch {
+                text: vec![text.clone()],
+                tags: vec![],
+            }],
+            QueryExpr::Tag(tag) => vec![Branch {
+                text: vec![],
+                tags: vec![*tag],
+            }],
+            QueryExpr::Term(_) | QueryExpr::Not(_) => vec![Branch::default()],
+            QueryExpr::Or(exprs) => exprs
+                .iter()
+                .flat_map(|i| i.branches())
+                .take(MAX_BRANCHES)
+                .collect(),
+            QueryExpr::And(exprs) => {
+                let mut out = vec![Branch::default()];
+                for expr in exprs {
+                    let branches = expr.branches();
+                    out = out
+                        .iter()
+                        .flat_map(|a| branches.iter().map(move |b| a.join(b)))
+                        .take(MAX_BRANCHES)
+                        .collect();
+                }
+                out
+            }
+        }
+    }
+}
+
+impl Branch {
+    /// Returns the text of the branch which will be used as query string
+    #[inline]
+    pub fn query_str(&self) -> String {
+        self.text.join(" ")
+    }
+
+    fn join(&self, other: &Branch) -> Branch {
+        let mut text = self.text.clone();
+        text.extend(other.text.iter().cloned());
+        let mut tags = self.tags.clone();
+        tags.extend(other.tags.iter().copied());
+        Branch { text, tags }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod expr;
 pub mod form;
 pub mod parser;
 pub mod prefix;
@@ -9,11 +10,17 @@ pub use form::Form;
 pub use tags::Tag;
 pub use user_settings::UserSettings;
 
-use self::regex::RegexSQuery;
+use self::{
+    expr::{Branch, QueryExpr},
+    regex::{RegexSQuery, RegexTarget},
+};
+use crate::ranking::{self, RankingProfile, DEFAULT_PROFILE};
+use itertools::Itertools;
 use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use std::hash::Hash;
 use types::jotoba::{
     language::{LangParam, Language},
+    names::name_type::NameType,
     search::SearchTarget,
     words::{misc::Misc, part_of_speech::PosSimple},
 };
@@ -25,7 +32,8 @@ const QUERY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC.add(b'/');
 pub struct Query {
     /// The raw query string from the user without any modifications
     pub raw_query: String,
-    /// Parsed query string which will be used to find results
+    /// Parsed query string which will be used to find results. For queries with an expression
+    /// this is the text of the first branch. See [`Query::text_alternatives`] for all branches
     pub query_str: String,
     /// Where to search {Words,Names,Kanji,Sentences}
     pub target: SearchTarget,
@@ -49,6 +57,12 @@ pub struct Query {
     pub cust_lang: Option<Language>,
     /// Regex query (for jp)
     pub regex: Option<RegexSQuery>,
+    /// Readings Japanese queries get matched against. Set by a `kana:` or `kanji:` prefix
+    pub reading_target: RegexTarget,
+    /// Boolean expression. Only set if the query contains operators, groups or negations
+    pub expr: Option<QueryExpr>,
+    /// Name of the ranking profile to use. Uses the default profile if not set
+    pub ranking_profile: Option<String>,
 }
 
 /// The language of the query content itself
@@ -62,6 +76,12 @@ pub enum QueryLang {
 }
 
 impl Query {
+    /// Returns the weights of the selected ranking profile
+    pub fn ranking(&self) -> RankingProfile {
+        let name = self.ranking_profile.as_deref().unwrap_or(DEFAULT_PROFILE);
+        ranking::get(name).unwrap_or_default()
+    }
+
     /// Returns true if the query has at least one pos tag
     #[inline]
     pub fn has_part_of_speech_tags(&self) -> bool {
@@ -98,6 +118,21 @@ impl Query {
         self.tags.iter().any(|i| *i == tag)
     }
 
+    /// Returns the name type to rank higher in name searches
+    #[inline]
+    pub fn preferred_name_type(&self) -> Option<NameType> {
+        self.tags.iter().find_map(|i| match i {
+            Tag::PreferNameType(name_type) => Some(*name_type),
+            _ => None,
+        })
+    }
+
+    /// Returns `true` if sentences should be ordered from easiest to hardest
+    #[inline]
+    pub fn easy_first(&self) -> bool {
+        self.has_tag(Tag::EasyFirst)
+    }
+
     /// Adds `n` pages to the query
     pub fn add_page(&mut self, n: usize) {
         self.page = (self.page + n).min(100);
@@ -156,4 +191,56 @@ impl Query {
     pub fn as_regex_query(&self) -> Option<&RegexSQuery> {
         self.regex.as_ref()
     }
+
+    /// Returns all branches of the query. Queries without an `OR` expression always have
+    /// exactly one branch consisting of the query string and its tags
+    pub fn branches(&self) -> Vec<Branch> {
+        if let Some(ref expr) = self.expr {
+            return expr.branches();
+        }
+
+        let text = (!self.query_str.is_empty())
+            .then(|| vec![self.query_str.clone()])
+            .unwrap_or_default();
+        vec![Branch {
+            text,
+            tags: self.tags.clone(),
+        }]
+    }
+
+    /// Returns a regex query for the text of every branch which is a valid regex
+    pub fn regex_queries(&self) -> Vec<RegexSQuery> {
+        if self.expr.is_none() {
+            return self.regex.iter().cloned().collect();
+        }
+
+        self.text_alternatives()
+            .into_iter()
+            .filter_map(|i| {
+                let (target, text) = parser::parse_reading_target(i);
+                RegexSQuery::new(&text).map(|i| i.with_target(target))
+            })
+            .collect()
+    }
+
+    /// Returns the query strings of all branches which contain text
+    pub fn text_alternatives(&self) -> Vec<String> {
+        if self.expr.is_none() {
+            return vec![self.query_str.clone()];
+        }
+
+        self.branches()
+            .into_iter()
+            .map(|i| i.query_str())
+            .filter(|i| !i.is_empty())
+            .unique()
+            .collect()
+    }
+
+    /// Returns `true` if `item` matches the queries expression. Always `true` if the query has
+    /// no expression
+    #[inline]
+    pub fn matches_expr<E: ExprEval>(&self, item: &E) -> bool {
+        self.expr.as_ref().map(|i| i.matches(item)).unwrap_or(true)
+    }
 }
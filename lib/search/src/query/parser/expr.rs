@@ -0,0 +1,267 @@
+use super::tags;
+use crate::query::expr::QueryExpr;
+
+/// A single token of a query expression
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Tag(String),
+    Quoted(String),
+    Word(String),
+}
+
+impl Token {
+    /// Returns `true` if the token is an operator or grouping token
+    #[inline]
+    fn is_operator(&self) -> bool {
+        matches!(
+            self,
+            Token::LParen | Token::RParen | Token::And | Token::Or | Token::Not
+        )
+    }
+}
+
+/// Parses a boolean query expression. Returns `None` if the query doesn't contain any boolean
+/// operators, groups or negations or if the expression is invalid. Such queries should be parsed
+/// the regular way instead
+pub fn parse(query: &str) -> Option<QueryExpr> {
+    let tokens = tokenize(query);
+    if !tokens.iter().any(|i| i.is_operator()) {
+        return None;
+    }
+
+    let mut parser = ExprParser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+
+    // Unbalanced parentheses or trailing operators
+    if parser.pos != parser.tokens.len() {
+        return None;
+    }
+
+    expr
+}
+
+/// Recursive descent parser over expression tokens. Each parse function returns
+/// `Some(None)` for valid input that doesn't produce an expression (eg. unknown tags)
+struct ExprParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl ExprParser {
+    #[inline]
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn parse_or(&mut self) -> Option<Option<QueryExpr>> {
+        let mut exprs = vec![];
+        exprs.extend(self.parse_and()?);
+
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            exprs.extend(self.parse_and()?);
+        }
+
+        Some(combine(exprs, QueryExpr::Or))
+    }
+
+    fn parse_and(&mut self) -> Option<Option<QueryExpr>> {
+        let mut exprs = vec![];
+        exprs.extend(self.parse_unary()?);
+
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.next();
+                }
+                Some(Token::Or) | Some(Token::RParen) | None => break,
+                // Juxtaposition implies AND
+                Some(_) => (),
+            }
+            exprs.extend(self.parse_unary()?);
+        }
+
+        Some(combine(exprs, QueryExpr::And))
+    }
+
+    fn parse_unary(&mut self) -> Option<Option<QueryExpr>> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            let inner = self.parse_unary()?;
+            return Some(inner.map(negate));
+        }
+
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Option<Option<QueryExpr>> {
+        match self.next()? {
+            Token::LParen => {
+                let inner = self.parse_or()?;
+                (self.next()? == Token::RParen).then(|| inner)
+            }
+            Token::Tag(tag) => {
+                let tags = tags::parse(&tag.to_lowercase());
+                Some(combine(
+                    tags.into_iter().map(QueryExpr::Tag).collect(),
+                    QueryExpr::And,
+                ))
+            }
+            Token::Quoted(term) => {
+                let term = term.trim().to_lowercase();
+                Some((!term.is_empty()).then(|| QueryExpr::Term(term)))
+            }
+            Token::Word(word) => {
+                // Consecutive words form a single text
+                let mut text = word;
+                while let Some(Token::Word(w)) = self.peek() {
+                    text.push(' ');
+                    text.push_str(w);
+                    self.pos += 1;
+                }
+                Some(Some(QueryExpr::Text(text)))
+            }
+            Token::RParen | Token::And | Token::Or | Token::Not => None,
+        }
+    }
+}
+
+/// Negates an expression. Negated text can't be used for retrieving so its handled like a
+/// quoted term
+fn negate(expr: QueryExpr) -> QueryExpr {
+    let expr = match expr {
+        QueryExpr::Text(text) => QueryExpr::Term(text.to_lowercase()),
+        QueryExpr::Not(inner) => return *inner,
+        e => e,
+    };
+    QueryExpr::Not(Box::new(expr))
+}
+
+/// Combines multiple expressions using `f`. Returns the expression itself if there is only one
+fn combine<F>(mut exprs: Vec<QueryExpr>, f: F) -> Option<QueryExpr>
+where
+    F: Fn(Vec<QueryExpr>) -> QueryExpr,
+{
+    match exprs.len() {
+        0 => None,
+        1 => exprs.pop(),
+        _ => Some(f(exprs)),
+    }
+}
+
+/// Splits a query into expression tokens
+fn tokenize(query: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut chars = query.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '(' | '（' => tokens.push(Token::LParen),
+            ')' | '）' => tokens.push(Token::RParen),
+            '|' => tokens.push(Token::Or),
+            '-' if matches!(chars.peek(), Some('#' | '"' | '(' | '（')) => tokens.push(Token::Not),
+            '"' => {
+                let term: String = chars.by_ref().take_while(|i| *i != '"').collect();
+                tokens.push(Token::Quoted(term));
+            }
+            c => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '(' | '（' | ')' | '）' | '"') {
+                        break;
+                    }
+                    word.push(*c);
+                    chars.next();
+                }
+
+                tokens.push(match word.as_str() {
+                    "AND" | "&&" => Token::And,
+                    "OR" | "||" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ if word.starts_with('#') => Token::Tag(word),
+                    _ => Token::Word(word),
+                });
+            }
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query::Tag;
+    use types::jotoba::words::part_of_speech::PosSimple;
+
+    #[test]
+    fn test_no_operators() {
+        assert_eq!(parse("to eat #verb"), None);
+        assert_eq!(parse(r#"dog "big""#), None);
+    }
+
+    #[test]
+    fn test_negated_tag() {
+        assert_eq!(
+            parse("#verb AND NOT #jlpt1"),
+            Some(QueryExpr::And(vec![
+                QueryExpr::Tag(Tag::PartOfSpeech(PosSimple::Verb)),
+                QueryExpr::Not(Box::new(QueryExpr::Tag(Tag::Jlpt(1)))),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_group() {
+        assert_eq!(
+            parse("(猫 OR 犬) #n5"),
+            Some(QueryExpr::And(vec![
+                QueryExpr::Or(vec![
+                    QueryExpr::Text("猫".to_string()),
+                    QueryExpr::Text("犬".to_string())
+                ]),
+                QueryExpr::Tag(Tag::Jlpt(5)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_exclusion_term() {
+        assert_eq!(
+            parse(r#"dog -"slang""#),
+            Some(QueryExpr::And(vec![
+                QueryExpr::Text("dog".to_string()),
+                QueryExpr::Not(Box::new(QueryExpr::Term("slang".to_string()))),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_unbalanced() {
+        assert_eq!(parse("(dog OR cat"), None);
+        assert_eq!(parse("dog OR"), None);
+    }
+
+    #[test]
+    fn test_branches() {
+        let expr = parse("(猫 OR 犬) #n5").unwrap();
+        let branches = expr.branches();
+        assert_eq!(branches.len(), 2);
+        assert_eq!(branches[0].query_str(), "猫");
+        assert_eq!(branches[1].query_str(), "犬");
+        assert!(branches.iter().all(|i| i.tags == vec![Tag::Jlpt(5)]));
+    }
+}
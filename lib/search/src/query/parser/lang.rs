@@ -6,7 +6,18 @@ use super::JAPANESE_THRESHOLD;
 
 /// Tries to determine between Japanese/Non japnaese
 pub fn parse(query: &str) -> QueryLang {
-    let query = strip_regex(query).unwrap_or_else(|| query.to_string());
+    let regex = RegexSQuery::new(query);
+
+    // Queries like `{kanji}{2}` only contain placeholders for japanese characters
+    if let Some(regex) = regex.as_ref() {
+        if regex.has_script() && regex.get_chars().is_empty() {
+            return QueryLang::Japanese;
+        }
+    }
+
+    let query = regex
+        .map(|i| strip_regex(&i))
+        .unwrap_or_else(|| query.to_string());
     if utils::korean::is_hangul_str(&query) {
         return QueryLang::Korean;
     }
@@ -32,7 +43,7 @@ fn get_jp_part(inp: &str) -> usize {
     ((japanese as f32 / total as f32) * 100f32) as usize
 }
 
-/// Removes regex parts from a query. Returns `None` if `query` does not contain regex symbols
-fn strip_regex(query: &str) -> Option<String> {
-    Some(RegexSQuery::new(query)?.get_chars().into_iter().collect())
+/// Removes regex parts from a query
+fn strip_regex(query: &RegexSQuery) -> String {
+    query.get_chars().into_iter().collect()
 }
@@ -1,9 +1,15 @@
+pub mod expr;
 pub mod lang;
 pub(crate) mod prefix;
 pub mod req_terms;
 pub(crate) mod tags;
 
-use super::{prefix::SearchPrefix, regex::RegexSQuery, Form, Query, Tag, UserSettings};
+use super::{
+    expr::QueryExpr,
+    prefix::SearchPrefix,
+    regex::{self, RegexSQuery, RegexTarget},
+    Form, Query, QueryLang, Tag, UserSettings,
+};
 use jp_utils::JapaneseExt;
 use types::jotoba::{kanji, language::Language as ContentLanguage, search::SearchTarget};
 
@@ -30,6 +36,10 @@ pub struct QueryParser {
     word_index: usize,
     /// Overwrite the users settings language
     language_override: Option<ContentLanguage>,
+    /// Tags passed next to the query
+    extra_tags: Vec<Tag>,
+    /// Name of the selected ranking profile
+    ranking_profile: Option<String>,
 }
 
 impl QueryParser {
@@ -47,6 +57,8 @@ impl QueryParser {
             page: 0,
             word_index: 0,
             language_override: None,
+            extra_tags: vec![],
+            ranking_profile: None,
         }
     }
 
@@ -69,6 +81,20 @@ impl QueryParser {
         self
     }
 
+    /// Adds tags to the query which are handled like tags written within the query
+    #[inline]
+    pub fn with_tags(mut self, tags: Vec<Tag>) -> Self {
+        self.extra_tags.extend(tags);
+        self
+    }
+
+    /// Selects the ranking profile used for the search
+    #[inline]
+    pub fn with_ranking_profile(mut self, profile: String) -> Self {
+        self.ranking_profile = Some(profile);
+        self
+    }
+
     /// Parses a user query into Query
     pub fn parse(mut self) -> Option<Query> {
         let (stripped, s_prefix) = prefix::parse_prefix(&self.raw_query);
@@ -76,25 +102,45 @@ impl QueryParser {
             self.language_override = Some(lang_overwrite);
         }
 
-        let (new_query, tags) = Self::extract_tags(&stripped);
-        let (new_query, must_contain) = req_terms::parse(&new_query);
+        let expr = expr::parse(stripped);
+
+        let (new_query, mut tags, must_contain, mut producer_tags) = match expr {
+            Some(ref expr) => Self::from_expr(expr),
+            None => {
+                let (new_query, tags) = Self::extract_tags(&stripped);
+                let (new_query, must_contain) = req_terms::parse(&new_query);
+                let producer_tags = tags.clone();
+                (new_query, tags, must_contain, producer_tags)
+            }
+        };
+
+        for tag in self.extra_tags.drain(..) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+            if !producer_tags.contains(&tag) {
+                producer_tags.push(tag);
+            }
+        }
+
         let query_str: String = new_query
             .trim()
             .chars()
             .into_iter()
             .take(MAX_QUERY_LEN)
             .collect();
+        let (reading_target, query_str) = parse_reading_target(query_str);
 
         // Don't allow empty queries
-        if query_str.is_empty() && !tags.iter().any(|i| i.is_producer()) {
+        if query_str.is_empty() && !producer_tags.iter().any(|i| i.is_producer()) {
             return None;
         }
 
         let q_lang = lang::parse(&query_str);
-        let target = self.get_search_target(&tags);
-        let form = self.parse_form(&query_str, &tags, s_prefix);
+        let target = self.get_search_target(&producer_tags);
+        let form = self.parse_form(&query_str, &producer_tags, s_prefix);
 
-        let regex = RegexSQuery::new(&query_str);
+        let regex = RegexSQuery::new(&query_str).map(|i| i.with_target(reading_target));
 
         Some(Query {
             q_lang,
@@ -110,9 +156,35 @@ impl QueryParser {
             cust_lang: self.language_override,
             must_contain,
             regex,
+            reading_target,
+            expr,
+            ranking_profile: self.ranking_profile,
         })
     }
 
+    /// Returns the query string of the first branch, all tags and terms which are required by
+    /// every branch and all tags which are not negated. Producers search the text of the other
+    /// branches through [`Query::text_alternatives`]
+    fn from_expr(expr: &QueryExpr) -> (String, Vec<Tag>, Vec<String>, Vec<Tag>) {
+        let query_str = expr
+            .branches()
+            .first()
+            .map(|i| i.query_str())
+            .unwrap_or_default();
+
+        let producer_tags = expr.positive_tags();
+
+        let mut tags = expr.required_tags();
+        // Non producer tags apply to the whole query, regardless of their position
+        for tag in producer_tags.iter().filter(|i| !i.is_producer()) {
+            if !tags.contains(tag) {
+                tags.push(*tag);
+            }
+        }
+
+        (query_str, tags, expr.required_terms(), producer_tags)
+    }
+
     // Extracts all tags from `query_str` and returns a new String along with the extracted tags
     #[inline]
     fn extract_tags(query_str: &str) -> (String, Vec<Tag>) {
@@ -194,6 +266,29 @@ impl QueryParser {
     }
 }
 
+/// Strips a `kana:` or `kanji:` prefix of Japanese queries and returns the readings the query
+/// should be matched against
+pub(crate) fn parse_reading_target(query: String) -> (RegexTarget, String) {
+    let normalized = query.replacen('：', ":", 1);
+    let (target, stripped) = regex::parse_target(&normalized);
+
+    if target == RegexTarget::All || lang::parse(stripped) != QueryLang::Japanese {
+        return (RegexTarget::All, query);
+    }
+
+    (target, stripped.to_string())
+}
+
+/// Parses a single tag. The leading `#` is optional
+pub fn parse_tag(tag: &str) -> Vec<Tag> {
+    let tag = tag.trim().to_lowercase();
+    if tag.starts_with('#') {
+        tags::parse(&tag)
+    } else {
+        tags::parse(&format!("#{tag}"))
+    }
+}
+
 #[inline]
 pub fn format_kanji_reading(s: &str) -> String {
     s.replace('.', "").replace('-', "").replace(' ', "")
@@ -202,3 +297,64 @@ pub fn format_kanji_reading(s: &str) -> String {
 pub fn calc_page_offset(page: usize, page_size: usize) -> usize {
     page.saturating_sub(1) * page_size
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_expr_branches() {
+        let expr = expr::parse("(猫 #n5) OR (犬 #n4)").unwrap();
+        let (query_str, tags, _, producer_tags) = QueryParser::from_expr(&expr);
+
+        // Only the text of the first branch is used as query string
+        assert_eq!(query_str, "猫");
+        // Tags of single branches are not required by every result
+        assert!(tags.is_empty());
+        assert_eq!(producer_tags, vec![Tag::Jlpt(5), Tag::Jlpt(4)]);
+    }
+
+    #[test]
+    fn test_parse_reading_target() {
+        let parse = |q: &str| parse_reading_target(q.to_string());
+
+        assert_eq!(
+            parse("kana:にほん"),
+            (RegexTarget::Kana, "にほん".to_string())
+        );
+        assert_eq!(
+            parse("kanji: 日本"),
+            (RegexTarget::Kanji, "日本".to_string())
+        );
+        assert_eq!(
+            parse("kana：に?ん"),
+            (RegexTarget::Kana, "に?ん".to_string())
+        );
+        assert_eq!(parse("にほん"), (RegexTarget::All, "にほん".to_string()));
+
+        // Only Japanese queries can be restricted to readings
+        assert_eq!(
+            parse("kana:house"),
+            (RegexTarget::All, "kana:house".to_string())
+        );
+        assert_eq!(parse("kana:"), (RegexTarget::All, "kana:".to_string()));
+    }
+
+    #[test]
+    fn test_reading_target_query() {
+        let parse = |q: &str| {
+            QueryParser::new(q.to_string(), SearchTarget::Words, UserSettings::default())
+                .parse()
+                .unwrap()
+        };
+
+        let query = parse("kana:にほん");
+        assert_eq!(query.query_str, "にほん");
+        assert_eq!(query.reading_target, RegexTarget::Kana);
+        assert!(!query.is_regex());
+
+        let query = parse("kanji:*本");
+        assert_eq!(query.query_str, "*本");
+        assert_eq!(query.as_regex_query().unwrap().target(), RegexTarget::Kanji);
+    }
+}
@@ -1,16 +1,21 @@
-use crate::query::Tag;
+use crate::query::{
+    tags::{FREQUENCY_BUCKETS, MAX_GRADE},
+    Tag,
+};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::str::FromStr;
 use types::jotoba::{
+    names::name_type::NameType,
     search::SearchTarget,
     sentences,
-    words::{misc::Misc, part_of_speech::PosSimple},
+    words::{misc::Misc, part_of_speech::PosSimple, pitch::PitchPattern},
 };
 use utils::trim_string_end;
 
-/// Regex for finding tags within a query.
-static TAG_REGEX: Lazy<Regex> = Lazy::new(|| regex::Regex::new("#[a-zA-Z0-9\\-]+").unwrap());
+/// Regex for finding tags within a query. Tags may have a value separated by `:`
+static TAG_REGEX: Lazy<Regex> =
+    Lazy::new(|| regex::Regex::new("#[a-zA-Z0-9\\-]+(:[^\\s#]+)?").unwrap());
 
 /// Extracts all tags from the query and returns a new one without tags along with those tags which were extracted
 pub fn extract_parse<'a, F>(inp: &'a str, parse: F) -> (String, Vec<Tag>)
@@ -65,6 +70,7 @@ pub fn parse(s: &str) -> Vec<Tag> {
                 tags.push(Tag::IrregularIruEru);
             }
             "katakana" => tags.push(Tag::Katakana),
+            "easy-first" | "easiest" => tags.push(Tag::EasyFirst),
             _ => (),
         }
     }
@@ -75,6 +81,18 @@ pub fn parse(s: &str) -> Vec<Tag> {
     if let Some(tag) = parse_jlpt_tag(s) {
         tags.push(tag);
     }
+    if let Some(tag) = parse_kanji_tag(s) {
+        tags.push(tag);
+    }
+    if let Some(tag) = parse_pitch_tag(s) {
+        tags.push(tag);
+    }
+    if let Some(tag) = parse_level_tag(s) {
+        tags.push(tag);
+    }
+    if let Some(tag) = parse_name_tag(s) {
+        tags.push(tag);
+    }
     if let Some(tag) = parse_search_type(s) {
         tags.push(tag);
     }
@@ -109,6 +127,95 @@ fn parse_genki_tag(s: &str) -> Option<Tag> {
     Some(Tag::GenkiLesson(genki))
 }
 
+/// Parses stroke count (`#strokes:5-7`), grade (`#grade3`), frequency bucket (`#freq2`) and
+/// radical (`#radical:氵`) tags
+fn parse_kanji_tag(s: &str) -> Option<Tag> {
+    if let Some(strokes) = s
+        .strip_prefix("#strokes:")
+        .or_else(|| s.strip_prefix("#stroke:"))
+    {
+        let (min, max) = strokes.split_once('-').unwrap_or((strokes, strokes));
+        let min = min.parse::<u8>().ok()?;
+        let max = max.parse::<u8>().ok()?;
+        return Some(Tag::StrokeCount(min.min(max), min.max(max)));
+    }
+
+    if let Some(grade) = s.strip_prefix("#grade") {
+        let grade = grade.parse::<u8>().ok()?;
+        return (1..=MAX_GRADE).contains(&grade).then(|| Tag::Grade(grade));
+    }
+
+    if let Some(bucket) = s.strip_prefix("#freq") {
+        let bucket = bucket.parse::<u8>().ok()?;
+        return (1..=FREQUENCY_BUCKETS)
+            .contains(&bucket)
+            .then(|| Tag::Frequency(bucket));
+    }
+
+    let radical = s.strip_prefix("#radical:")?;
+    let mut chars = radical.chars();
+    let literal = chars.next()?;
+    chars.next().is_none().then(|| Tag::Radical(literal))
+}
+
+/// Parses pitch accent patterns (`#heiban`) and pitch drops (`#drop2`)
+fn parse_pitch_tag(s: &str) -> Option<Tag> {
+    let s = s.to_lowercase();
+
+    if let Some(drop) = s.strip_prefix("#drop") {
+        return Some(Tag::PitchDrop(drop.parse::<u8>().ok()?.min(6)));
+    }
+
+    Some(Tag::PitchPattern(match s.as_str() {
+        "#heiban" => PitchPattern::Heiban,
+        "#atamadaka" => PitchPattern::Atamadaka,
+        "#nakadaka" => PitchPattern::Nakadaka,
+        "#odaka" => PitchPattern::Odaka,
+        _ => return None,
+    }))
+}
+
+/// Parses sentence level tags like `#level:4` or `#level:3-5`
+fn parse_level_tag(s: &str) -> Option<Tag> {
+    let level = s.strip_prefix("#level:")?;
+
+    if let Ok(level) = level.parse::<i8>() {
+        return Some(Tag::SentenceLevel(level, level));
+    }
+
+    // Skip the first char to allow negative lower bounds
+    let split = level.get(1..)?.find('-')? + 1;
+    let min = level[..split].parse::<i8>().ok()?;
+    let max = level[split + 1..].parse::<i8>().ok()?;
+    Some(Tag::SentenceLevel(min.min(max), min.max(max)))
+}
+
+/// Parses name type filters like `#surname` and the preferred name type (`#prefer:place`)
+fn parse_name_tag(s: &str) -> Option<Tag> {
+    match s.strip_prefix("#prefer:") {
+        Some(name_type) => parse_name_type(name_type).map(Tag::PreferNameType),
+        None => parse_name_type(&s[1..]).map(Tag::NameType),
+    }
+}
+
+/// Parses a name type. Only the listed names are valid tags since the short codes of the other
+/// name types would clash with common tags like #obj or #ev
+fn parse_name_type(s: &str) -> Option<NameType> {
+    Some(match s {
+        "female" => NameType::Female,
+        "male" => NameType::Male,
+        "given" | "firstname" | "given-name" => NameType::Given,
+        "surname" | "lastname" | "family-name" => NameType::Surname,
+        "place" => NameType::Place,
+        "station" => NameType::RailwayStation,
+        "company" => NameType::Company,
+        "organization" | "org" => NameType::Organization,
+        "person" => NameType::Person,
+        "product" => NameType::Product,
+        _ => return None,
+    })
+}
+
 /// Parse only search type
 fn parse_search_type(s: &str) -> Option<Tag> {
     Some(match s[1..].to_lowercase().as_str() {
@@ -136,4 +243,90 @@ mod test {
         assert_eq!(parse_genki_tag("#genki3"), Some(Tag::GenkiLesson(3)));
         assert_eq!(parse_genki_tag("#genki23"), Some(Tag::GenkiLesson(23)));
     }
+
+    #[test]
+    fn test_parse_kanji_tag() {
+        assert_eq!(
+            parse_kanji_tag("#strokes:5-7"),
+            Some(Tag::StrokeCount(5, 7))
+        );
+        assert_eq!(
+            parse_kanji_tag("#strokes:7-5"),
+            Some(Tag::StrokeCount(5, 7))
+        );
+        assert_eq!(parse_kanji_tag("#strokes:4"), Some(Tag::StrokeCount(4, 4)));
+        assert_eq!(parse_kanji_tag("#grade3"), Some(Tag::Grade(3)));
+        assert_eq!(parse_kanji_tag("#grade0"), None);
+        assert_eq!(parse_kanji_tag("#grade11"), None);
+        assert_eq!(parse_kanji_tag("#freq0"), None);
+        assert_eq!(parse_kanji_tag("#freq6"), None);
+        assert_eq!(parse_kanji_tag("#freq2"), Some(Tag::Frequency(2)));
+        assert_eq!(parse_kanji_tag("#radical:氵"), Some(Tag::Radical('氵')));
+        assert_eq!(parse_kanji_tag("#radical:氵水"), None);
+        assert_eq!(parse_kanji_tag("#strokes:a"), None);
+    }
+
+    #[test]
+    fn test_parse_pitch_tag() {
+        assert_eq!(
+            parse_pitch_tag("#heiban"),
+            Some(Tag::PitchPattern(PitchPattern::Heiban))
+        );
+        assert_eq!(
+            parse_pitch_tag("#Odaka"),
+            Some(Tag::PitchPattern(PitchPattern::Odaka))
+        );
+        assert_eq!(parse_pitch_tag("#drop2"), Some(Tag::PitchDrop(2)));
+        assert_eq!(parse_pitch_tag("#drop"), None);
+        assert_eq!(parse_pitch_tag("#heibann"), None);
+    }
+
+    #[test]
+    fn test_parse_level_tag() {
+        assert_eq!(parse_level_tag("#level:4"), Some(Tag::SentenceLevel(4, 4)));
+        assert_eq!(
+            parse_level_tag("#level:3-5"),
+            Some(Tag::SentenceLevel(3, 5))
+        );
+        assert_eq!(
+            parse_level_tag("#level:5-3"),
+            Some(Tag::SentenceLevel(3, 5))
+        );
+        assert_eq!(
+            parse_level_tag("#level:-2-1"),
+            Some(Tag::SentenceLevel(-2, 1))
+        );
+        assert_eq!(parse_level_tag("#level:a"), None);
+        assert_eq!(parse_level_tag("#level4"), None);
+    }
+
+    #[test]
+    fn test_parse_name_tag() {
+        assert_eq!(
+            parse_name_tag("#surname"),
+            Some(Tag::NameType(NameType::Surname))
+        );
+        assert_eq!(
+            parse_name_tag("#female"),
+            Some(Tag::NameType(NameType::Female))
+        );
+        assert_eq!(
+            parse_name_tag("#prefer:place"),
+            Some(Tag::PreferNameType(NameType::Place))
+        );
+        assert_eq!(parse_name_tag("#prefer:unknown"), None);
+
+        for tag in [
+            "#ev", "#obj", "#oth", "#doc", "#char", "#group", "#work", "#ship",
+        ] {
+            assert_eq!(parse_name_tag(tag), None);
+        }
+    }
+
+    #[test]
+    fn test_extract_kanji_tags() {
+        let (query, tags) = extract_parse("#radical:氵 water #strokes:5-7", |s| (parse(s), true));
+        assert_eq!(query, "water");
+        assert_eq!(tags, vec![Tag::Radical('氵'), Tag::StrokeCount(5, 7)]);
+    }
 }
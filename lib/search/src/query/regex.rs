@@ -7,77 +7,215 @@
 //! # Supported syntax
 //! `*` - Allows 0-n other characters
 //! `?` - Allows 1 other characters
+//! `[日月]` - Allows 1 of the listed characters. `[^日月]` allows any other character
+//! `{kanji}`, `{kana}`, `{hiragana}`, `{katakana}` - Allows 1 character of the given script
+//! `{n}`, `{n,}`, `{n,m}` - Repeats the previous character, set or placeholder
+//! `kana:` / `kanji:` - Prefix to only match kana readings or kanji writings. Applies to all
+//! Japanese queries, not only the ones with regex syntax
 //!
 //! # Note
 //! All queries containing (custom)regex syntax will be handled as full-word matches. In other words if
 //! a query contains regex syntax, only full words will be matched. If words should also be open to
 //! an end (eg. right variable) then a regex charecter has to be placed at the end as well
 
-use std::hash::Hash;
+use std::{hash::Hash, iter::Peekable, ops::RangeInclusive, str::Chars};
 
+use indexes::regex::Script;
+use jp_utils::JapaneseExt;
 use regex::Regex;
 
 /// All characters treated as regex characters
-pub const REGEX_CHARS: &[char] = &['*', '?', '?'];
+pub const REGEX_CHARS: &[char] = &['*', '?', '+', '[', ']', '{', '}'];
+
+/// Max amount of repetitions allowed in `{n,m}`
+const MAX_REPEAT: usize = 20;
 
 /// Regex Search query. Can be used to match words
 #[derive(Clone, Debug)]
 pub struct RegexSQuery {
     query: String,
     regex: Regex,
+    items: Vec<Item>,
+    target: RegexTarget,
+}
+
+/// Readings of a word a regex query gets matched against
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RegexTarget {
+    #[default]
+    All,
+    Kana,
+    Kanji,
+}
+
+/// Something a word needs to contain at least once in order to match a regex query
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Requirement {
+    Char(char),
+    Script(Script),
+}
+
+/// A single token of a regex query
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Char(char),
+    /// Exactly one character
+    Any,
+    /// Any amount of characters
+    AnySeq,
+    Set {
+        chars: Vec<char>,
+        negated: bool,
+    },
+    Script(Vec<Script>),
+}
+
+/// A token along with how often it has to occur
+#[derive(Clone, Debug, PartialEq)]
+struct Item {
+    token: Token,
+    min: usize,
+    max: usize,
 }
 
 impl RegexSQuery {
     /// Create a new regex query. Returns `None` if invalid or no regex given
     pub fn new(query: &str) -> Option<Self> {
         let query = adjust_regex(query);
+        let (target, pattern) = parse_target(&query);
 
-        if !Self::is_regex(&query) {
+        let items = parse(pattern)?;
+        if items.iter().all(|i| i.is_literal()) {
             return None;
         }
 
-        let regex = Regex::new(&Self::convert_regex(&query)).ok()?;
-        Some(RegexSQuery { query, regex })
+        let regex = Regex::new(&Self::convert_regex(&items)).ok()?;
+        Some(RegexSQuery {
+            query,
+            regex,
+            items,
+            target,
+        })
+    }
+
+    /// Restricts the query to the given readings. Used for queries whose target prefix got
+    /// stripped already
+    #[inline]
+    pub fn with_target(mut self, target: RegexTarget) -> Self {
+        self.target = target;
+        self
     }
 
     /// Returns `true` if a word matches the regex query
     #[inline]
     pub fn matches(&self, word: &str) -> bool {
-        self.regex.is_match(word)
+        let target_matches = match self.target {
+            RegexTarget::All => true,
+            RegexTarget::Kana => word.is_kana(),
+            RegexTarget::Kanji => word.has_kanji(),
+        };
+        target_matches && self.regex.is_match(word)
     }
 
     /// Returns all characters that don't represent regex syntax
     pub fn get_chars(&self) -> Vec<char> {
         let mut out = Vec::with_capacity(self.query.len());
-        for c in self.query.chars() {
-            if !REGEX_CHARS.contains(&c) {
-                out.push(c);
+        for item in &self.items {
+            match &item.token {
+                Token::Char(c) => out.push(*c),
+                Token::Set {
+                    chars,
+                    negated: false,
+                } => out.extend(chars),
+                _ => (),
             }
         }
         out
     }
 
-    /// Returns a real regex expression which will be used to match words
-    fn convert_regex(query: &str) -> String {
-        let mut out = String::with_capacity(query.len() + 2);
-        out.push('^');
-        out.push_str(
-            &query
-                .replace('*', ".*")
-                .replace('?', ".{1}")
-                .replace('+', ".{1}"),
-        );
-        if !out.ends_with('$') {
-            out.push('$');
+    /// Returns `true` if the query contains a script placeholder like `{kanji}`
+    #[inline]
+    pub fn has_script(&self) -> bool {
+        self.items
+            .iter()
+            .any(|i| matches!(i.token, Token::Script(..)))
+    }
+
+    /// Returns all requirements a matching word has to fulfill. A word has to fulfill at least one
+    /// requirement of each returned list
+    pub fn requirements(&self) -> Vec<Vec<Requirement>> {
+        let mut out: Vec<Vec<Requirement>> = vec![];
+
+        for item in self.items.iter().filter(|i| i.min > 0) {
+            let req = match &item.token {
+                Token::Char(c) => vec![Requirement::Char(*c)],
+                Token::Set {
+                    chars,
+                    negated: false,
+                } => chars.iter().map(|i| Requirement::Char(*i)).collect(),
+                Token::Script(scripts) => scripts.iter().map(|i| Requirement::Script(*i)).collect(),
+                _ => continue,
+            };
+
+            if !out.contains(&req) {
+                out.push(req);
+            }
         }
+
         out
     }
 
-    /// Returns `true` if query can be interpreted as regex query
+    /// Returns the range of lengths a matching reading can have. `None` if the length is
+    /// unbounded
+    pub fn len_bounds(&self) -> Option<RangeInclusive<usize>> {
+        if self.items.iter().any(|i| i.token == Token::AnySeq) {
+            return None;
+        }
+
+        let min = self.items.iter().map(|i| i.min).sum();
+        let max = self.items.iter().map(|i| i.max).sum();
+        Some(min..=max)
+    }
+
+    /// Returns the readings the query should be matched against
     #[inline]
-    fn is_regex(query: &str) -> bool {
-        let query = adjust_regex(query);
-        query.contains('*') || query.contains('+') || query.contains('?')
+    pub fn target(&self) -> RegexTarget {
+        self.target
+    }
+
+    /// Returns a real regex expression which will be used to match words
+    fn convert_regex(items: &[Item]) -> String {
+        let mut out = String::with_capacity(items.len() * 2 + 2);
+        out.push('^');
+
+        for item in items {
+            let token = match &item.token {
+                Token::Char(c) => regex::escape(&c.to_string()),
+                Token::Any => ".".to_string(),
+                Token::AnySeq => ".*".to_string(),
+                Token::Set { chars, negated } => {
+                    let chars: String = chars
+                        .iter()
+                        .map(|i| regex::escape(&i.to_string()))
+                        .collect();
+                    let negation = if *negated { "^" } else { "" };
+                    format!("[{negation}{chars}]")
+                }
+                Token::Script(scripts) => {
+                    let classes: Vec<_> = scripts.iter().map(|i| i.regex_class()).collect();
+                    format!("(?:{})", classes.join("|"))
+                }
+            };
+
+            if item.min == 1 && item.max == 1 || item.token == Token::AnySeq {
+                out.push_str(&token);
+            } else {
+                out.push_str(&format!("(?:{token}){{{},{}}}", item.min, item.max));
+            }
+        }
+
+        out.push('$');
+        out
     }
 
     /// Get a reference to the regex squery's query.
@@ -86,6 +224,119 @@ impl RegexSQuery {
     }
 }
 
+impl Item {
+    #[inline]
+    fn new(token: Token) -> Self {
+        Self {
+            token,
+            min: 1,
+            max: 1,
+        }
+    }
+
+    /// Returns `true` if the item only matches itself
+    #[inline]
+    fn is_literal(&self) -> bool {
+        matches!(self.token, Token::Char(..)) && self.min == 1 && self.max == 1
+    }
+}
+
+/// Parses the tokens of a regex query. Returns `None` if the query contains invalid syntax
+fn parse(query: &str) -> Option<Vec<Item>> {
+    let mut items: Vec<Item> = vec![];
+    let mut chars = query.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '*' => Token::AnySeq,
+            '?' | '+' => Token::Any,
+            '[' => parse_set(&mut chars)?,
+            '{' => {
+                let inner = take_until(&mut chars, '}')?;
+                if let Some(script) = parse_script(&inner) {
+                    Token::Script(script)
+                } else {
+                    let (min, max) = parse_repetition(&inner)?;
+                    let last = items.last_mut()?;
+                    if last.token == Token::AnySeq || last.min != 1 || last.max != 1 {
+                        return None;
+                    }
+                    last.min = min;
+                    last.max = max;
+                    continue;
+                }
+            }
+            ']' | '}' => return None,
+            c => Token::Char(c),
+        };
+
+        items.push(Item::new(token));
+    }
+
+    Some(items)
+}
+
+/// Parses a character set. Expects the leading `[` to be consumed already
+fn parse_set(chars: &mut Peekable<Chars>) -> Option<Token> {
+    let negated = chars.next_if_eq(&'^').is_some();
+    let set: Vec<char> = take_until(chars, ']')?.chars().collect();
+    if set.is_empty() {
+        return None;
+    }
+    Some(Token::Set {
+        chars: set,
+        negated,
+    })
+}
+
+fn parse_script(inp: &str) -> Option<Vec<Script>> {
+    Some(match inp.trim().to_lowercase().as_str() {
+        "kanji" | "漢字" => vec![Script::Kanji],
+        "kana" | "かな" | "仮名" => vec![Script::Hiragana, Script::Katakana],
+        "hiragana" | "ひらがな" => vec![Script::Hiragana],
+        "katakana" | "カタカナ" => vec![Script::Katakana],
+        _ => return None,
+    })
+}
+
+/// Parses `n`, `n,` and `n,m` repetitions
+fn parse_repetition(inp: &str) -> Option<(usize, usize)> {
+    let (min, max) = match inp.split_once(',') {
+        Some((min, "")) => (min.trim().parse().ok()?, MAX_REPEAT),
+        Some((min, max)) => (min.trim().parse().ok()?, max.trim().parse().ok()?),
+        None => {
+            let n = inp.trim().parse().ok()?;
+            (n, n)
+        }
+    };
+
+    (min <= max && max > 0 && max <= MAX_REPEAT).then(|| (min, max))
+}
+
+/// Returns all characters until `end`. Consumes `end` as well. Returns `None` if `end` doesn't
+/// occur
+fn take_until(chars: &mut Peekable<Chars>, end: char) -> Option<String> {
+    let mut out = String::new();
+    for c in chars.by_ref() {
+        if c == end {
+            return Some(out);
+        }
+        out.push(c);
+    }
+    None
+}
+
+/// Strips the target prefix of the query
+pub(crate) fn parse_target(query: &str) -> (RegexTarget, &str) {
+    if let Some(query) = query.strip_prefix("kana:") {
+        return (RegexTarget::Kana, query.trim_start());
+    }
+    if let Some(query) = query.strip_prefix("kanji:") {
+        return (RegexTarget::Kanji, query.trim_start());
+    }
+    (RegexTarget::All, query)
+}
+
 /// Adjusts the query to a consistent format
 #[inline]
 fn adjust_regex(query: &str) -> String {
@@ -93,6 +344,12 @@ fn adjust_regex(query: &str) -> String {
         .replace('＊', "*")
         .replace('＋', "+")
         .replace('？', "?")
+        .replace('［', "[")
+        .replace('］', "]")
+        .replace('｛', "{")
+        .replace('｝', "}")
+        .replace('，', ",")
+        .replace('：', ":")
 }
 
 impl Hash for RegexSQuery {
@@ -110,3 +367,102 @@ impl PartialEq for RegexSQuery {
 }
 
 impl Eq for RegexSQuery {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_wildcards() {
+        let query = RegexSQuery::new("宇宙*行士").unwrap();
+        assert!(query.matches("宇宙飛行士"));
+        assert!(!query.matches("宇宙飛行"));
+
+        let query = RegexSQuery::new("宇宙？行士").unwrap();
+        assert!(query.matches("宇宙飛行士"));
+        assert!(!query.matches("宇宙行士"));
+
+        assert!(RegexSQuery::new("宇宙飛行士").is_none());
+    }
+
+    #[test]
+    fn test_set() {
+        let query = RegexSQuery::new("[日月]曜日").unwrap();
+        assert!(query.matches("日曜日"));
+        assert!(query.matches("月曜日"));
+        assert!(!query.matches("火曜日"));
+
+        let query = RegexSQuery::new("[^日月]曜日").unwrap();
+        assert!(query.matches("火曜日"));
+        assert!(!query.matches("日曜日"));
+
+        assert!(RegexSQuery::new("[日月曜日").is_none());
+    }
+
+    #[test]
+    fn test_script() {
+        let query = RegexSQuery::new("{kanji}{2}する").unwrap();
+        assert!(query.matches("勉強する"));
+        assert!(!query.matches("勉する"));
+        assert!(!query.matches("べんきょうする"));
+
+        let query = RegexSQuery::new("{katakana}{1,}").unwrap();
+        assert!(query.matches("テレビ"));
+        assert!(query.matches("コーヒー"));
+        assert!(!query.matches("てれび"));
+    }
+
+    #[test]
+    fn test_repetition() {
+        let query = RegexSQuery::new("?{2,3}ん").unwrap();
+        assert!(query.matches("にほん"));
+        assert!(query.matches("せんせん"));
+        assert!(!query.matches("ほん"));
+
+        assert!(RegexSQuery::new("{2}ん").is_none());
+        assert!(RegexSQuery::new("*{2}ん").is_none());
+        assert!(RegexSQuery::new("?{3,2}ん").is_none());
+    }
+
+    #[test]
+    fn test_escaped() {
+        let query = RegexSQuery::new("a.c*").unwrap();
+        assert!(query.matches("a.cd"));
+        assert!(!query.matches("abcd"));
+    }
+
+    #[test]
+    fn test_target() {
+        let query = RegexSQuery::new("kana:に?ん").unwrap();
+        assert_eq!(query.target(), RegexTarget::Kana);
+        assert!(query.matches("にほん"));
+
+        let query = RegexSQuery::new("kanji:*本").unwrap();
+        assert!(query.matches("日本"));
+        assert!(!query.matches("ほん"));
+    }
+
+    #[test]
+    fn test_requirements() {
+        let query = RegexSQuery::new("[日月]曜?{kana}{0,1}").unwrap();
+        assert_eq!(
+            query.requirements(),
+            vec![
+                vec![Requirement::Char('日'), Requirement::Char('月')],
+                vec![Requirement::Char('曜')],
+            ]
+        );
+        assert_eq!(query.len_bounds(), Some(3..=4));
+        assert_eq!(query.get_chars(), vec!['日', '月', '曜']);
+
+        let query = RegexSQuery::new("{kana}*").unwrap();
+        assert_eq!(
+            query.requirements(),
+            vec![vec![
+                Requirement::Script(Script::Hiragana),
+                Requirement::Script(Script::Katakana)
+            ]]
+        );
+        assert_eq!(query.len_bounds(), None);
+    }
+}
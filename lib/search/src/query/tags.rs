@@ -1,9 +1,20 @@
 use types::jotoba::{
+    names::name_type::NameType,
     search::SearchTarget,
     sentences,
-    words::{misc::Misc, part_of_speech::PosSimple},
+    words::{misc::Misc, part_of_speech::PosSimple, pitch::PitchPattern},
 };
 
+/// Amount of kanji frequency ranks within a single [`Tag::Frequency`] bucket
+pub const FREQUENCY_BUCKET_SIZE: u16 = 500;
+
+/// Amount of [`Tag::Frequency`] buckets covering all ranked kanji
+pub const FREQUENCY_BUCKETS: u8 = 5;
+
+/// Highest school grade of kanji. Grades 1 to 6 are taught in elementary school, 8 in secondary
+/// school and 9 to 10 are used for name kanji
+pub const MAX_GRADE: u8 = 10;
+
 /// Hashtag based search tags
 #[derive(Debug, Clone, Copy, PartialEq, Hash)]
 pub enum Tag {
@@ -15,17 +26,42 @@ pub enum Tag {
     Katakana,
     SentenceTag(sentences::Tag),
     IrregularIruEru,
+    PitchPattern(PitchPattern),
+    /// Amount of morae after which the pitch drops
+    PitchDrop(u8),
+
+    // Kanji tags
+    /// Inclusive range of stroke counts
+    StrokeCount(u8, u8),
+    Grade(u8),
+    /// Frequency rank bucket starting at 1 for the most frequent kanji
+    Frequency(u8),
+    Radical(char),
+
+    // Sentence tags
+    /// Inclusive range of sentence levels
+    SentenceLevel(i8, i8),
+
+    // Name tags
+    NameType(NameType),
 
     // Non producer
     SearchType(SearchTarget),
     Hidden,
+    /// Ranks names of the given type higher without filtering others
+    PreferNameType(NameType),
+    /// Orders sentences from easiest to hardest
+    EasyFirst,
 }
 
 impl Tag {
     /// Returns true if the tag can be used without a query
     #[inline]
     pub fn is_producer(&self) -> bool {
-        !self.is_search_type() && !self.is_hidden()
+        !self.is_search_type()
+            && !self.is_hidden()
+            && !self.is_prefer_name_type()
+            && !self.is_easy_first()
     }
 
     /// Returns `true` if the tag is [`SearchType`].
@@ -149,4 +185,52 @@ impl Tag {
     pub fn is_katakana(&self) -> bool {
         matches!(self, Self::Katakana)
     }
+
+    /// Returns `true` if the tag filters kanji only
+    #[inline]
+    pub fn is_kanji_tag(&self) -> bool {
+        matches!(
+            self,
+            Self::StrokeCount(..) | Self::Grade(..) | Self::Frequency(..) | Self::Radical(..)
+        )
+    }
+
+    #[inline]
+    pub fn as_name_type(&self) -> Option<&NameType> {
+        if let Self::NameType(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the tag is [`PreferNameType`].
+    ///
+    /// [`PreferNameType`]: Tag::PreferNameType
+    #[inline]
+    pub fn is_prefer_name_type(&self) -> bool {
+        matches!(self, Self::PreferNameType(..))
+    }
+
+    /// Returns `true` if the tag is [`SentenceLevel`].
+    ///
+    /// [`SentenceLevel`]: Tag::SentenceLevel
+    #[inline]
+    pub fn is_sentence_level(&self) -> bool {
+        matches!(self, Self::SentenceLevel(..))
+    }
+
+    /// Returns `true` if the tag is [`EasyFirst`].
+    ///
+    /// [`EasyFirst`]: Tag::EasyFirst
+    #[inline]
+    pub fn is_easy_first(&self) -> bool {
+        matches!(self, Self::EasyFirst)
+    }
+
+    /// Returns `true` if the tag filters words by their pitch accent
+    #[inline]
+    pub fn is_pitch_tag(&self) -> bool {
+        matches!(self, Self::PitchPattern(..) | Self::PitchDrop(..))
+    }
 }
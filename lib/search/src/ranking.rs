@@ -0,0 +1,199 @@
+//! Weights used to rank search results. Additional profiles can be loaded from a file and get
+//! selected per search which allows comparing ranking changes without recompiling. A loaded
+//! profile named `default` replaces the built-in weights.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, error::Error, fs, path::Path, sync::RwLock};
+
+/// Name of the profile used if a search doesn't select one
+pub const DEFAULT_PROFILE: &str = "default";
+
+static PROFILES: Lazy<RwLock<HashMap<String, RankingProfile>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Loads all ranking profiles from a JSON or TOML file mapping profile names to their weights.
+/// Weights missing in a profile fall back to the built-in values. Returns the amount of loaded
+/// profiles
+///
+/// ```toml
+/// [strict_kana.words]
+/// reading_mismatch = 0.5
+///
+/// [strict_kana.sentences]
+/// max_top_dist = 0.5
+/// ```
+pub fn load<P: AsRef<Path>>(path: P) -> Result<usize, Box<dyn Error>> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)?;
+
+    let profiles: HashMap<String, RankingProfile> = match path.extension().and_then(|i| i.to_str())
+    {
+        Some("toml") => toml::from_str(&content)?,
+        _ => serde_json::from_str(&content)?,
+    };
+
+    let count = profiles.len();
+    PROFILES.write().unwrap().extend(profiles);
+    Ok(count)
+}
+
+/// Adds or replaces the profile with the given name
+pub fn set<S: Into<String>>(name: S, profile: RankingProfile) {
+    PROFILES.write().unwrap().insert(name.into(), profile);
+}
+
+/// Returns the profile with the given name. Returns the built-in weights for the default profile
+/// if it hasn't been overwritten
+pub fn get(name: &str) -> Option<RankingProfile> {
+    let profile = PROFILES.read().unwrap().get(name).copied();
+    if profile.is_none() && name == DEFAULT_PROFILE {
+        return Some(RankingProfile::default());
+    }
+    profile
+}
+
+/// Returns `true` if there is a profile with the given name
+#[inline]
+pub fn exists(name: &str) -> bool {
+    get(name).is_some()
+}
+
+/// Weights for ranking the results of all search targets
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct RankingProfile {
+    pub words: WordWeights,
+    pub names: NameWeights,
+    pub sentences: SentenceWeights,
+    pub kanji: KanjiWeights,
+}
+
+/// Weights for ranking words
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct WordWeights {
+    /// Factor for sentence reader results not matching the original query as well as the
+    /// sentences word does
+    pub original_query_mismatch: f32,
+    /// Factor for words whose reading isn't exactly the query
+    pub reading_mismatch: f32,
+    /// Factor for words without a JLPT level
+    pub no_jlpt: f32,
+    /// Factor for words not marked as common
+    pub not_common: f32,
+    /// Min text similarity of foreign queries for the gloss similarity to be taken into account
+    pub foreign_text_sim_threshold: f32,
+    /// Weight of the gloss similarity of foreign queries
+    pub foreign_gloss_weight: f32,
+    /// Factor for words of custom dictionaries
+    pub custom_boost: f32,
+}
+
+impl Default for WordWeights {
+    fn default() -> Self {
+        Self {
+            original_query_mismatch: 0.7,
+            reading_mismatch: 0.7,
+            no_jlpt: 0.999,
+            not_common: 0.999,
+            foreign_text_sim_threshold: 0.5,
+            foreign_gloss_weight: 100.0,
+            custom_boost: 1.0,
+        }
+    }
+}
+
+/// Weights for ranking names
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct NameWeights {
+    /// Weight passed to the weighted dice coefficient
+    pub dice_weight: f32,
+    /// Factor for names having the type preferred by the query
+    pub preferred_type: f32,
+}
+
+impl Default for NameWeights {
+    fn default() -> Self {
+        Self {
+            dice_weight: 0.1,
+            preferred_type: 1.5,
+        }
+    }
+}
+
+/// Weights for ranking sentences
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct SentenceWeights {
+    /// Weight of the query vectors length compared to the sentences one
+    pub query_weight: f32,
+    /// Factor for sentences without a translation in the users language for Japanese queries
+    pub native_no_translation: f32,
+    /// Factor for sentences without a translation in the users language for foreign queries
+    pub foreign_no_translation: f32,
+    /// Max distance of a sentences relevance to the best one to still be shown
+    pub max_top_dist: f32,
+}
+
+impl Default for SentenceWeights {
+    fn default() -> Self {
+        Self {
+            query_weight: 100.0,
+            native_no_translation: 0.99,
+            foreign_no_translation: 0.8,
+            max_top_dist: 0.9,
+        }
+    }
+}
+
+/// Weights for ranking kanji found by their meaning
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct KanjiWeights {
+    /// Factor for kanji without a meaning being equal to the query
+    pub meaning_mismatch: f32,
+    /// Factor for kanji without a meaning starting with the query
+    pub no_prefix_match: f32,
+    /// Max amount a kanjis relevance gets reduced by for being uncommon
+    pub frequency: f32,
+    /// Factor for kanji not taught in school
+    pub no_grade: f32,
+    /// Factor for kanji without a JLPT level
+    pub no_jlpt: f32,
+}
+
+impl Default for KanjiWeights {
+    fn default() -> Self {
+        Self {
+            meaning_mismatch: 0.7,
+            no_prefix_match: 0.8,
+            frequency: 0.2,
+            no_grade: 0.95,
+            no_jlpt: 0.95,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_partial_profile() {
+        let toml = "[test.words]\nreading_mismatch = 0.5\n";
+        let profiles: HashMap<String, RankingProfile> = toml::from_str(toml).unwrap();
+        let profile = profiles["test"];
+
+        assert_eq!(profile.words.reading_mismatch, 0.5);
+        assert_eq!(profile.words.no_jlpt, 0.999);
+        assert_eq!(profile.sentences, SentenceWeights::default());
+    }
+
+    #[test]
+    fn test_default_profile() {
+        assert!(exists(DEFAULT_PROFILE));
+        assert!(!exists("unknown_profile"));
+    }
+}
@@ -0,0 +1,187 @@
+//! Reporting of slow queries. Searches taking longer than the configured threshold get appended
+//! to a JSON-lines file which gets rotated once it grows too large.
+
+use crate::{executor::search_result::ProducerTiming, query::Query};
+use log::{error, warn};
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use types::jotoba::language::Language;
+
+/// Size in bytes after which the report file gets rotated
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+static REPORTER: OnceCell<Reporter> = OnceCell::new();
+
+/// Enables reporting of queries taking longer than `threshold` to the file at `path`
+pub fn init<P: AsRef<Path>>(path: P, threshold: Duration) -> io::Result<()> {
+    let reporter = Reporter::new(path.as_ref().to_path_buf(), threshold)?;
+    if REPORTER.set(reporter).is_err() {
+        warn!("Slow query reporting already initialized");
+    }
+    Ok(())
+}
+
+/// Reports a search if it took longer than the configured threshold. Does nothing if reporting
+/// wasn't initialized
+pub fn report(query: &Query, duration: Duration, timings: &[ProducerTiming], results: usize) {
+    let reporter = match REPORTER.get() {
+        Some(r) => r,
+        None => return,
+    };
+
+    if duration < reporter.threshold {
+        return;
+    }
+
+    let entry = Entry::new(query, duration, timings, results);
+    if let Err(err) = reporter.write(&entry) {
+        error!("Failed to report slow query: {err}");
+    }
+}
+
+struct Reporter {
+    path: PathBuf,
+    threshold: Duration,
+    /// Size in bytes after which the file gets rotated
+    max_size: u64,
+    file: Mutex<File>,
+}
+
+impl Reporter {
+    fn new(path: PathBuf, threshold: Duration) -> io::Result<Self> {
+        let file = open(&path)?;
+        Ok(Self {
+            path,
+            threshold,
+            max_size: MAX_FILE_SIZE,
+            file: Mutex::new(file),
+        })
+    }
+
+    fn write(&self, entry: &Entry) -> io::Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        let mut file = self.file.lock().unwrap();
+
+        if file.metadata()?.len() + line.len() as u64 > self.max_size {
+            // Keep the previous file next to the new one
+            let mut rotated = self.path.clone().into_os_string();
+            rotated.push(".1");
+            fs::rename(&self.path, rotated)?;
+            *file = open(&self.path)?;
+        }
+
+        file.write_all(line.as_bytes())
+    }
+}
+
+#[inline]
+fn open(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// A single line in the report file
+#[derive(Serialize)]
+struct Entry<'a> {
+    /// Unix timestamp in seconds
+    timestamp: u64,
+    raw_query: &'a str,
+    query: &'a str,
+    target: String,
+    form: String,
+    tags: Vec<String>,
+    language: Language,
+    query_language: String,
+    duration_ms: u128,
+    producers: Vec<ProducerEntry<'a>>,
+    results: usize,
+}
+
+#[derive(Serialize)]
+struct ProducerEntry<'a> {
+    name: &'a str,
+    duration_ms: u128,
+    found: usize,
+}
+
+impl<'a> Entry<'a> {
+    fn new(
+        query: &'a Query,
+        duration: Duration,
+        timings: &'a [ProducerTiming],
+        results: usize,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|i| i.as_secs())
+            .unwrap_or_default();
+
+        let producers = timings
+            .iter()
+            .map(|i| ProducerEntry {
+                name: &i.name,
+                duration_ms: i.duration.as_millis(),
+                found: i.found,
+            })
+            .collect();
+
+        Self {
+            timestamp,
+            raw_query: &query.raw_query,
+            query: &query.query_str,
+            target: format!("{:?}", query.target),
+            form: format!("{:?}", query.form),
+            tags: query.tags.iter().map(|i| format!("{i:?}")).collect(),
+            language: query.get_search_lang(),
+            query_language: format!("{:?}", query.q_lang),
+            duration_ms: duration.as_millis(),
+            producers,
+            results,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rotate() {
+        let dir = std::env::temp_dir().join(format!("jotoba_report_{}", std::process::id()));
+        // Leftovers of a previous run
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("slow_queries.jsonl");
+        let rotated = dir.join("slow_queries.jsonl.1");
+
+        let query = Query {
+            raw_query: "test".to_string(),
+            ..Default::default()
+        };
+        let entry = Entry::new(&query, Duration::from_secs(1), &[], 0);
+        let line_len = serde_json::to_string(&entry).unwrap().len() as u64 + 1;
+
+        let mut reporter = Reporter::new(path.clone(), Duration::ZERO).unwrap();
+        reporter.max_size = line_len * 2;
+
+        reporter.write(&entry).unwrap();
+        reporter.write(&entry).unwrap();
+        assert!(!rotated.exists());
+        assert_eq!(fs::metadata(&path).unwrap().len(), line_len * 2);
+
+        // Exceeds the limit, so the full file gets moved away
+        reporter.write(&entry).unwrap();
+        assert_eq!(fs::metadata(&rotated).unwrap().len(), line_len * 2);
+        assert_eq!(fs::metadata(&path).unwrap().len(), line_len);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -65,7 +65,12 @@ impl<'a> Searchable for Search<'a> {
 
     #[inline]
     fn max_top_dist(&self) -> Option<f32> {
-        Some(0.9)
+        // Relevance isn't comparable anymore if sentences are ordered by their difficulty
+        if self.query.easy_first() {
+            return None;
+        }
+
+        Some(self.query.ranking().sentences.max_top_dist)
         //None
     }
 }
@@ -0,0 +1,42 @@
+use types::jotoba::sentences::Sentence;
+
+/// Returns a relevance ordering sentences from easiest to hardest. Sentences are ordered by their
+/// guessed JLPT level first and by their level second. `relevance` is only used to order
+/// sentences of the same difficulty and is expected to be within 0..=1
+pub fn easy_first(sentence: &Sentence, relevance: f32) -> f32 {
+    // N5 is the easiest level. Sentences without a guess are treated as hardest
+    let jlpt = sentence.jlpt_guess.map_or(0, |i| i.get()) as f32;
+
+    // Lower levels are easier
+    let level = sentence.level().unwrap_or(i8::MAX);
+    let level = (i8::MAX as i16 - level as i16) as f32;
+
+    // Each jlpt level covers all 256 possible levels and each level covers the relevance
+    (jlpt * 256.0 + level) * 2.0 + relevance.clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::num::{NonZeroI8, NonZeroU8};
+
+    fn sentence(jlpt: Option<u8>, level: Option<i8>) -> Sentence {
+        let mut sentence = Sentence::default();
+        sentence.jlpt_guess = jlpt.and_then(NonZeroU8::new);
+        sentence.level = level.and_then(|i| NonZeroI8::new(i + 10));
+        sentence
+    }
+
+    #[test]
+    fn test_easy_first() {
+        let n5 = easy_first(&sentence(Some(5), Some(3)), 0.0);
+        let n5_harder = easy_first(&sentence(Some(5), Some(4)), 1.0);
+        let n4 = easy_first(&sentence(Some(4), Some(-5)), 1.0);
+        let unknown = easy_first(&sentence(None, None), 1.0);
+
+        assert!(n5 > n5_harder);
+        assert!(n5_harder > n4);
+        assert!(n4 > unknown);
+        assert!(easy_first(&sentence(Some(5), Some(3)), 0.5) > n5);
+    }
+}
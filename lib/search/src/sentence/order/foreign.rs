@@ -1,3 +1,5 @@
+use super::difficulty;
+use crate::ranking::SentenceWeights;
 use engine::relevance::{data::SortData, RelevanceEngine};
 use sparse_vec::SpVec32;
 use types::jotoba::{language::Language, sentences::Sentence};
@@ -5,11 +7,18 @@ use vsm::doc_vec::DocVector;
 
 pub struct ForeignOrder {
     lang: Language,
+    weights: SentenceWeights,
+    /// Order results from easiest to hardest
+    easy_first: bool,
 }
 
 impl ForeignOrder {
-    pub fn new(lang: Language) -> Self {
-        Self { lang }
+    pub fn new(lang: Language, weights: SentenceWeights, easy_first: bool) -> Self {
+        Self {
+            lang,
+            weights,
+            easy_first,
+        }
     }
 }
 
@@ -25,7 +34,11 @@ impl RelevanceEngine for ForeignOrder {
         let mut rel = item.vec_similarity();
 
         if !item.item().has_translation(self.lang) {
-            rel *= 0.8;
+            rel *= self.weights.foreign_no_translation;
+        }
+
+        if self.easy_first {
+            rel = difficulty::easy_first(item.item(), rel);
         }
 
         rel
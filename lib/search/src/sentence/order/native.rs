@@ -1,17 +1,24 @@
+use super::difficulty;
+use crate::ranking::SentenceWeights;
 use engine::relevance::{data::SortData, RelevanceEngine};
 use sparse_vec::{SpVec32, VecExt};
 use types::jotoba::{language::Language, sentences::Sentence};
 use vsm::doc_vec::DocVector;
 
-pub const QUERY_WEIGHT: f32 = 100.0;
-
 pub struct NativeOrder {
     lang: Language,
+    weights: SentenceWeights,
+    /// Order results from easiest to hardest
+    easy_first: bool,
 }
 
 impl NativeOrder {
-    pub fn new(lang: Language) -> Self {
-        Self { lang }
+    pub fn new(lang: Language, weights: SentenceWeights, easy_first: bool) -> Self {
+        Self {
+            lang,
+            weights,
+            easy_first,
+        }
     }
 }
 
@@ -26,10 +33,15 @@ impl RelevanceEngine for NativeOrder {
         item: &SortData<'item, 'query, Self::OutItem, Self::IndexItem, Self::Query>,
     ) -> f32 {
         //let mut rel = term_dist(item.query(), item.index_item().vec());
-        let mut rel = sim(item.query(), item.index_item().vec(), QUERY_WEIGHT);
+        let query_weight = self.weights.query_weight;
+        let mut rel = sim(item.query(), item.index_item().vec(), query_weight);
 
         if !item.item().has_translation(self.lang) {
-            rel *= 0.99;
+            rel *= self.weights.native_no_translation;
+        }
+
+        if self.easy_first {
+            rel = difficulty::easy_first(item.item(), rel);
         }
 
         rel
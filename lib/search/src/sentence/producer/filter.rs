@@ -1,5 +1,8 @@
 use super::kanji;
-use crate::{engine, query::Query};
+use crate::{
+    engine,
+    query::{expr::ExprEval, Query, Tag},
+};
 use index_framework::traits::{backend::Backend, dictionary::IndexDictionary};
 use jp_utils::JapaneseExt;
 use sparse_vec::VecExt;
@@ -26,18 +29,49 @@ pub(crate) fn filter_sentence(query: &Query, sentence: &Sentence) -> bool {
         }
     }
 
-    if !query
-        .tags
-        .iter()
-        .filter_map(|i| i.as_sentence_tag())
-        .all(|tag| sentence.has_tag(tag))
-    {
+    let eval = SentenceExprEval { sentence };
+
+    if !query.tags.iter().all(|i| eval.eval_tag(i).unwrap_or(true)) {
+        return false;
+    }
+
+    if !query.matches_expr(&eval) {
         return false;
     }
 
     true
 }
 
+/// Evaluates query expressions for a single sentence
+struct SentenceExprEval<'a> {
+    sentence: &'a Sentence,
+}
+
+impl<'a> ExprEval for SentenceExprEval<'a> {
+    fn eval_tag(&self, tag: &Tag) -> Option<bool> {
+        Some(match tag {
+            Tag::SentenceTag(s_tag) => self.sentence.has_tag(s_tag),
+            Tag::Jlpt(jlpt) => self.sentence.jlpt_guess.map(|i| i.get()) == Some(*jlpt),
+            Tag::SentenceLevel(min, max) => self
+                .sentence
+                .level()
+                .map_or(false, |i| (*min..=*max).contains(&i)),
+            _ => return None,
+        })
+    }
+
+    fn eval_term(&self, term: &str) -> bool {
+        if term.is_japanese() {
+            return sentence_contains_jp(self.sentence, term);
+        }
+
+        self.sentence
+            .translations
+            .iter()
+            .any(|i| i.text.to_lowercase().contains(term))
+    }
+}
+
 fn by_quot_marks(query: &Query, sentence: &Sentence) -> bool {
     if !by_quot_marks_jp(query, sentence) {
         return false;
@@ -71,17 +105,9 @@ fn by_quot_marks_fe(query: &Query, sentence: &str) -> bool {
 */
 
 fn by_quot_marks_jp(query: &Query, sentence: &Sentence) -> bool {
-    let jp_sentence = &sentence.japanese;
-
     let jp_terms = query.must_contain.iter().filter(|i| i.is_japanese());
     for needle in jp_terms {
-        let is_kana = needle.is_kana();
-
-        // If kana reading and kana contains needle
-        if (is_kana && sentence.get_kana().contains(needle))
-            // Or full reading contains
-            || (!is_kana && jp_sentence.contains(needle))
-        {
+        if sentence_contains_jp(sentence, needle) {
             continue;
         }
 
@@ -91,6 +117,16 @@ fn by_quot_marks_jp(query: &Query, sentence: &Sentence) -> bool {
     true
 }
 
+/// Returns `true` if the japanese sentence or its kana reading contains `needle`
+fn sentence_contains_jp(sentence: &Sentence, needle: &str) -> bool {
+    let is_kana = needle.is_kana();
+
+    // If kana reading and kana contains needle
+    (is_kana && sentence.get_kana().contains(needle))
+        // Or full reading contains
+        || (!is_kana && sentence.japanese.contains(needle))
+}
+
 /// Vector filter for Sentences filtering based on quoted terms
 pub struct FeQotTermsVecFilter {
     mc_terms: Vec<u32>,
@@ -1,7 +1,11 @@
 use super::filter::{self, FeQotTermsVecFilter};
 use crate::{
     engine::sentences::foreign,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     query::{Query, QueryLang},
     sentence::{order::foreign::ForeignOrder, Search},
 };
@@ -19,16 +23,25 @@ impl<'a> ForeignProducer<'a> {
         Self { query, language }
     }
 
-    fn task(&self) -> SearchTask<'static, foreign::Engine> {
-        let query_str = &self.query.query_str;
+    /// Returns a task for each text alternative of the query
+    fn tasks(&self) -> Vec<SearchTask<'static, foreign::Engine>> {
+        self.query
+            .text_alternatives()
+            .iter()
+            .map(|q_str| self.task(q_str))
+            .collect()
+    }
+
+    fn task(&self, query_str: &str) -> SearchTask<'static, foreign::Engine> {
         let query_c = self.query.clone();
         let vec_filter = FeQotTermsVecFilter::new(&self.query);
         let lang = self.query.lang();
+        let weights = self.query.ranking().sentences;
 
         SearchTask::with_language(query_str, self.language)
             .with_result_filter(move |i| filter::filter_sentence(&query_c, *i))
             .with_item_filter(move |i| vec_filter.filter(i))
-            .with_custom_order(ForeignOrder::new(lang))
+            .with_custom_order(ForeignOrder::new(lang, weights, self.query.easy_first()))
     }
 }
 
@@ -42,14 +55,22 @@ impl<'a> Producer for ForeignProducer<'a> {
             <Self::Target as Searchable>::ResAdd,
         >,
     ) {
-        self.task().find_to(out);
+        for mut task in self.tasks() {
+            task.find_to(out);
+        }
     }
 
     fn should_run(&self, _already_found: usize) -> bool {
         self.query.form.is_normal() && self.query.q_lang == QueryLang::Foreign
     }
 
+    fn dependency(&self) -> Dependency {
+        Dependency::None
+    }
+
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
-        self.task().estimate_to(out);
+        for mut task in self.tasks() {
+            task.estimate_to(out);
+        }
     }
 }
@@ -1,7 +1,11 @@
 use super::filter;
 use crate::{
     engine::sentences::native,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     query::{Query, QueryLang},
     sentence::{order::native::NativeOrder, Search},
 };
@@ -19,23 +23,33 @@ impl<'a> NativeProducer<'a> {
         Self { query, lang }
     }
 
-    fn task(&self) -> SearchTask<'static, native::Engine> {
+    /// Returns a task for each text alternative of the query
+    fn tasks(&self) -> Vec<SearchTask<'static, native::Engine>> {
+        self.jp_readings()
+            .iter()
+            .map(|q_str| self.task(q_str))
+            .collect()
+    }
+
+    fn task(&self, query_str: &str) -> SearchTask<'static, native::Engine> {
         let query = self.query.clone();
-        let query_str = self.jp_reading();
+        let weights = self.query.ranking().sentences;
 
-        SearchTask::with_language(&query_str, self.lang)
+        SearchTask::with_language(query_str, self.lang)
             .with_result_filter(move |sentence| filter::filter_sentence(&query, *sentence))
-            .with_custom_order(NativeOrder::new(self.query.lang()))
+            .with_custom_order(NativeOrder::new(
+                self.query.lang(),
+                weights,
+                self.query.easy_first(),
+            ))
     }
 
-    fn jp_reading(&self) -> String {
-        let mut query_str = self.query.query_str.clone();
-
+    fn jp_readings(&self) -> Vec<String> {
         if let Some(kanji_reading) = self.query.form.as_kanji_reading() {
-            query_str = kanji_reading.literal.to_string();
+            return vec![kanji_reading.literal.to_string()];
         }
 
-        query_str
+        self.query.text_alternatives()
     }
 }
 
@@ -49,14 +63,43 @@ impl<'a> Producer for NativeProducer<'a> {
             <Self::Target as Searchable>::ResAdd,
         >,
     ) {
-        self.task().find_to(out);
+        for mut task in self.tasks() {
+            task.find_to(out);
+        }
     }
 
     fn should_run(&self, _already_found: usize) -> bool {
         self.query.form.is_normal() && self.query.q_lang == QueryLang::Japanese
     }
 
+    fn dependency(&self) -> Dependency {
+        Dependency::None
+    }
+
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
-        self.task().estimate_to(out);
+        for mut task in self.tasks() {
+            task.estimate_to(out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query::parser::expr;
+
+    #[test]
+    fn test_or_query_readings() {
+        let expr = expr::parse("(猫 #n5) OR 犬").unwrap();
+        let query = Query {
+            query_str: "猫".to_string(),
+            q_lang: QueryLang::Japanese,
+            expr: Some(expr),
+            ..Default::default()
+        };
+
+        // Every branch gets searched, not only the first one
+        let producer = NativeProducer::new(&query, Language::English);
+        assert_eq!(producer.jp_readings(), vec!["猫", "犬"]);
     }
 }
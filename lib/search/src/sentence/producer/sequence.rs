@@ -1,5 +1,9 @@
 use crate::{
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     query::Query,
     sentence::Search,
 };
@@ -49,4 +53,8 @@ impl<'a> Producer for SequenceProducer<'a> {
     fn should_run(&self, _already_found: usize) -> bool {
         self.query.form.is_sequence()
     }
+
+    fn dependency(&self) -> Dependency {
+        Dependency::None
+    }
 }
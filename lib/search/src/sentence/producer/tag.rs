@@ -1,7 +1,11 @@
 use crate::{
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     query::{Query, Tag},
-    sentence::Search,
+    sentence::{order, Search},
 };
 use engine::{
     pushable::FilteredMaxCounter,
@@ -10,6 +14,9 @@ use engine::{
 };
 use types::jotoba::sentences::Sentence;
 
+/// Max amount of sentences to produce per tag
+const MAX_SENTENCES: usize = 1000;
+
 /// Producer for Tags
 pub struct TagProducer<'a> {
     query: &'a Query,
@@ -20,18 +27,29 @@ impl<'a> TagProducer<'a> {
         Self { query }
     }
 
+    /// Returns the producer tag of each branch of the query
+    fn get_producer_tags(&self) -> Vec<Tag> {
+        self.query
+            .branches()
+            .iter()
+            .filter_map(|branch| {
+                branch
+                    .tags
+                    .iter()
+                    .filter(|i| i.is_jlpt() || i.is_sentence_tag() || i.is_sentence_level())
+                    .find(|i| i.is_producer())
+                    .copied()
+            })
+            .collect()
+    }
+
     fn find_to<P>(&self, out: &mut P)
     where
         P: Pushable<Item = RelItem<&'static Sentence>>,
     {
-        let tag = self
-            .query
-            .tags
-            .iter()
-            .filter(|i| i.is_jlpt() || i.is_sentence_tag())
-            .find(|i| i.is_producer())
-            .unwrap();
-        self.push_tag(tag, out);
+        for tag in self.get_producer_tags() {
+            self.push_tag(&tag, out);
+        }
     }
 
     pub fn push_tag<P>(&self, tag: &Tag, out: &mut P)
@@ -43,6 +61,7 @@ impl<'a> TagProducer<'a> {
         match tag {
             Tag::SentenceTag(sentence_tag) => self.push_iter(s_res.by_tag(sentence_tag), out),
             Tag::Jlpt(jlpt) => self.push_iter(s_res.by_jlpt(*jlpt), out),
+            Tag::SentenceLevel(min, max) => self.push_iter(s_res.by_level(*min..=*max), out),
             _ => (),
         }
     }
@@ -51,13 +70,32 @@ impl<'a> TagProducer<'a> {
     where
         P: Pushable<Item = RelItem<&'static Sentence>>,
         I: Iterator<Item = &'static Sentence>,
+    {
+        if self.query.easy_first() {
+            // Only the first sentences get produced so the easiest ones have to come first
+            let sentences = easiest_first(iter);
+            self.push_limited(sentences.into_iter(), out, |_, s| {
+                order::difficulty::easy_first(s, 0.0)
+            });
+            return;
+        }
+
+        self.push_limited(iter, out, |c, _| c as f32);
+    }
+
+    /// Pushes the sentences of `iter` until `MAX_SENTENCES` got pushed successfully
+    fn push_limited<P, I, F>(&self, iter: I, out: &mut P, rel: F)
+    where
+        P: Pushable<Item = RelItem<&'static Sentence>>,
+        I: Iterator<Item = &'static Sentence>,
+        F: Fn(usize, &Sentence) -> f32,
     {
         let mut c = 0;
         for w in iter {
-            let item = RelItem::new(w, c as f32);
+            let item = RelItem::new(w, rel(c, w));
             if out.push(item) {
                 c += 1;
-                if c >= 1000 {
+                if c >= MAX_SENTENCES {
                     break;
                 }
             }
@@ -65,6 +103,18 @@ impl<'a> TagProducer<'a> {
     }
 }
 
+/// Returns all sentences of `iter` ordered from easiest to hardest
+fn easiest_first<I>(iter: I) -> Vec<&'static Sentence>
+where
+    I: Iterator<Item = &'static Sentence>,
+{
+    let mut sentences: Vec<_> = iter
+        .map(|i| (i, order::difficulty::easy_first(i, 0.0)))
+        .collect();
+    sentences.sort_by(|a, b| b.1.total_cmp(&a.1));
+    sentences.into_iter().map(|i| i.0).collect()
+}
+
 impl<'a> Producer for TagProducer<'a> {
     type Target = Search<'a>;
 
@@ -84,13 +134,33 @@ impl<'a> Producer for TagProducer<'a> {
     }
 
     fn should_run(&self, _already_found: usize) -> bool {
-        self.query.query_str.is_empty()
-            && self
-                .query
-                .tags
-                .iter()
-                // Only run for jlpt and sentence tags
-                .filter(|i| i.is_jlpt() || i.is_sentence_tag())
-                .any(|i| i.is_producer())
+        // Only run for jlpt and sentence tags
+        self.query.query_str.is_empty() && !self.get_producer_tags().is_empty()
+    }
+
+    fn dependency(&self) -> Dependency {
+        Dependency::None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::num::NonZeroU8;
+
+    fn sentence(jlpt: u8) -> &'static Sentence {
+        let mut sentence = Sentence::default();
+        sentence.jlpt_guess = NonZeroU8::new(jlpt);
+        Box::leak(Box::new(sentence))
+    }
+
+    #[test]
+    fn test_easiest_first() {
+        let sentences = [sentence(1), sentence(5), sentence(0), sentence(3)];
+        let ordered: Vec<_> = easiest_first(sentences.into_iter())
+            .into_iter()
+            .map(|i| i.jlpt_guess.map_or(0, |i| i.get()))
+            .collect();
+        assert_eq!(ordered, vec![5, 3, 1, 0]);
     }
 }
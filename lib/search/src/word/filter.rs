@@ -1,4 +1,4 @@
-use crate::query::Query;
+use crate::query::{expr::ExprEval, regex::RegexTarget, Query, QueryLang, Tag};
 use jp_utils::JapaneseExt;
 use std::borrow::Borrow;
 use types::jotoba::words::Word;
@@ -24,8 +24,11 @@ impl WordFilter {
             wf.by_pos_tags(word)?;
             wf.by_jlpt(word)?;
             wf.by_katakana_tag(word)?;
+            wf.by_pitch_tags(word)?;
+            wf.by_reading_target(word)?;
 
             wf.by_quot_marks(word)?;
+            wf.by_expr(word)?;
 
             Some(())
         }
@@ -40,10 +43,51 @@ impl WordFilter {
 
     #[inline]
     fn by_katakana_tag(&self, w: &Word) -> Option<()> {
-        let has_tag = self.query.has_tag(crate::query::Tag::Katakana);
+        let has_tag = self.query.has_tag(Tag::Katakana);
         (!has_tag || w.get_reading_str().is_katakana()).then(|| ())
     }
 
+    #[inline]
+    fn by_pitch_tags(&self, w: &Word) -> Option<()> {
+        self.query
+            .tags
+            .iter()
+            .all(|tag| matches_pitch(w, tag).unwrap_or(true))
+            .then(|| ())
+    }
+
+    /// Filters words whose readings containing the query aren't of the target set with a `kana:`
+    /// or `kanji:` prefix. Words matched by another form of the query (eg. an inflection) are kept.
+    /// Regex queries match their target themselves
+    fn by_reading_target(&self, w: &Word) -> Option<()> {
+        let target = self.query.reading_target;
+        if target == RegexTarget::All
+            || self.query.q_lang != QueryLang::Japanese
+            || self.query.is_regex()
+        {
+            return Some(());
+        }
+
+        let query = self.query.query_str.as_str();
+        let matched: Vec<_> = w
+            .reading_iter(true)
+            .map(|i| i.reading.as_str())
+            .filter(|i| i.contains(query))
+            .collect();
+        if matched.is_empty() {
+            return Some(());
+        }
+
+        matched
+            .iter()
+            .any(|i| match target {
+                RegexTarget::Kana => i.is_kana(),
+                RegexTarget::Kanji => i.has_kanji(),
+                RegexTarget::All => true,
+            })
+            .then(|| ())
+    }
+
     #[inline]
     fn by_jlpt(&self, w: &Word) -> Option<()> {
         // Ignore if not set
@@ -99,6 +143,15 @@ impl WordFilter {
         fn_q_terms.is_empty().then(|| ())
     }
 
+    #[inline]
+    fn by_expr(&self, w: &Word) -> Option<()> {
+        let eval = WordExprEval {
+            filter: self,
+            word: w,
+        };
+        self.query.matches_expr(&eval).then(|| ())
+    }
+
     #[inline]
     fn by_quot_marks_jp(&self, w: &Word, q_term: &str) -> Option<()> {
         if q_term.is_kana() {
@@ -112,3 +165,47 @@ impl WordFilter {
         Some(())
     }
 }
+
+/// Returns `true` if `word` has the pitch accent of the given pitch tag. `None` if the tag isn't a
+/// pitch tag
+fn matches_pitch(word: &Word, tag: &Tag) -> Option<bool> {
+    Some(match tag {
+        Tag::PitchPattern(pattern) => word.has_pitch_pattern(*pattern),
+        Tag::PitchDrop(drop) => word.has_pitch_drop(*drop),
+        _ => return None,
+    })
+}
+
+/// Evaluates query expressions for a single word
+struct WordExprEval<'a> {
+    filter: &'a WordFilter,
+    word: &'a Word,
+}
+
+impl<'a> ExprEval for WordExprEval<'a> {
+    fn eval_tag(&self, tag: &Tag) -> Option<bool> {
+        let w = self.word;
+        Some(match tag {
+            Tag::PartOfSpeech(pos) => w.has_pos(&[*pos]),
+            Tag::Misc(misc) => w.has_misc(misc),
+            Tag::Jlpt(jlpt) => w.get_jlpt_lvl() == Some(*jlpt),
+            Tag::Katakana => w.get_reading_str().is_katakana(),
+            Tag::PitchPattern(..) | Tag::PitchDrop(..) => return matches_pitch(w, tag),
+            Tag::IrregularIruEru => resources::get()
+                .words()
+                .irregular_ichidan()
+                .any(|i| i.sequence == w.sequence),
+            _ => return None,
+        })
+    }
+
+    fn eval_term(&self, term: &str) -> bool {
+        if term.is_japanese() {
+            return self.filter.by_quot_marks_jp(self.word, term).is_some();
+        }
+
+        self.word
+            .gloss_iter_by_lang(self.filter.query.lang_param())
+            .any(|i| i.to_lowercase().contains(term))
+    }
+}
@@ -13,7 +13,10 @@ use types::jotoba::words::Word;
 use filter::WordFilter;
 use producer::{
     foreign::{romaji::RomajiProducer, ForeignProducer},
-    japanese::{number::NumberProducer, sentence_reader::SReaderProducer, NativeProducer},
+    japanese::{
+        deinflect::DeinflectProducer, number::NumberProducer, sentence_reader::SReaderProducer,
+        NativeProducer,
+    },
     k_reading::KReadingProducer,
     regex::RegexProducer,
     sequence::SeqProducer,
@@ -29,12 +32,19 @@ pub struct Search<'a> {
 
 impl<'a> Search<'a> {
     pub fn new(query: &'a Query) -> Self {
+        // Fall back to the rule based deinflector if no UniDic dictionary is loaded
+        let inflections: Box<dyn Producer<Target = Self>> = if sentence_reader::is_loaded() {
+            Box::new(SReaderProducer::new(query))
+        } else {
+            Box::new(DeinflectProducer::new(query))
+        };
+
         let producer: Vec<Box<dyn Producer<Target = Self>>> = vec![
             Box::new(KReadingProducer::new(query)),
             Box::new(TagProducer::new(query)),
             Box::new(SeqProducer::new(query)),
             Box::new(RegexProducer::new(query)),
-            Box::new(SReaderProducer::new(query)),
+            inflections,
             Box::new(NativeProducer::new(query)),
             Box::new(ForeignProducer::new(query)),
             Box::new(RomajiProducer::new(query)),
@@ -95,3 +105,36 @@ impl<'a> Searchable for Search<'a> {
 fn max_top_dist_filter(query: &Query) -> bool {
     !query.is_regex() && query.form.is_normal()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{executor::group_len, query::QueryLang};
+
+    #[test]
+    fn test_text_producers_concurrent() {
+        let query = Query {
+            query_str: "house".to_string(),
+            q_lang: QueryLang::Foreign,
+            ..Default::default()
+        };
+        let search = Search::new(&query);
+        let producers = search.get_producer();
+
+        // Splits the producers into the groups the executor runs concurrently
+        let mut groups: Vec<Vec<String>> = vec![];
+        let mut pos = 0;
+        while pos < producers.len() {
+            let len = group_len(&producers[pos..]);
+            groups.push(producers[pos..pos + len].iter().map(|i| i.name()).collect());
+            pos += len;
+        }
+
+        let group = groups
+            .iter()
+            .find(|i| i.contains(&"ForeignProducer".to_string()))
+            .unwrap();
+        assert!(group.contains(&"RomajiProducer".to_string()));
+        assert!(group.contains(&"NativeProducer".to_string()));
+    }
+}
@@ -1,5 +1,6 @@
 use super::REMOVE_PARENTHESES;
-use engine::relevance::{data::SortData, RelevanceEngine};
+use crate::ranking::WordWeights;
+use engine::relevance::{data::SortData, explain, RelevanceEngine};
 use indexes::ng_freq::{term_dist, NgFreqIndex};
 use sparse_vec::{SpVec32, VecExt};
 use types::jotoba::{
@@ -13,6 +14,8 @@ pub struct ForeignOrder {
     query_vec_en: Option<SpVec32>,
 
     lang: Language,
+
+    weights: WordWeights,
 }
 
 impl ForeignOrder {
@@ -22,9 +25,51 @@ impl ForeignOrder {
             query_vec_lang: SpVec32::default(),
             query_vec_en: None,
             lang: Language::English,
+            weights: WordWeights::default(),
         }
     }
 
+    /// Set the weights of the ranking profile to use
+    pub fn with_weights(mut self, weights: WordWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Sets the query for scoring words with [`ForeignOrder::score_unindexed`]. Indexed words get
+    /// the query passed by the search engine instead
+    pub fn with_query(mut self, query: &str, lang: Language) -> Self {
+        self.set_query(query, lang);
+        self
+    }
+
+    /// Scores a word which isn't part of the index, like words of custom dictionaries. Since
+    /// there is no gloss vector for such words, the gloss similarity is approximated by the text
+    /// similarity
+    pub fn score_unindexed(&self, word: &Word, lang: Language) -> f32 {
+        let text_sim = self.text_sim(word, lang);
+
+        let mut rel_add = 0.0;
+        if text_sim >= self.weights.foreign_text_sim_threshold {
+            explain::index_relevance(text_sim);
+            rel_add += text_sim * self.weights.foreign_gloss_weight;
+        }
+
+        let score = (rel_add + text_sim) / 2.0;
+        explain::replace("averaged with text similarity", score);
+        score
+    }
+
+    fn set_query(&mut self, query: &str, lang: Language) {
+        let query = query.to_lowercase();
+        self.query_vec_lang = build_vec(get_ng_index(lang), &query);
+
+        if lang != Language::English {
+            self.query_vec_en = Some(build_vec(get_ng_index(Language::English), &query));
+        }
+
+        self.lang = lang;
+    }
+
     #[inline]
     fn get_query_vec(&self, lang: Language) -> &SpVec32 {
         if lang == self.lang {
@@ -82,26 +127,21 @@ impl RelevanceEngine for ForeignOrder {
         let text_sim = self.text_sim(word, lang);
 
         let mut rel_add = 0.0;
-        if text_sim >= 0.5 {
+        if text_sim >= self.weights.foreign_text_sim_threshold {
             let index_item = item.index_item().vec();
             let gloss_sim = item.query().scalar(index_item);
-            rel_add += gloss_sim * 100.0;
+            explain::index_relevance(gloss_sim);
+            rel_add += gloss_sim * self.weights.foreign_gloss_weight;
         }
 
-        (rel_add + text_sim) / 2.0
+        let score = (rel_add + text_sim) / 2.0;
+        explain::replace("averaged with text similarity", score);
+        score
     }
 
     fn init(&mut self, init: engine::relevance::RelEngineInit) {
         let lang = init.language.unwrap();
-
-        let query = init.query.to_lowercase();
-        self.query_vec_lang = build_vec(get_ng_index(lang), &query);
-
-        if lang != Language::English {
-            self.query_vec_en = Some(build_vec(get_ng_index(Language::English), &query));
-        }
-
-        self.lang = lang;
+        self.set_query(&init.query, lang);
     }
 }
 
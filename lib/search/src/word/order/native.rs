@@ -1,4 +1,5 @@
-use engine::relevance::{data::SortData, RelevanceEngine};
+use crate::ranking::WordWeights;
+use engine::relevance::{data::SortData, explain, RelevanceEngine};
 use indexes::ng_freq::{term_dist, NgFreqIndex};
 use japanese::ToKanaExt;
 use jp_utils::JapaneseExt;
@@ -16,6 +17,8 @@ pub struct NativeOrder {
     w_index: Option<usize>,
 
     query_vec: SpVec32,
+
+    weights: WordWeights,
 }
 
 impl NativeOrder {
@@ -27,9 +30,16 @@ impl NativeOrder {
             w_index: None,
             query_vec: SpVec32::empty(),
             query_hw: String::new(),
+            weights: WordWeights::default(),
         }
     }
 
+    /// Set the weights of the ranking profile to use
+    pub fn with_weights(mut self, weights: WordWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
     /// Set a custom sentence reader word index
     pub fn with_w_index(mut self, index: usize) -> Self {
         self.w_index = Some(index);
@@ -41,6 +51,53 @@ impl NativeOrder {
         self
     }
 
+    /// Sets the query for scoring words with [`NativeOrder::score_unindexed`]. Indexed words get
+    /// the query passed by the search engine instead
+    pub fn with_query(mut self, query: &str) -> Self {
+        self.query_vec = build_ng_vec(&query.to_halfwidth().to_hiragana());
+        self.query_hw = query.to_halfwidth().to_hiragana();
+        self
+    }
+
+    /// Scores a word which isn't part of the index, like words of custom dictionaries. Uses the
+    /// text similarity in place of the index relevance and applies the same weights as for
+    /// indexed words
+    pub fn score_unindexed(&self, word: &Word) -> f32 {
+        let exact = word
+            .reading_iter(true)
+            .any(|i| i.reading.to_halfwidth().to_hiragana() == self.query_hw);
+        let text_sim = if exact { 1.0 } else { self.text_sim(word) };
+        explain::index_relevance(text_sim);
+        let score = self.apply_reading_weight(word, text_sim);
+        self.apply_word_weights(word, score)
+    }
+
+    /// Applies the weight for words whose reading isn't exactly the original query
+    fn apply_reading_weight(&self, word: &Word, mut score: f32) -> f32 {
+        let kana = word.reading.kana.reading.to_halfwidth().to_hiragana();
+        if kana != self.orig_query && word.get_reading().reading.to_halfwidth() != self.orig_query {
+            score *= self.weights.reading_mismatch;
+            explain::factor("reading mismatch", self.weights.reading_mismatch);
+        }
+        score
+    }
+
+    /// Applies the weights which only depend on the word itself
+    fn apply_word_weights(&self, word: &Word, mut score: f32) -> f32 {
+        if word.jlpt_lvl.is_none() {
+            score *= self.weights.no_jlpt;
+            explain::factor("no jlpt level", self.weights.no_jlpt);
+        }
+
+        // Is common
+        if !word.is_common() {
+            score *= self.weights.not_common;
+            explain::factor("not common", self.weights.not_common);
+        }
+
+        score
+    }
+
     #[inline]
     fn exceeded_threshold<'i, 'q, A, B, C>(item: &SortData<'i, 'q, A, B, C>, score: f32) -> bool {
         item.threshold().map(|th| score < th).unwrap_or(false)
@@ -72,21 +129,27 @@ impl RelevanceEngine for NativeOrder {
     ) -> f32 {
         let word = item.item();
         let mut score = item.index_item().dice(item.query());
+        explain::index_relevance(score);
 
         // If alternative reading matches query exactly
         if Self::exceeded_threshold(item, score) {
             return 0.0;
         }
 
-        score *= self.text_sim(word);
+        let text_sim = self.text_sim(word);
+        score *= text_sim;
+        explain::factor("text similarity", text_sim);
 
         if let Some(ref o_ts) = self.orig_query_ts {
             if self.w_index.unwrap_or(0) == 0 {
                 let new = item.index_item().dice(o_ts);
                 if new > score {
                     score = new;
+                    explain::replace("original query dice", new);
                 } else {
-                    score *= 0.7;
+                    let factor = self.weights.original_query_mismatch;
+                    score *= factor;
+                    explain::factor("original query mismatch", factor);
                 }
             }
         }
@@ -107,22 +170,13 @@ impl RelevanceEngine for NativeOrder {
             return 0.0;
         }
 
-        if kana != self.orig_query && word.get_reading().reading.to_halfwidth() != self.orig_query {
-            score *= 0.7;
-        }
+        let score = self.apply_reading_weight(word, score);
 
         if Self::exceeded_threshold(item, score) {
             return 0.0;
         }
 
-        if word.jlpt_lvl.is_none() {
-            score *= 0.999;
-        }
-
-        // Is common
-        if !word.is_common() {
-            score *= 0.999;
-        }
+        let score = self.apply_word_weights(word, score);
 
         //let reading_len = utils::real_string_len(&reading);
         /* if reading_len == 1 && reading.is_kanji() {
@@ -0,0 +1,71 @@
+use crate::{
+    query::Query,
+    word::order::{foreign::ForeignOrder, native::NativeOrder},
+};
+use types::jotoba::{language::Language, words::Word};
+
+/// Returns all words of custom dictionaries with a reading equal to or starting with one of the
+/// query's text alternatives together with their relevance
+pub(crate) fn native(query: &Query) -> Vec<(&'static Word, f32)> {
+    let index = match indexes::custom::get() {
+        Some(index) => index,
+        None => return vec![],
+    };
+
+    let weights = query.ranking().words;
+    let words = resources::get().words();
+    let mut out = vec![];
+    for q_str in query.text_alternatives() {
+        let order = NativeOrder::new(query.raw_query.clone())
+            .with_weights(weights)
+            .with_query(&q_str);
+
+        let found = index
+            .find_native(&q_str)
+            .into_iter()
+            .filter_map(|(seq, rel)| Some((words.by_sequence(seq)?, rel)))
+            .map(|(word, rel)| {
+                let score = order.score_unindexed(word);
+                (word, score * rel * weights.custom_boost)
+            });
+        out.extend(found);
+    }
+    out
+}
+
+/// Returns all words of custom dictionaries with a gloss matching one of the query's text
+/// alternatives together with their relevance
+pub(crate) fn foreign(query: &Query) -> Vec<(&'static Word, f32)> {
+    let index = match indexes::custom::get() {
+        Some(index) => index,
+        None => return vec![],
+    };
+
+    let lang = query.get_search_lang();
+    let mut languages = vec![lang];
+    if lang != Language::English && query.show_english() {
+        languages.push(Language::English);
+    }
+
+    let weights = query.ranking().words;
+    let words = resources::get().words();
+    let mut out = vec![];
+    for q_str in query.text_alternatives() {
+        let order = ForeignOrder::new()
+            .with_weights(weights)
+            .with_query(&q_str, lang);
+
+        for language in languages.iter() {
+            let found = index
+                .find_foreign(&q_str, *language)
+                .into_iter()
+                .filter_map(|(seq, rel)| Some((words.by_sequence(seq)?, rel)))
+                .map(|(word, rel)| {
+                    let score = order.score_unindexed(word, *language);
+                    (word, score * rel * weights.custom_boost)
+                });
+            out.extend(found);
+        }
+    }
+    out
+}
@@ -2,11 +2,20 @@ pub mod romaji;
 pub mod task;
 
 use crate::{
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    engine::words::foreign::Engine,
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     query::{Query, QueryLang},
     word::Search,
 };
-use engine::pushable::FilteredMaxCounter;
+use engine::{
+    pushable::{FilteredMaxCounter, Pushable},
+    relevance::item::RelItem,
+    task::SearchTask,
+};
 use task::ForeignSearch;
 use types::jotoba::language::Language;
 
@@ -19,6 +28,23 @@ impl<'a> ForeignProducer<'a> {
     pub fn new(query: &'a Query) -> Self {
         Self { query }
     }
+
+    /// Returns a task for each text alternative of the query and language
+    fn tasks(&self) -> Vec<SearchTask<'static, Engine>> {
+        let lang = self.query.get_search_lang();
+        let mut tasks = vec![];
+
+        for q_str in self.query.text_alternatives() {
+            tasks.push(ForeignSearch::new(self.query, &q_str, lang).task());
+
+            // Add english results
+            if lang != Language::English && self.query.show_english() {
+                tasks.push(ForeignSearch::new(self.query, &q_str, Language::English).task());
+            }
+        }
+
+        tasks
+    }
 }
 
 impl<'a> Producer for ForeignProducer<'a> {
@@ -31,41 +57,30 @@ impl<'a> Producer for ForeignProducer<'a> {
             <Self::Target as Searchable>::ResAdd,
         >,
     ) {
-        // convert WordOutput -> Word
-        //let mut p_mod = PushMod::new(out, |i: RelItem<WordOutput>| i.map_item(|i| i.word));
-
-        let q_str = &self.query.query_str;
-        let lang = self.query.get_search_lang();
-
-        ForeignSearch::new(self.query, q_str, lang)
-            .task()
-            .find_to(out);
+        for mut task in self.tasks() {
+            task.find_to(out);
+        }
 
-        // Add english results
-        if lang != Language::English && self.query.show_english() {
-            ForeignSearch::new(self.query, q_str, Language::English)
-                .task()
-                .find_to(out);
+        for (word, rel) in super::custom::foreign(self.query) {
+            out.push(RelItem::new(word, rel));
         }
     }
 
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
-        let q_str = &self.query.query_str;
-        let lang = self.query.get_search_lang();
-
-        ForeignSearch::new(self.query, q_str, lang)
-            .task()
-            .estimate_to(out);
+        for mut task in self.tasks() {
+            task.estimate_to(out);
+        }
 
-        // Add english results
-        if lang != Language::English && self.query.show_english() {
-            ForeignSearch::new(self.query, q_str, Language::English)
-                .task()
-                .estimate_to(out);
+        for (word, _) in super::custom::foreign(self.query) {
+            out.push(word);
         }
     }
 
     fn should_run(&self, _already_found: usize) -> bool {
         self.query.q_lang == QueryLang::Foreign && !self.query.query_str.is_empty()
     }
+
+    fn dependency(&self) -> Dependency {
+        Dependency::None
+    }
 }
@@ -2,7 +2,11 @@ use japanese::guessing::could_be_romaji;
 
 use crate::{
     engine::words::native::Engine,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     query::{Query, QueryLang},
     word::{producer::japanese::task::NativeSearch, Search},
 };
@@ -63,4 +67,8 @@ impl<'a> Producer for RomajiProducer<'a> {
             && self.query.q_lang == QueryLang::Foreign
             && could_be_romaji(&self.query.query_str)
     }
+
+    fn dependency(&self) -> Dependency {
+        Dependency::Count
+    }
 }
@@ -0,0 +1,93 @@
+use engine::{pushable::FilteredMaxCounter, task::SearchTask};
+use japanese::deinflect::{self, Deinflection};
+
+use crate::{
+    engine::words::native::Engine,
+    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    query::{Query, QueryLang},
+    word::{filter::WordFilter, order::native::NativeOrder, result::InflectionInformation, Search},
+};
+
+/// Maximum amount of deinflection candidates to search for
+const MAX_CANDIDATES: usize = 20;
+
+/// Producer for inflected words using the rule based deinflector. Used instead of the sentence
+/// reader if no UniDic dictionary is loaded
+pub struct DeinflectProducer<'a> {
+    query: &'a Query,
+    candidates: Vec<Deinflection>,
+}
+
+impl<'a> DeinflectProducer<'a> {
+    pub fn new(query: &'a Query) -> Self {
+        let candidates = if Self::is_applicable(query) {
+            deinflect::deinflect(&query.query_str)
+                .into_iter()
+                .take(MAX_CANDIDATES)
+                .collect()
+        } else {
+            vec![]
+        };
+
+        Self { query, candidates }
+    }
+
+    #[inline]
+    fn is_applicable(query: &Query) -> bool {
+        query.q_lang == QueryLang::Japanese && query.form.is_normal() && !query.query_str.is_empty()
+    }
+
+    /// Search task for words being the dictionary form of `deinflection`
+    fn task(&self, deinflection: &Deinflection) -> SearchTask<'static, Engine> {
+        let filter = WordFilter::new(self.query.clone());
+        let term = deinflection
+            .suru_noun()
+            .unwrap_or(&deinflection.term)
+            .to_string();
+        let d = deinflection.clone();
+
+        let order = NativeOrder::new(term.clone()).with_weights(self.query.ranking().words);
+        SearchTask::new(&term)
+            .with_custom_order(order)
+            .with_result_filter(move |word| {
+                !filter.filter_word(*word)
+                    && word.has_reading(&term)
+                    && word.matches_deinflection(&d)
+            })
+    }
+}
+
+impl<'a> Producer for DeinflectProducer<'a> {
+    type Target = Search<'a>;
+
+    fn produce(
+        &self,
+        out: &mut OutputBuilder<
+            <Self::Target as Searchable>::Item,
+            <Self::Target as Searchable>::ResAdd,
+        >,
+    ) {
+        for deinflection in self.candidates.iter() {
+            let before = out.len();
+            self.task(deinflection).find_to(out);
+
+            // Show the inflections of the first matching dictionary form
+            if out.len() > before && out.output_add.inflection.is_none() {
+                out.output_add.inflection = Some(InflectionInformation {
+                    lexeme: deinflection.term.clone(),
+                    inflections: deinflection.reasons.iter().map(|i| (*i).into()).collect(),
+                });
+            }
+        }
+    }
+
+    fn should_run(&self, _already_found: usize) -> bool {
+        !self.candidates.is_empty()
+    }
+
+    fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
+        for deinflection in self.candidates.iter() {
+            self.task(deinflection).estimate_to(out);
+        }
+    }
+}
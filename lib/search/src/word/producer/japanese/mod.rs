@@ -1,15 +1,24 @@
+pub mod deinflect;
 pub mod number;
 pub mod sentence_reader;
 pub mod task;
 
 use crate::{
     engine::words::native::Engine,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     query::{Query, QueryLang},
     word::Search,
 };
 
-use engine::{pushable::FilteredMaxCounter, task::SearchTask};
+use engine::{
+    pushable::{FilteredMaxCounter, Pushable},
+    relevance::item::RelItem,
+    task::SearchTask,
+};
 use task::NativeSearch;
 
 /// Produces search results for native search input
@@ -22,8 +31,13 @@ impl<'a> NativeProducer<'a> {
         Self { query }
     }
 
-    fn task(&self) -> SearchTask<'static, Engine> {
-        NativeSearch::new(self.query, &self.query.query_str).task()
+    /// Returns a task for each text alternative of the query
+    fn tasks(&self) -> Vec<SearchTask<'static, Engine>> {
+        self.query
+            .text_alternatives()
+            .iter()
+            .map(|q_str| NativeSearch::new(self.query, q_str).task())
+            .collect()
     }
 }
 
@@ -37,11 +51,23 @@ impl<'a> Producer for NativeProducer<'a> {
             <Self::Target as Searchable>::ResAdd,
         >,
     ) {
-        self.task().find_to(out);
+        for mut task in self.tasks() {
+            task.find_to(out);
+        }
+
+        for (word, rel) in super::custom::native(self.query) {
+            out.push(RelItem::new(word, rel));
+        }
     }
 
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
-        self.task().estimate_to(out)
+        for mut task in self.tasks() {
+            task.estimate_to(out);
+        }
+
+        for (word, _) in super::custom::native(self.query) {
+            out.push(word);
+        }
     }
 
     fn should_run(&self, already_found: usize) -> bool {
@@ -54,4 +80,8 @@ impl<'a> Producer for NativeProducer<'a> {
 
         already_found < 5
     }
+
+    fn dependency(&self) -> Dependency {
+        Dependency::Count
+    }
 }
@@ -46,7 +46,9 @@ impl<'a> SReaderProducer<'a> {
 
         let search = NativeSearch::new(self.query, &normalized);
         let o_query = search.original_query().to_string();
-        let order = NativeOrder::new(o_query).with_oquery_ts(original_query);
+        let order = NativeOrder::new(o_query)
+            .with_oquery_ts(original_query)
+            .with_weights(self.query.ranking().words);
         Some(search.task().with_custom_order(order))
     }
 
@@ -76,7 +78,9 @@ impl<'a> SReaderProducer<'a> {
 
         let search = NativeSearch::new(self.query, &normalized);
 
-        let order = NativeOrder::new(inflected).with_w_index(self.sentence_index());
+        let order = NativeOrder::new(inflected)
+            .with_w_index(self.sentence_index())
+            .with_weights(self.query.ranking().words);
 
         Some(search.task().with_custom_order(order))
     }
@@ -87,7 +91,9 @@ impl<'a> SReaderProducer<'a> {
         let inflected = word.get_inflected();
         let search = NativeSearch::new(self.query, &inflected);
         let o_query = search.original_query().to_string();
-        let order = NativeOrder::new(o_query).with_w_index(self.sentence_index());
+        let order = NativeOrder::new(o_query)
+            .with_w_index(self.sentence_index())
+            .with_weights(self.query.ranking().words);
         Some(search.task().with_custom_order(order))
     }
 }
@@ -188,11 +194,16 @@ fn word_exists(term: &str) -> bool {
 /// Generates furigana for a sentence
 fn set_furigana(s: &mut Sentence) {
     for part in s.iter_mut() {
-        let p = part.clone();
-        part.set_furigana(|inp| furigana_by_reading(inp, &p))
+        set_part_furigana(part);
     }
 }
 
+/// Generates furigana for a single word using the reading of its dictionary entry
+pub fn set_part_furigana(part: &mut Part) {
+    let p = part.clone();
+    part.set_furigana(|inp| furigana_by_reading(inp, &p))
+}
+
 /// Returns furigana of the given `morpheme` if available
 fn furigana_by_reading(morpheme: &str, part: &sentence_reader::Part) -> Option<String> {
     word_furi(morpheme, part).or_else(|| name_furi(morpheme))
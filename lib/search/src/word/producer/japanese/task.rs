@@ -49,9 +49,10 @@ impl<'a> NativeSearch<'a> {
     pub fn task(&self) -> SearchTask<'static, Engine> {
         let filter = WordFilter::new(self.query.clone());
         let original_query = self.original_query().to_string();
+        let order = NativeOrder::new(original_query).with_weights(self.query.ranking().words);
 
         SearchTask::new(self.query_str)
-            .with_custom_order(NativeOrder::new(original_query))
+            .with_custom_order(order)
             .with_result_filter(move |item| !filter.filter_word(*item))
             .with_threshold(self.threshold)
     }
@@ -8,7 +8,11 @@ use types::jotoba::{kanji::Kanji, words::Word};
 
 use crate::{
     engine::words::native::k_reading,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     query::Query,
     word::{order::kanji_reading::KanjiReadingRelevance, Search},
 };
@@ -72,6 +76,10 @@ impl<'a> Producer for KReadingProducer<'a> {
         self.query.form.is_kanji_reading()
     }
 
+    fn dependency(&self) -> Dependency {
+        Dependency::None
+    }
+
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
         let mut m = PushMod::new(out, |i: RelItem<&Word>| i.item);
         // TODO: use estimate_to here
@@ -1,3 +1,4 @@
+pub(crate) mod custom;
 pub mod foreign;
 pub mod japanese;
 pub mod k_reading;
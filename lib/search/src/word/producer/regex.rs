@@ -3,7 +3,11 @@ use types::jotoba::words::Word;
 
 use crate::{
     engine::words::native::regex,
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     query::{regex::RegexSQuery, Query},
     word::{order::regex::regex_order, Search},
 };
@@ -22,19 +26,16 @@ impl<'a> RegexProducer<'a> {
         Self { query }
     }
 
-    fn find_to_unsorted<P: Pushable<Item = RelItem<&'static Word>>>(
-        &self,
-        out: &mut P,
-    ) -> Option<()> {
-        let regex_query = self.query.as_regex_query()?;
-        search(&regex_query, |_, _| 0, out);
-        Some(())
+    fn find_to_unsorted<P: Pushable<Item = RelItem<&'static Word>>>(&self, out: &mut P) {
+        for regex_query in self.query.regex_queries() {
+            search(&regex_query, |_, _| 0, out);
+        }
     }
 
-    fn find_to<P: Pushable<Item = RelItem<&'static Word>>>(&self, out: &mut P) -> Option<()> {
-        let regex_query = self.query.as_regex_query()?;
-        search(&regex_query, |w, r| regex_order(w, r, &regex_query), out);
-        Some(())
+    fn find_to<P: Pushable<Item = RelItem<&'static Word>>>(&self, out: &mut P) {
+        for regex_query in self.query.regex_queries() {
+            search(&regex_query, |w, r| regex_order(w, r, &regex_query), out);
+        }
     }
 }
 
@@ -52,7 +53,11 @@ impl<'a> Producer for RegexProducer<'a> {
     }
 
     fn should_run(&self, _already_found: usize) -> bool {
-        self.query.as_regex_query().is_some()
+        !self.query.regex_queries().is_empty()
+    }
+
+    fn dependency(&self) -> Dependency {
+        Dependency::None
     }
 
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
@@ -69,9 +74,17 @@ where
     let word_resources = resources::get().words();
 
     let index = indexes::get().word().regex();
-    let possible_results = regex::find_words(index, &query.get_chars());
+    let possible_results = regex::find_words(index, query, out.deadline());
+    // Marks the search as incomplete if the index lookup stopped because of the deadline
+    if out.timed_out() {
+        return;
+    }
 
     for seq_id in possible_results.into_iter().sorted() {
+        if out.timed_out() {
+            break;
+        }
+
         let word = word_resources.by_sequence(seq_id).unwrap();
 
         let item_iter = word
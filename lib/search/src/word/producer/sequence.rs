@@ -1,5 +1,9 @@
 use crate::{
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     query::Query,
     word::Search,
 };
@@ -42,6 +46,10 @@ impl<'a> Producer for SeqProducer<'a> {
         self.query.form.is_sequence()
     }
 
+    fn dependency(&self) -> Dependency {
+        Dependency::None
+    }
+
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
         if let Some(word) = self.word() {
             out.push(word);
@@ -1,7 +1,11 @@
 use types::jotoba::words::Word;
 
 use crate::{
-    executor::{out_builder::OutputBuilder, producer::Producer, searchable::Searchable},
+    executor::{
+        out_builder::OutputBuilder,
+        producer::{Dependency, Producer},
+        searchable::Searchable,
+    },
     query::{Query, Tag},
     word::Search,
 };
@@ -20,10 +24,18 @@ impl<'a> TagProducer<'a> {
         Self { query }
     }
 
-    fn get_producer_tag(&self) -> Option<&Tag> {
+    /// Returns the producer tag of each branch of the query
+    fn get_producer_tags(&self) -> Vec<Tag> {
         self.query
-            .tags
+            .branches()
             .iter()
+            .filter_map(|i| self.get_producer_tag(&i.tags))
+            .copied()
+            .collect()
+    }
+
+    fn get_producer_tag<'t>(&self, tags: &'t [Tag]) -> Option<&'t Tag> {
+        tags.iter()
             .filter(|i| i.is_producer() && !i.is_sentence_tag())
             // Use tag with fewest items that it'll produce to reduce the amount of items that have to be filtered
             .map(|i| (self.tag_len(i).unwrap_or(usize::MAX), i))
@@ -35,9 +47,10 @@ impl<'a> TagProducer<'a> {
     where
         P: Pushable<Item = RelItem<&'static Word>>,
     {
-        // Find first producer tag. All other tags are treated as filter
-        let producer_tag = self.get_producer_tag().unwrap();
-        self.find_words(out, producer_tag);
+        // Find first producer tag of each branch. All other tags are treated as filter
+        for producer_tag in self.get_producer_tags() {
+            self.find_words(out, &producer_tag);
+        }
     }
 
     fn find_words<P>(&self, out: &mut P, tag: &Tag)
@@ -51,6 +64,8 @@ impl<'a> TagProducer<'a> {
             Tag::Jlpt(jlpt) => self.push_iter(words.by_jlpt(*jlpt), out),
             Tag::Katakana => self.push_iter(words.katakana(), out),
             Tag::IrregularIruEru => self.push_iter(words.irregular_ichidan(), out),
+            Tag::PitchPattern(p) => self.push_iter(words.by_pitch_pattern(*p), out),
+            Tag::PitchDrop(drop) => self.push_iter(words.by_pitch_drop(*drop), out),
             _ => (),
         }
     }
@@ -82,6 +97,8 @@ impl<'a> TagProducer<'a> {
             Tag::Jlpt(j) => w_retr.jlpt_len(*j),
             Tag::IrregularIruEru => Some(w_retr.irregular_ichidan_len()),
             Tag::Katakana => Some(w_retr.katakana_len()),
+            Tag::PitchPattern(p) => Some(w_retr.pitch_pattern_len(*p)),
+            Tag::PitchDrop(drop) => Some(w_retr.pitch_drop_len(*drop)),
             _ => None,
         }
     }
@@ -102,7 +119,11 @@ impl<'a> Producer for TagProducer<'a> {
 
     fn should_run(&self, _already_found: usize) -> bool {
         // Only run this producer if there is no query (except tags) and there are tags which can produce output
-        self.query.query_str.is_empty() && self.get_producer_tag().is_some()
+        self.query.query_str.is_empty() && !self.get_producer_tags().is_empty()
+    }
+
+    fn dependency(&self) -> Dependency {
+        Dependency::None
     }
 
     fn estimate_to(&self, out: &mut FilteredMaxCounter<<Self::Target as Searchable>::Item>) {
@@ -1,11 +1,141 @@
-use crate::grammar::{rule::Rule, rule_set::RuleSet, Analyzer};
-use once_cell::sync::Lazy;
+use crate::{
+    grammar::{rule::Rule, rule_set::RuleSet, Analyzer},
+    sentence::inflection,
+};
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use std::{collections::HashMap, error::Error, fs, path::Path};
+use types::jotoba::words::inflection::Inflection;
 
-static RULES: Lazy<Analyzer> = Lazy::new(|| Analyzer::new(get_rules()));
+static GRAMMAR: OnceCell<Grammar> = OnceCell::new();
+
+/// Returns the loaded grammar or the built-in one if no grammar file was loaded
+#[inline]
+pub(crate) fn grammar() -> &'static Grammar {
+    GRAMMAR.get_or_init(Grammar::default)
+}
 
 /// Returns a grammar analyzer with a japanese inflection ruleset
+#[inline]
 pub(crate) fn get_grammar_analyzer() -> &'static Analyzer {
-    &RULES
+    &grammar().words
+}
+
+/// Loads a grammar file and extends the built-in grammar with it. Has to be called before the
+/// first sentence gets parsed, otherwise the built-in grammar is used
+pub fn load_grammar<P: AsRef<Path>>(path: P) -> Result<(), Box<dyn Error>> {
+    let grammar = Grammar::from_file(path)?;
+    GRAMMAR
+        .set(grammar)
+        .map_err(|_| "Grammar already initialized".into())
+}
+
+/// Rules used to join morphemes to words and to map their inflections
+pub(crate) struct Grammar {
+    /// Rules for joining morphemes to words
+    pub(crate) words: Analyzer,
+    /// Rules for joining the inflection morphemes of a single word
+    pub(crate) inflections: Analyzer,
+    /// Rule names of morpheme lexemes which aren't known to the built-in mapping
+    morphemes: HashMap<String, String>,
+    /// Lexeme chains mapped to the inflection they represent
+    chains: HashMap<Vec<String>, Inflection>,
+}
+
+/// Grammar file extending the built-in grammar. Rules with a name of an existing rule add their
+/// destination rules to it. Supported formats are JSON and TOML.
+///
+/// ```json
+/// {
+///   "rules": [{ "name": "V", "rhs": ["ざる"] }, { "name": "ざる", "rhs": ["を"] }],
+///   "inflection_rules": [{ "name": "ざる", "rhs": ["を"] }],
+///   "morphemes": { "ず": "ざる" },
+///   "inflections": [{ "chain": ["ず", "を", "得る", "ない"], "inflection": "ZaruWoEnai" }]
+/// }
+/// ```
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct GrammarFile {
+    rules: Vec<Rule>,
+    inflection_rules: Vec<Rule>,
+    morphemes: HashMap<String, String>,
+    inflections: Vec<ChainMapping>,
+}
+
+#[derive(Deserialize)]
+struct ChainMapping {
+    chain: Vec<String>,
+    inflection: Inflection,
+}
+
+impl Grammar {
+    /// Loads a grammar file and merges it into the built-in grammar
+    fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+
+        let file: GrammarFile = match path.extension().and_then(|i| i.to_str()) {
+            Some("toml") => toml::from_str(&content)?,
+            _ => serde_json::from_str(&content)?,
+        };
+
+        Self::from_grammar_file(file)
+    }
+
+    fn from_grammar_file(file: GrammarFile) -> Result<Self, Box<dyn Error>> {
+        let mut words = get_rules();
+        let mut inflections = inflection::get_rules();
+
+        for rule in file.rules {
+            words.merge(rule);
+        }
+        for rule in file.inflection_rules {
+            inflections.merge(rule);
+        }
+
+        if !words.check() || !inflections.check() {
+            return Err("Grammar references undefined rules".into());
+        }
+
+        let mut chains = inflection::default_chains();
+        for mapping in file.inflections {
+            if mapping.chain.is_empty() {
+                return Err("Empty inflection chain".into());
+            }
+            chains.insert(mapping.chain, mapping.inflection);
+        }
+
+        Ok(Self {
+            words: Analyzer::new(words),
+            inflections: Analyzer::new(inflections),
+            morphemes: file.morphemes,
+            chains,
+        })
+    }
+
+    /// Returns the rule name of a morpheme lexeme defined in the grammar file
+    #[inline]
+    pub(crate) fn morpheme_rule(&self, lexeme: &str) -> Option<&str> {
+        self.morphemes.get(lexeme).map(|i| i.as_str())
+    }
+
+    /// Returns the inflection represented by a chain of lexemes
+    #[inline]
+    pub(crate) fn chain_inflection(&self, lexemes: &[&str]) -> Option<Inflection> {
+        let chain: Vec<String> = lexemes.iter().map(|i| i.to_string()).collect();
+        self.chains.get(&chain).copied()
+    }
+}
+
+impl Default for Grammar {
+    fn default() -> Self {
+        Self {
+            words: Analyzer::new(get_rules()),
+            inflections: Analyzer::new(inflection::get_rules()),
+            morphemes: HashMap::new(),
+            chains: inflection::default_chains(),
+        }
+    }
 }
 
 /// Returns a set of rules for japanese text analyzing
@@ -47,7 +177,7 @@ fn get_rules() -> RuleSet {
     // い rule
     rules.push(Rule::new("た", end));
     rules.push(Rule::new("たり", end));
-    rules.push(Rule::new("ない", &["て", "た"]));
+    rules.push(Rule::new("ない", &["て", "た", "ば"]));
     rules.push(Rule::new("たい", &["て", "ない", "た"]));
 
     // じゃない
@@ -81,7 +211,18 @@ fn get_rules() -> RuleSet {
     rules.push(Rule::new("とく", ru_ending));
 
     // ば conditional
-    rules.push(Rule::new("ば", end));
+    rules.push(Rule::new("ば", &["なる"]));
+
+    // なければならない
+    rules.push(Rule::new("なる", &["ない"]));
+
+    // ざるを得ない
+    rules.push(Rule::new("ざる", &["を"]));
+    rules.push(Rule::new("を", &["得る"]));
+    rules.push(Rule::new("得る", &["ない", "ます"]));
+
+    // っぱなし
+    rules.push(Rule::new("っぱなし", end));
 
     // される causative
     rules.push(Rule::new("さ", &["せる", "れる"]));
@@ -124,6 +265,8 @@ fn get_rules() -> RuleSet {
             "さ",
             // ぬ
             "ん",
+            "ざる",
+            "っぱなし",
         ],
     ));
 
@@ -133,3 +276,102 @@ fn get_rules() -> RuleSet {
     // generate ruleset
     RuleSet::new(&rules)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_grammar_file() {
+        let file: GrammarFile = serde_json::from_str(
+            r#"{
+                "rules": [{ "name": "V", "rhs": ["ざる"] }, { "name": "ざる", "rhs": ["を"] },
+                    { "name": "を", "rhs": ["得る"] }, { "name": "得る", "rhs": ["ない"] }],
+                "morphemes": { "ず": "ざる" },
+                "inflections": [{ "chain": ["ず", "を", "得る", "ない"], "inflection": "ZaruWoEnai" }]
+            }"#,
+        )
+        .unwrap();
+        let grammar = Grammar::from_grammar_file(file).unwrap();
+
+        assert!(grammar
+            .words
+            .check_full(&["V", "ざる", "を", "得る", "ない"]));
+        // Built-in rules are kept
+        assert!(grammar.words.check_full(&["V", "たい", "ない"]));
+        assert_eq!(grammar.morpheme_rule("ず"), Some("ざる"));
+        assert_eq!(
+            grammar.chain_inflection(&["ず", "を", "得る", "ない"]),
+            Some(Inflection::ZaruWoEnai)
+        );
+        assert_eq!(
+            grammar.chain_inflection(&["て", "おく"]),
+            Some(Inflection::TeOku)
+        );
+    }
+
+    #[test]
+    fn test_zaru_wo_enai() {
+        let grammar = Grammar::default();
+        assert!(grammar
+            .words
+            .check_full(&["V", "ざる", "を", "得る", "ない"]));
+        assert!(grammar
+            .words
+            .check_full(&["V", "ざる", "を", "得る", "ます"]));
+        assert!(grammar
+            .inflections
+            .check_full(&["ざる", "を", "得る", "ない"]));
+        assert_eq!(
+            grammar.chain_inflection(&["ず", "を", "得る", "ない"]),
+            Some(Inflection::ZaruWoEnai)
+        );
+    }
+
+    #[test]
+    fn test_obligation() {
+        let grammar = Grammar::default();
+        assert!(grammar
+            .words
+            .check_full(&["V", "ない", "ば", "なる", "ない"]));
+        assert!(grammar
+            .words
+            .check_full(&["V", "ない", "ば", "なる", "ない", "た"]));
+        assert!(grammar
+            .inflections
+            .check_full(&["ない", "ば", "なる", "ない"]));
+        assert_eq!(
+            grammar.chain_inflection(&["ない", "ば", "なる", "ない"]),
+            Some(Inflection::Obligation)
+        );
+        // なければ alone is still a negative conditional
+        assert!(grammar.words.check_full(&["V", "ない", "ば"]));
+        assert_eq!(grammar.chain_inflection(&["ない", "ば"]), None);
+    }
+
+    #[test]
+    fn test_ppanashi() {
+        let grammar = Grammar::default();
+        assert!(grammar.words.check_full(&["V", "っぱなし"]));
+        assert_eq!(
+            grammar.chain_inflection(&["っ放し"]),
+            Some(Inflection::Ppanashi)
+        );
+    }
+
+    #[test]
+    fn test_builtin_rules_complete() {
+        let grammar = Grammar::default();
+        assert!(grammar.words.rules().check());
+        assert!(grammar.inflections.rules().check());
+    }
+
+    #[test]
+    fn test_undefined_rule() {
+        let file = GrammarFile {
+            rules: vec![Rule::new("V", &["undefined"])],
+            ..GrammarFile::default()
+        };
+        assert!(Grammar::from_grammar_file(file).is_err());
+    }
+}
@@ -1,35 +1,50 @@
 use super::rule_set::ALL_WILDCARD;
+use serde::{Deserialize, Serialize};
 
 /// Represents a single rule describing a possible production
 /// of a grammar
-#[derive(Clone, Copy)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Rule {
-    name: &'static str,
-    rhs: &'static [&'static str],
+    name: String,
+    #[serde(default)]
+    rhs: Vec<String>,
 }
 
 impl Rule {
     /// Creates a new rule
-    pub fn new(name: &'static str, rhs: &'static [&'static str]) -> Self {
-        Self { name, rhs }
+    pub fn new<S: Into<String>>(name: S, rhs: &[&str]) -> Self {
+        let rhs = rhs.iter().map(|i| i.to_string()).collect();
+        Self {
+            name: name.into(),
+            rhs,
+        }
     }
 
     /// Get the rule's name.
     #[inline]
-    pub fn name(&self) -> &'static str {
-        self.name
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
     /// Get the rule's destination rules
     #[inline]
-    pub fn rhs(&self) -> &'static [&'static str] {
-        self.rhs
+    pub fn rhs(&self) -> &[String] {
+        &self.rhs
     }
 
     /// Returns `true` if the rule has a dst rule with `name`
     #[inline]
     pub fn has_dst(&self, name: &str) -> bool {
-        self.rhs.iter().any(|i| *i == name || *i == ALL_WILDCARD)
+        self.rhs.iter().any(|i| i == name || i == ALL_WILDCARD)
+    }
+
+    /// Adds all destination rules of `other` which aren't already destinations of this rule
+    pub fn extend(&mut self, other: &Rule) {
+        for dst in other.rhs() {
+            if !self.rhs.contains(dst) {
+                self.rhs.push(dst.clone());
+            }
+        }
     }
 }
 
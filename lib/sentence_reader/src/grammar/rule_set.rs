@@ -5,7 +5,7 @@ pub const ALL_WILDCARD: &str = "*";
 
 #[derive(Clone)]
 pub struct RuleSet {
-    rules: HashMap<&'static str, Rule>,
+    rules: HashMap<String, Rule>,
 }
 
 impl RuleSet {
@@ -13,7 +13,7 @@ impl RuleSet {
     pub fn new(rules: &[Rule]) -> Self {
         let rules = rules
             .iter()
-            .map(|i| (i.name(), *i))
+            .map(|i| (i.name().to_string(), i.clone()))
             .collect::<HashMap<_, _>>();
         Self { rules }
     }
@@ -29,10 +29,25 @@ impl RuleSet {
             self.add_all_wildcard();
         }
 
-        self.rules.insert(rule.name(), rule);
+        self.rules.insert(rule.name().to_string(), rule);
         true
     }
 
+    /// Adds a Rule to the RuleSet. If a rule with the same name already exists, the destination
+    /// rules of `rule` get added to the existing one
+    pub fn merge(&mut self, rule: Rule) {
+        if rule.has_dst(ALL_WILDCARD) {
+            self.add_all_wildcard();
+        }
+
+        match self.rules.get_mut(rule.name()) {
+            Some(existing) => existing.extend(&rule),
+            None => {
+                self.rules.insert(rule.name().to_string(), rule);
+            }
+        }
+    }
+
     /// Returns `true` if ruleSet has a rule with `name`
     pub fn has_rule(&self, name: &str) -> bool {
         self.rules.contains_key(name)
@@ -43,10 +58,10 @@ impl RuleSet {
         // check that all used dst rules are reachable
         for (_, rule) in self.rules.iter() {
             for rhs in rule.rhs() {
-                if *rhs == ALL_WILDCARD {
+                if rhs == ALL_WILDCARD {
                     continue;
                 }
-                if !self.rules.contains_key(rhs) {
+                if !self.rules.contains_key(rhs.as_str()) {
                     return false;
                 }
             }
@@ -68,7 +83,7 @@ impl RuleSet {
 
         // add dummy rule that allows any production
         self.rules
-            .insert(ALL_WILDCARD, Rule::new(ALL_WILDCARD, &[]));
+            .insert(ALL_WILDCARD.to_string(), Rule::new(ALL_WILDCARD, &[]));
     }
 }
 
@@ -80,7 +95,7 @@ impl Debug for RuleSet {
                 if pos > 0 {
                     dst.push_str(" | ");
                 }
-                dst.push_str(*d);
+                dst.push_str(d);
             }
             if dst.is_empty() {
                 continue;
@@ -9,6 +9,7 @@ use once_cell::sync::{Lazy, OnceCell};
 use output::ParseResult;
 use sentence::SentenceAnalyzer;
 
+pub use analyzer::load_grammar;
 pub use igo_unidic;
 
 pub use output::Sentence;
@@ -61,4 +62,11 @@ impl<'input> Parser<'input> {
 
         ParseResult::Sentence(Sentence::new(sent_parse))
     }
+
+    /// Returns all words of the input. Other than `parse` this doesn't classify the input and
+    /// also returns single words without inflections
+    #[inline]
+    pub fn parts(&self) -> Vec<Part> {
+        self.sentence_analyzer.analyze::<Part>()
+    }
 }
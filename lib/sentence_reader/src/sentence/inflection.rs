@@ -1,8 +1,9 @@
 use super::FromMorphemes;
-use crate::grammar::{rule::Rule, rule_set::RuleSet, Analyzer};
+use crate::analyzer;
+use crate::grammar::{rule::Rule, rule_set::RuleSet};
 use crate::sentence::SentenceAnalyzer;
 use igo_unidic::Morpheme;
-use once_cell::sync::Lazy;
+use std::collections::HashMap;
 use types::jotoba::words::inflection::Inflection;
 
 /*
@@ -38,53 +39,98 @@ impl<'b> FromMorphemes<'static, 'b> for Inflection {
         let lexemes = parts.iter().map(|i| i.lexeme).collect::<Vec<_>>();
 
         if lexemes.is_empty() {
-            None
-        } else if lexemes.len() == 1 {
-            if parts[0].surface == "たら" {
-                return Some(Self::Tara);
-            }
-
-            Some(match lexemes[0] {
-                "ない" | "ぬ" => Inflection::Negative,
-                "ます" => Inflection::Polite,
-                "て" | "で" => Inflection::TeForm,
-                "だ" | "た" => Inflection::Past,
-                "れる" => Inflection::Passive,
-                "せる" | "させる" => Inflection::Causative,
-                "られる" => Inflection::PotentialOrPassive,
-                "たい" => Inflection::Tai,
-                "たり" | "だり" => Inflection::Tari,
-                "てる" | "でる" => Inflection::TeIru,
-                "とく" | "どく" => Inflection::Toku,
-                "ちゃう" | "じゃう" => Inflection::Chau,
-                "ば" => Inflection::Ba,
-                _ => return None,
-            })
-        } else {
-            Some(match lexemes.as_slice() {
-                &["て", "いる"] | &["で", "いる"] => Inflection::TeIru,
-                &["て", "ある"] | &["で", "ある"] => Inflection::TeAru,
-                &["て", "みる"] | &["で", "みる"] => Inflection::TeMiru,
-                &["て", "しまう"] | &["で", "しまう"] => Inflection::TeShimau,
-                &["て", "おく"] | &["で", "おく"] => Inflection::TeOku,
-                &["さ", "せる"] => Inflection::Causative,
-                // Fake する; The tokenizer tokenizes the さ of される as a form of する
-                &["する", "れる"] => Inflection::CausativePassive,
-                _ => return None,
-            })
+            return None;
         }
+
+        if lexemes.len() == 1 && parts[0].surface == "たら" {
+            return Some(Self::Tara);
+        }
+
+        analyzer::grammar().chain_inflection(&lexemes)
+    }
+}
+
+/// Inflections of a group of morphemes
+struct Inflections(Vec<Inflection>);
+
+impl<'b> FromMorphemes<'static, 'b> for Inflections {
+    fn from(parts: Vec<Morpheme<'static, 'b>>, pos: usize) -> Option<Self> {
+        if let Some(inflection) = <Inflection as FromMorphemes>::from(parts.clone(), pos) {
+            return Some(Self(vec![inflection]));
+        }
+
+        // Groups only matching the beginning of a longer chain (eg. the なければ of なければならない)
+        // are made of the inflections of their single morphemes
+        let inflections = parts
+            .into_iter()
+            .filter_map(|i| <Inflection as FromMorphemes>::from(vec![i], pos))
+            .collect::<Vec<_>>();
+        (!inflections.is_empty()).then(|| Self(inflections))
     }
 }
 
 pub(crate) fn parse_inflections(morph: &[Morpheme<'static, '_>]) -> Vec<Inflection> {
-    SentenceAnalyzer::new(&INFLECTION_RULES, morph.to_vec()).analyze::<Inflection>()
+    let grammar = &analyzer::grammar().inflections;
+    SentenceAnalyzer::new(grammar, morph.to_vec())
+        .analyze::<Inflections>()
+        .into_iter()
+        .flat_map(|i| i.0)
+        .collect()
 }
 
-static INFLECTION_RULES: Lazy<Analyzer> = Lazy::new(|| Analyzer::new(get_rules()));
+/// Returns the built-in mapping of lexeme chains to inflections
+pub(crate) fn default_chains() -> HashMap<Vec<String>, Inflection> {
+    let chains: &[(&[&str], Inflection)] = &[
+        (&["ない"], Inflection::Negative),
+        (&["ぬ"], Inflection::Negative),
+        (&["ます"], Inflection::Polite),
+        (&["て"], Inflection::TeForm),
+        (&["で"], Inflection::TeForm),
+        (&["だ"], Inflection::Past),
+        (&["た"], Inflection::Past),
+        (&["れる"], Inflection::Passive),
+        (&["せる"], Inflection::Causative),
+        (&["させる"], Inflection::Causative),
+        (&["られる"], Inflection::PotentialOrPassive),
+        (&["たい"], Inflection::Tai),
+        (&["たり"], Inflection::Tari),
+        (&["だり"], Inflection::Tari),
+        (&["てる"], Inflection::TeIru),
+        (&["でる"], Inflection::TeIru),
+        (&["とく"], Inflection::Toku),
+        (&["どく"], Inflection::Toku),
+        (&["ちゃう"], Inflection::Chau),
+        (&["じゃう"], Inflection::Chau),
+        (&["ば"], Inflection::Ba),
+        (&["て", "いる"], Inflection::TeIru),
+        (&["で", "いる"], Inflection::TeIru),
+        (&["て", "ある"], Inflection::TeAru),
+        (&["で", "ある"], Inflection::TeAru),
+        (&["て", "みる"], Inflection::TeMiru),
+        (&["で", "みる"], Inflection::TeMiru),
+        (&["て", "しまう"], Inflection::TeShimau),
+        (&["で", "しまう"], Inflection::TeShimau),
+        (&["て", "おく"], Inflection::TeOku),
+        (&["で", "おく"], Inflection::TeOku),
+        (&["さ", "せる"], Inflection::Causative),
+        // Fake する; The tokenizer tokenizes the さ of される as a form of する
+        (&["する", "れる"], Inflection::CausativePassive),
+        (&["ず", "を", "得る", "ない"], Inflection::ZaruWoEnai),
+        (&["ず", "を", "得る"], Inflection::ZaruWoEnai),
+        (&["ない", "ば", "なる", "ない"], Inflection::Obligation),
+        (&["っ放し"], Inflection::Ppanashi),
+        (&["放し"], Inflection::Ppanashi),
+    ];
+
+    chains
+        .iter()
+        .map(|(chain, inflection)| (chain.iter().map(|i| i.to_string()).collect(), *inflection))
+        .collect()
+}
 
 /// Returns a set of rules for japanese text analyzing
-fn get_rules() -> RuleSet {
-    let mut rules = Vec::with_capacity(7);
+pub(crate) fn get_rules() -> RuleSet {
+    let mut rules = Vec::with_capacity(14);
 
     rules.push(Rule::new("いる", &[]));
     rules.push(Rule::new("ある", &[]));
@@ -99,5 +145,15 @@ fn get_rules() -> RuleSet {
     ));
     rules.push(Rule::new("さ", &["れる"]));
 
+    // ざるを得ない
+    rules.push(Rule::new("ざる", &["を"]));
+    rules.push(Rule::new("を", &["得る"]));
+    rules.push(Rule::new("得る", &["ない"]));
+
+    // なければならない
+    rules.push(Rule::new("ない", &["ば"]));
+    rules.push(Rule::new("ば", &["なる"]));
+    rules.push(Rule::new("なる", &["ない"]));
+
     RuleSet::new(&rules)
 }
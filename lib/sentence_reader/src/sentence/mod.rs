@@ -112,6 +112,10 @@ impl<'input> SentenceAnalyzer<'input> {
 }
 
 pub(crate) fn map_morph_to_rule(pos: usize, morph: &Morpheme<'_, '_>) -> Option<&'static str> {
+    if let Some(rule) = crate::analyzer::grammar().morpheme_rule(morph.lexeme) {
+        return Some(rule);
+    }
+
     if morph.surface == "じゃ" {
         return Some("じゃ");
     }
@@ -180,6 +184,28 @@ pub(crate) fn map_morph_to_rule(pos: usize, morph: &Morpheme<'_, '_>) -> Option<
         return Some("ば");
     }
 
+    if morph.lexeme == "ず" && morph.surface == "ざる" {
+        return Some("ざる");
+    }
+
+    if morph.lexeme == "っ放し" || morph.lexeme == "放し" {
+        return Some("っぱなし");
+    }
+
+    // The following rules only continue a word (eg. ざるを得ない or なければならない). At pos == 0
+    // they are a particle or the verbs 得る and なる
+    if morph.lexeme == "を" && pos > 0 {
+        return Some("を");
+    }
+
+    if morph.lexeme == "得る" && pos > 0 {
+        return Some("得る");
+    }
+
+    if morph.lexeme == "なる" && pos > 0 {
+        return Some("なる");
+    }
+
     if morph.lexeme == "ぬ" {
         return Some("ん");
     }
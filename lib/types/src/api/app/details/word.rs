@@ -2,7 +2,7 @@ use serde::Serialize;
 
 use crate::{
     api::{app::search::responses::kanji::Kanji, app::search::responses::words::Word},
-    jotoba::words::inflection::Inflections,
+    jotoba::words::{conjugation::ConjugationTable, inflection::Inflections},
 };
 
 #[derive(Serialize)]
@@ -12,6 +12,8 @@ pub struct Details {
     kanji: Vec<Kanji>,
     #[serde(skip_serializing_if = "Option::is_none")]
     conjugations: Option<Inflections>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    conjugation_table: Option<ConjugationTable>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     collocations: Vec<Word>,
     has_sentence: bool,
@@ -32,6 +34,7 @@ impl Details {
         word: Word,
         kanji: Vec<Kanji>,
         conjugations: Option<Inflections>,
+        conjugation_table: Option<ConjugationTable>,
         collocations: Vec<Word>,
         has_sentence: bool,
         transitivity_pair: Option<TransitivityPair>,
@@ -40,6 +43,7 @@ impl Details {
             word,
             kanji,
             conjugations,
+            conjugation_table,
             collocations,
             has_sentence,
             transitivity_pair,
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct Request {
+    pub text: String,
+    /// Omit furigana of kanji with this JLPT level or an easier one
+    #[serde(default)]
+    pub known_jlpt: Option<u8>,
+    /// Omit furigana of kanji taught in this grade or an earlier one
+    #[serde(default)]
+    pub known_grade: Option<u8>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Response {
+    pub segments: Vec<Segment>,
+    /// The annotated text as ruby HTML
+    pub html: String,
+}
+
+impl Response {
+    #[inline]
+    pub fn new(segments: Vec<Segment>, html: String) -> Self {
+        Self { segments, html }
+    }
+}
+
+/// A part of the annotated text
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub text: String,
+    /// Kana reading of `text`. Only set for kanji which need furigana
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reading: Option<String>,
+}
+
+impl Segment {
+    #[inline]
+    pub fn new(text: String, reading: Option<String>) -> Self {
+        Self { text, reading }
+    }
+
+    #[inline]
+    pub fn plain(text: String) -> Self {
+        Self::new(text, None)
+    }
+}
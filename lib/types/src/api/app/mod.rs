@@ -1,5 +1,6 @@
 pub mod completions;
 pub mod details;
+pub mod furigana;
 pub mod image;
 pub mod kanji;
 pub mod news;
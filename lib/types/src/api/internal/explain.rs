@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Response of a word search explaining the score of each result
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Response {
+    pub words: Vec<ExplainedWord>,
+}
+
+/// A single word result together with the calculation of its relevance
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExplainedWord {
+    pub sequence: u32,
+    pub reading: String,
+    /// Producer which found the word
+    pub producer: String,
+    /// Final relevance used to order the results
+    pub relevance: f32,
+    /// Relevance of the word within the index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_relevance: Option<f32>,
+    /// Factors applied to the index relevance in the order they got applied
+    pub factors: Vec<Factor>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Factor {
+    pub name: String,
+    pub value: f32,
+    pub op: FactorOp,
+}
+
+/// How a factor got applied to the score
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FactorOp {
+    Multiply,
+    Replace,
+}
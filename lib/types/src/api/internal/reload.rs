@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Response {
+    pub status: ReloadStatus,
+}
+
+impl Response {
+    #[inline]
+    pub fn new(status: ReloadStatus) -> Self {
+        Self { status }
+    }
+}
+
+/// State of a data reload
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReloadStatus {
+    /// A new reload has been started in the background
+    Started,
+    /// Another reload is still running
+    Running,
+}
@@ -2,9 +2,13 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use super::PageInfo;
+
 #[derive(Serialize, Deserialize)]
 pub struct Response {
     pub kanji: Vec<Kanji>,
+    #[serde(flatten)]
+    pub page: PageInfo,
 }
 
 #[derive(Serialize, Deserialize)]
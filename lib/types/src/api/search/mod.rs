@@ -1,9 +1,10 @@
 pub mod kanji;
 pub mod name;
+pub mod pitch;
 pub mod sentence;
 pub mod word;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::jotoba::language::Language;
 
@@ -18,4 +19,61 @@ pub struct SearchRequest {
 
     #[serde(default)]
     pub no_english: bool,
+
+    /// Page to return, starting at 1. Pages above 100 (400 for kanji) are rejected
+    #[serde(default)]
+    pub page: Option<u32>,
+
+    #[serde(default)]
+    pub page_size: Option<u32>,
+
+    /// Tags applied to the query. The leading `#` is optional
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Name of the ranking profile to order the results with
+    #[serde(default)]
+    pub ranking_profile: Option<String>,
+}
+
+/// Pagination information of a search response
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PageInfo {
+    pub page: u32,
+    pub page_size: u32,
+    /// Amount of all results
+    pub total: usize,
+    /// `true` if there are results on following pages
+    pub has_more: bool,
+    /// `true` if the search ran out of time and only returned the results found so far
+    #[serde(default)]
+    pub incomplete: bool,
+}
+
+impl PageInfo {
+    /// Creates a new PageInfo for a 1 based `page`
+    pub fn new(page: u32, page_size: u32, total: usize) -> Self {
+        let page = page.max(1);
+        let has_more = (page as usize) * (page_size as usize) < total;
+        Self {
+            page,
+            page_size,
+            total,
+            has_more,
+            incomplete: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_page_info() {
+        assert!(PageInfo::new(1, 10, 11).has_more);
+        assert!(!PageInfo::new(2, 10, 11).has_more);
+        assert!(!PageInfo::new(0, 10, 10).has_more);
+        assert_eq!(PageInfo::new(0, 10, 10).page, 1);
+    }
 }
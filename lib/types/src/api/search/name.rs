@@ -1,10 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+use super::PageInfo;
+
 use crate::jotoba::names::name_type::NameType;
 
 #[derive(Serialize, Deserialize)]
 pub struct Response {
     names: Vec<Name>,
+    #[serde(flatten)]
+    page: PageInfo,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -33,6 +37,18 @@ impl From<Vec<&crate::jotoba::names::Name>> for Response {
     #[inline]
     fn from(name: Vec<&crate::jotoba::names::Name>) -> Self {
         let names: Vec<Name> = name.into_iter().map(Name::from).collect();
-        Self { names }
+        Self {
+            names,
+            page: PageInfo::default(),
+        }
+    }
+}
+
+impl Response {
+    /// Sets the pagination information of the response
+    #[inline]
+    pub fn with_page(mut self, page: PageInfo) -> Self {
+        self.page = page;
+        self
     }
 }
@@ -0,0 +1,41 @@
+use super::word::{Accent, Word};
+use crate::jotoba::language::Language;
+use serde::{Deserialize, Serialize};
+
+/// Payload of a pitch lookup
+#[derive(Deserialize)]
+pub struct Request {
+    pub kana: String,
+
+    #[serde(default)]
+    pub language: Language,
+
+    #[serde(default)]
+    pub no_english: bool,
+}
+
+/// All words written with the requested kana grouped by their pitch accent
+#[derive(Serialize, Deserialize, Default)]
+pub struct Response {
+    pub groups: Vec<PitchGroup>,
+    /// Words without known pitch accent
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unknown: Vec<Word>,
+}
+
+/// Homophones sharing the same pitch accent
+#[derive(Serialize, Deserialize)]
+pub struct PitchGroup {
+    pub accent: Accent,
+    pub words: Vec<Word>,
+}
+
+impl PitchGroup {
+    #[inline]
+    pub fn new(accent: Accent) -> Self {
+        Self {
+            accent,
+            words: vec![],
+        }
+    }
+}
@@ -1,10 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+use super::PageInfo;
+
 use crate::jotoba::language::Language;
 
 #[derive(Serialize, Deserialize)]
 pub struct Response {
     sentences: Vec<Sentence>,
+    #[serde(flatten)]
+    page: PageInfo,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -20,6 +24,18 @@ pub struct Sentence {
 impl From<Vec<Sentence>> for Response {
     #[inline]
     fn from(sentences: Vec<Sentence>) -> Self {
-        Self { sentences }
+        Self {
+            sentences,
+            page: PageInfo::default(),
+        }
+    }
+}
+
+impl Response {
+    /// Sets the pagination information of the response
+    #[inline]
+    pub fn with_page(mut self, page: PageInfo) -> Self {
+        self.page = page;
+        self
     }
 }
@@ -1,12 +1,15 @@
-use std::path::Path;
+use std::{collections::BTreeMap, path::Path};
 
 use crate::{
-    api::search::kanji::Kanji,
+    api::search::{kanji::Kanji, PageInfo},
     jotoba::{
         language::Language,
         words::{
-            dialect::Dialect, field::Field, misc::Misc, part_of_speech::PartOfSpeech,
-            pitch::PitchPart,
+            dialect::Dialect,
+            field::Field,
+            misc::Misc,
+            part_of_speech::PartOfSpeech,
+            pitch::{self, Pitch, PitchPart, PitchPattern},
         },
     },
 };
@@ -18,11 +21,24 @@ use serde::{Deserialize, Serialize};
 pub struct Response {
     kanji: Vec<Kanji>,
     words: Vec<Word>,
+    #[serde(flatten)]
+    page: PageInfo,
 }
 
 impl Response {
     pub fn new(words: Vec<Word>, kanji: Vec<Kanji>) -> Self {
-        Self { kanji, words }
+        Self {
+            kanji,
+            words,
+            page: PageInfo::default(),
+        }
+    }
+
+    /// Sets the pagination information of the response
+    #[inline]
+    pub fn with_page(mut self, page: PageInfo) -> Self {
+        self.page = page;
+        self
     }
 
     #[cfg(feature = "jotoba_intern")]
@@ -36,10 +52,70 @@ impl Response {
         let kanji = convert_kanji(wres.1, assets_path);
         let words = convert_words(wres.0);
 
-        Self { kanji, words }
+        Self::new(words, kanji)
+    }
+}
+
+/// Payload of a batch word search
+#[derive(Deserialize)]
+pub struct BatchRequest {
+    #[serde(default)]
+    pub queries: Vec<String>,
+
+    #[serde(default)]
+    pub sequences: Vec<u32>,
+
+    #[serde(default)]
+    pub language: Language,
+
+    #[serde(default)]
+    pub no_english: bool,
+}
+
+impl BatchRequest {
+    /// Returns the amount of lookups in the request
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.queries.len() + self.sequences.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
+/// Response of a batch word search. Results are keyed by their input
+#[derive(Serialize, Deserialize, Default)]
+pub struct BatchResponse {
+    pub queries: BTreeMap<String, BatchResult>,
+    pub sequences: BTreeMap<u32, BatchResult>,
+}
+
+/// Result of a single lookup in a batch word search
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchResult {
+    Words(Vec<Word>),
+    Error(String),
+}
+
+/// A single event of a streamed word search
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    /// New words found by a single producer
+    Results { producer: String, words: Vec<Word> },
+    /// Final result of the search. Replaces all previously streamed words
+    Summary {
+        total: usize,
+        words: Vec<Word>,
+        kanji: Vec<Kanji>,
+        /// `true` if the search ran out of time
+        incomplete: bool,
+    },
+}
+
 /// Represents a single Word result with 1 (main) Japanese reading and n glosses
 #[derive(Serialize, Deserialize)]
 pub struct Word {
@@ -52,6 +128,34 @@ pub struct Word {
     audio: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pitch: Option<Vec<PitchPart>>,
+    /// All pitch accents of the word
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    accents: Vec<Accent>,
+    /// `true` if the word comes from a custom dictionary
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    custom: bool,
+}
+
+/// A single pitch accent of a word
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Accent {
+    /// Amount of morae after which the pitch drops
+    pub drop: u8,
+    pub pattern: PitchPattern,
+    pub parts: Vec<PitchPart>,
+}
+
+impl Accent {
+    /// Creates the accent of `kana` with the pitch dropping after `drop` morae
+    pub fn new(kana: &str, drop: u8) -> Option<Self> {
+        let parts = Pitch::new(kana, drop)?.parts;
+        let pattern = PitchPattern::new(drop, pitch::mora_count(kana));
+        Some(Self {
+            drop,
+            pattern,
+            parts,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -117,6 +221,11 @@ impl From<&crate::jotoba::words::Word> for Word {
         let senses = word.senses.iter().map(|i| Sense::from(i)).collect();
 
         let pitch = word.get_first_pitch().map(|i| i.parts.clone());
+        let accents = word
+            .accents
+            .iter()
+            .filter_map(|drop| Accent::new(word.get_kana(), drop))
+            .collect();
 
         Self {
             common: word.is_common(),
@@ -129,6 +238,8 @@ impl From<&crate::jotoba::words::Word> for Word {
             alt_readings: None,
             audio: word.audio_file_name(),
             pitch,
+            accents,
+            custom: word.is_custom(),
         }
     }
 }
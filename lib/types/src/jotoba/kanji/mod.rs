@@ -5,6 +5,7 @@ use self::{
     radical::DetailedRadical,
     reading::{Reading, ReadingType},
 };
+use super::language::Language;
 use serde::{Deserialize, Serialize};
 use std::{
     char,
@@ -32,7 +33,10 @@ pub struct Kanji {
     pub kun_dicts: Vec<u32>,
     pub on_dicts: Vec<u32>,
     pub similar_kanji: Vec<char>,
+    /// English meanings
     pub meanings: Vec<String>,
+    /// Meanings in other languages
+    pub foreign_meanings: Vec<(Language, Vec<String>)>,
     pub radical: DetailedRadical,
     pub parts: Vec<char>,
 }
@@ -161,6 +165,19 @@ impl Kanji {
         self.get_animation_path(assets_path).exists()
     }
 
+    /// Returns the meanings of the kanji in `language`
+    pub fn meanings_in(&self, language: Language) -> &[String] {
+        if language == Language::English {
+            return &self.meanings;
+        }
+
+        self.foreign_meanings
+            .iter()
+            .find(|i| i.0 == language)
+            .map(|i| i.1.as_slice())
+            .unwrap_or_default()
+    }
+
     /// Returns `true` if kanji has on or kun compounds (or both)
     #[inline]
     pub fn has_compounds(&self) -> bool {
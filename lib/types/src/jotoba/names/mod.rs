@@ -38,6 +38,15 @@ impl Name {
             .unwrap_or(false)
     }
 
+    /// Returns `true` if the name has the given type
+    #[inline]
+    pub fn has_type(&self, name_type: NameType) -> bool {
+        self.name_type
+            .as_ref()
+            .map(|i| i.contains(&name_type))
+            .unwrap_or(false)
+    }
+
     #[inline]
     pub fn get_reading(&self) -> &str {
         self.kanji.as_ref().unwrap_or(&self.kana)
@@ -0,0 +1,536 @@
+use super::{
+    inflection::InflectionPair,
+    part_of_speech::{AdjectiveType, GodanVerbEnding, IrregularVerb, PartOfSpeech, VerbType},
+    Word,
+};
+use jp_utils::furi::segment::SegmentRef;
+use serde::{Deserialize, Serialize};
+
+/// Full conjugation table of a verb or adjective. All forms are encoded furigana strings so
+/// readings of kanji stems are preserved
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ConjugationTable {
+    pub class: ConjugationClass,
+    pub non_past: Politeness,
+    pub past: Politeness,
+    pub te_form: InflectionPair,
+    pub conditional_ba: InflectionPair,
+    pub conditional_tara: InflectionPair,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volitional: Option<Volitional>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imperative: Option<InflectionPair>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub potential: Option<InflectionPair>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passive: Option<InflectionPair>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub causative: Option<InflectionPair>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub causative_passive: Option<InflectionPair>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desiderative: Option<InflectionPair>,
+}
+
+/// Plain and polite variant of a form
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Politeness {
+    pub plain: InflectionPair,
+    pub polite: InflectionPair,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Volitional {
+    pub plain: String,
+    pub polite: String,
+}
+
+/// Conjugation class a table was built for
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConjugationClass {
+    Godan,
+    Ichidan,
+    Suru,
+    /// Verbs like 愛する which partially conjugate like godan verbs ending in す
+    SuruSpecial,
+    SuruNoun,
+    Kuru,
+    IAdjective,
+    NaAdjective,
+}
+
+/// Godan endings with their a, i, e and o stem as well as their te and ta form
+const GODAN: &[[&str; 7]] = &[
+    ["う", "わ", "い", "え", "お", "って", "った"],
+    ["く", "か", "き", "け", "こ", "いて", "いた"],
+    ["ぐ", "が", "ぎ", "げ", "ご", "いで", "いだ"],
+    ["す", "さ", "し", "せ", "そ", "して", "した"],
+    ["つ", "た", "ち", "て", "と", "って", "った"],
+    ["ぬ", "な", "に", "ね", "の", "んで", "んだ"],
+    ["ぶ", "ば", "び", "べ", "ぼ", "んで", "んだ"],
+    ["む", "ま", "み", "め", "も", "んで", "んだ"],
+    ["る", "ら", "り", "れ", "ろ", "って", "った"],
+];
+
+/// Builds the conjugation table of `word` based on its part of speech. Returns `None` if the word
+/// can't be conjugated
+pub fn of_word(word: &Word) -> Option<ConjugationTable> {
+    let mut suru_noun = false;
+
+    for pos in word.get_pos() {
+        let table = match pos {
+            PartOfSpeech::Verb(VerbType::Godan(ending)) => godan(word, *ending),
+            PartOfSpeech::Verb(VerbType::Ichidan) => ichidan(word, false),
+            PartOfSpeech::Verb(VerbType::IchidanKureru) => ichidan(word, true),
+            PartOfSpeech::Verb(VerbType::Kuru) => kuru(word),
+            PartOfSpeech::Verb(VerbType::Irregular(IrregularVerb::Suru)) => {
+                let prefix = strip(word, "する")?;
+                Some(suru(&prefix, ConjugationClass::Suru))
+            }
+            PartOfSpeech::Verb(VerbType::Irregular(IrregularVerb::SuruSpecial)) => {
+                let prefix = strip(word, "する")?;
+                Some(suru_special(&prefix))
+            }
+            PartOfSpeech::Verb(VerbType::Irregular(IrregularVerb::NounOrAuxSuru)) => {
+                suru_noun = true;
+                None
+            }
+            PartOfSpeech::Adjective(AdjectiveType::Keiyoushi) => i_adjective(word, false),
+            PartOfSpeech::Adjective(AdjectiveType::KeiyoushiYoiIi) => i_adjective(word, true),
+            PartOfSpeech::Adjective(AdjectiveType::Na) => Some(na_adjective(&full(word))),
+            _ => None,
+        };
+
+        if table.is_some() {
+            return table;
+        }
+    }
+
+    // Nouns taking する are only conjugated if they have no other conjugatable part of speech
+    suru_noun.then(|| suru(&full(word), ConjugationClass::SuruNoun))
+}
+
+/// Stems of a verb all other forms get built from
+struct Bases {
+    dict: String,
+    /// Stem followed by ない
+    negative: String,
+    /// Stem followed by ます
+    masu: String,
+    te: String,
+    ta: String,
+    volitional: String,
+    imperative: String,
+    ba: String,
+    /// Forms conjugating like ichidan verbs
+    potential: String,
+    passive: String,
+    causative: String,
+    causative_passive: String,
+}
+
+fn godan(word: &Word, ending: GodanVerbEnding) -> Option<ConjugationTable> {
+    let kana = &word.reading.kana.reading;
+    let last = kana.chars().last()?.to_string();
+    let row = GODAN.iter().find(|i| i[0] == last)?;
+    let stem = strip(word, &last)?;
+
+    let (mut te, mut ta) = (format!("{stem}{}", row[5]), format!("{stem}{}", row[6]));
+    let mut masu = format!("{stem}{}", row[2]);
+    let mut imperative = format!("{stem}{}", row[3]);
+    let mut negative = format!("{stem}{}", row[1]);
+
+    match ending {
+        GodanVerbEnding::IkuYuku => {
+            te = format!("{stem}って");
+            ta = format!("{stem}った");
+        }
+        GodanVerbEnding::USpecial => {
+            te = format!("{stem}うて");
+            ta = format!("{stem}うた");
+        }
+        GodanVerbEnding::Aru => {
+            masu = format!("{stem}い");
+            imperative = format!("{stem}い");
+        }
+        // ある has ない as negative
+        GodanVerbEnding::RuIrreg => {
+            negative = kana.strip_suffix("ある").unwrap_or_default().to_string();
+        }
+        _ => (),
+    }
+
+    let bases = Bases {
+        dict: full(word),
+        negative,
+        masu,
+        te,
+        ta,
+        volitional: format!("{stem}{}う", row[4]),
+        imperative,
+        ba: format!("{stem}{}ば", row[3]),
+        potential: format!("{stem}{}る", row[3]),
+        passive: format!("{stem}{}れる", row[1]),
+        causative: format!("{stem}{}せる", row[1]),
+        causative_passive: format!("{stem}{}せられる", row[1]),
+    };
+
+    Some(verb(ConjugationClass::Godan, bases))
+}
+
+fn ichidan(word: &Word, kureru: bool) -> Option<ConjugationTable> {
+    let stem = strip(word, "る")?;
+
+    let imperative = if kureru {
+        stem.clone()
+    } else {
+        format!("{stem}ろ")
+    };
+
+    let bases = Bases {
+        dict: full(word),
+        negative: stem.clone(),
+        masu: stem.clone(),
+        te: format!("{stem}て"),
+        ta: format!("{stem}た"),
+        volitional: format!("{stem}よう"),
+        imperative,
+        ba: format!("{stem}れば"),
+        potential: format!("{stem}られる"),
+        passive: format!("{stem}られる"),
+        causative: format!("{stem}させる"),
+        causative_passive: format!("{stem}させられる"),
+    };
+
+    Some(verb(ConjugationClass::Ichidan, bases))
+}
+
+fn kuru(word: &Word) -> Option<ConjugationTable> {
+    let full = full(word);
+    let furi_ending = format!("{}る", SegmentRef::new_kanji("来", &["く"]).encode());
+
+    let (prefix, kanji) = if let Some(prefix) = full.strip_suffix(&furi_ending) {
+        (prefix.to_string(), true)
+    } else if let Some(prefix) = full.strip_suffix("来る") {
+        (prefix.to_string(), true)
+    } else {
+        (strip(word, "くる")?, false)
+    };
+
+    // The reading of 来 changes depending on the form
+    let ku = |reading: &str, rest: &str| {
+        let stem = if kanji {
+            SegmentRef::new_kanji("来", &[reading]).encode()
+        } else {
+            reading.to_string()
+        };
+        format!("{prefix}{stem}{rest}")
+    };
+
+    let bases = Bases {
+        dict: ku("く", "る"),
+        negative: ku("こ", ""),
+        masu: ku("き", ""),
+        te: ku("き", "て"),
+        ta: ku("き", "た"),
+        volitional: ku("こ", "よう"),
+        imperative: ku("こ", "い"),
+        ba: ku("く", "れば"),
+        potential: ku("こ", "られる"),
+        passive: ku("こ", "られる"),
+        causative: ku("こ", "させる"),
+        causative_passive: ku("こ", "させられる"),
+    };
+
+    Some(verb(ConjugationClass::Kuru, bases))
+}
+
+/// Builds the table of a suru verb. `prefix` is the part in front of する
+fn suru(prefix: &str, class: ConjugationClass) -> ConjugationTable {
+    let bases = Bases {
+        dict: format!("{prefix}する"),
+        negative: format!("{prefix}し"),
+        masu: format!("{prefix}し"),
+        te: format!("{prefix}して"),
+        ta: format!("{prefix}した"),
+        volitional: format!("{prefix}しよう"),
+        imperative: format!("{prefix}しろ"),
+        ba: format!("{prefix}すれば"),
+        potential: format!("{prefix}できる"),
+        passive: format!("{prefix}される"),
+        causative: format!("{prefix}させる"),
+        causative_passive: format!("{prefix}させられる"),
+    };
+
+    verb(class, bases)
+}
+
+/// Builds the table of a verb like 愛する. Forms based on the negative stem, as well as the
+/// volitional, imperative, potential and passive forms are built like for godan verbs ending in す
+fn suru_special(prefix: &str) -> ConjugationTable {
+    let bases = Bases {
+        dict: format!("{prefix}する"),
+        negative: format!("{prefix}さ"),
+        masu: format!("{prefix}し"),
+        te: format!("{prefix}して"),
+        ta: format!("{prefix}した"),
+        volitional: format!("{prefix}そう"),
+        imperative: format!("{prefix}せ"),
+        ba: format!("{prefix}すれば"),
+        potential: format!("{prefix}せる"),
+        passive: format!("{prefix}される"),
+        causative: format!("{prefix}させる"),
+        causative_passive: format!("{prefix}させられる"),
+    };
+
+    verb(ConjugationClass::SuruSpecial, bases)
+}
+
+fn verb(class: ConjugationClass, b: Bases) -> ConjugationTable {
+    let neg = &b.negative;
+    let masu = &b.masu;
+
+    ConjugationTable {
+        class,
+        non_past: Politeness {
+            plain: pair(&b.dict, format!("{neg}ない")),
+            polite: pair(format!("{masu}ます"), format!("{masu}ません")),
+        },
+        past: Politeness {
+            plain: pair(&b.ta, format!("{neg}なかった")),
+            polite: pair(format!("{masu}ました"), format!("{masu}ませんでした")),
+        },
+        te_form: pair(&b.te, format!("{neg}なくて")),
+        conditional_ba: pair(&b.ba, format!("{neg}なければ")),
+        conditional_tara: pair(format!("{}ら", b.ta), format!("{neg}なかったら")),
+        volitional: Some(Volitional {
+            plain: b.volitional.clone(),
+            polite: format!("{masu}ましょう"),
+        }),
+        imperative: Some(pair(&b.imperative, format!("{}な", b.dict))),
+        potential: Some(ichidan_pair(&b.potential)),
+        passive: Some(ichidan_pair(&b.passive)),
+        causative: Some(ichidan_pair(&b.causative)),
+        causative_passive: Some(ichidan_pair(&b.causative_passive)),
+        desiderative: Some(pair(format!("{masu}たい"), format!("{masu}たくない"))),
+    }
+}
+
+fn i_adjective(word: &Word, yoi_ii: bool) -> Option<ConjugationTable> {
+    let mut stem = strip(word, "い")?;
+
+    // いい conjugates like よい. If written in kanji (良い) only the reading of the kanji changes
+    if yoi_ii {
+        if let Some(prefix) = stem.strip_suffix("|い]") {
+            stem = format!("{prefix}|よ]");
+        } else if let Some(prefix) = stem.strip_suffix('い') {
+            stem = format!("{prefix}よ");
+        }
+    }
+
+    let dict = full(word);
+
+    Some(ConjugationTable {
+        class: ConjugationClass::IAdjective,
+        non_past: Politeness {
+            plain: pair(&dict, format!("{stem}くない")),
+            polite: pair(format!("{dict}です"), format!("{stem}くないです")),
+        },
+        past: Politeness {
+            plain: pair(format!("{stem}かった"), format!("{stem}くなかった")),
+            polite: pair(format!("{stem}かったです"), format!("{stem}くなかったです")),
+        },
+        te_form: pair(format!("{stem}くて"), format!("{stem}くなくて")),
+        conditional_ba: pair(format!("{stem}ければ"), format!("{stem}くなければ")),
+        conditional_tara: pair(format!("{stem}かったら"), format!("{stem}くなかったら")),
+        volitional: None,
+        imperative: None,
+        potential: None,
+        passive: None,
+        causative: None,
+        causative_passive: None,
+        desiderative: None,
+    })
+}
+
+fn na_adjective(stem: &str) -> ConjugationTable {
+    ConjugationTable {
+        class: ConjugationClass::NaAdjective,
+        non_past: Politeness {
+            plain: pair(format!("{stem}だ"), format!("{stem}ではない")),
+            polite: pair(format!("{stem}です"), format!("{stem}ではありません")),
+        },
+        past: Politeness {
+            plain: pair(format!("{stem}だった"), format!("{stem}ではなかった")),
+            polite: pair(
+                format!("{stem}でした"),
+                format!("{stem}ではありませんでした"),
+            ),
+        },
+        te_form: pair(format!("{stem}で"), format!("{stem}ではなくて")),
+        conditional_ba: pair(format!("{stem}であれば"), format!("{stem}でなければ")),
+        conditional_tara: pair(format!("{stem}だったら"), format!("{stem}ではなかったら")),
+        volitional: None,
+        imperative: None,
+        potential: None,
+        passive: None,
+        causative: None,
+        causative_passive: None,
+        desiderative: None,
+    }
+}
+
+#[inline]
+fn pair<P: Into<String>, N: Into<String>>(positive: P, negative: N) -> InflectionPair {
+    InflectionPair {
+        positive: positive.into(),
+        negative: negative.into(),
+    }
+}
+
+/// Positive and negative form of a form conjugating like an ichidan verb
+#[inline]
+fn ichidan_pair(form: &str) -> InflectionPair {
+    let stem = form.strip_suffix('る').unwrap_or(form);
+    pair(form, format!("{stem}ない"))
+}
+
+/// Returns the encoded furigana of the word or its kana reading if it has no kanji
+fn full(word: &Word) -> String {
+    match (&word.furigana, &word.reading.kanji) {
+        (Some(furi), Some(_)) => furi.clone(),
+        (None, Some(kanji)) => kanji.reading.clone(),
+        _ => word.reading.kana.reading.clone(),
+    }
+}
+
+/// Returns the encoded furigana of `word` without the kana `ending`. Falls back to the kana
+/// reading if the ending is part of a kanji block
+fn strip(word: &Word, ending: &str) -> Option<String> {
+    let full = full(word);
+
+    // Only strip kana following the last kanji block
+    let tail_start = full.rfind(']').map(|i| i + 1).unwrap_or(0);
+    if full[tail_start..].ends_with(ending) {
+        return Some(full[..full.len() - ending.len()].to_string());
+    }
+
+    let kana = &word.reading.kana.reading;
+    kana.strip_suffix(ending).map(|i| i.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::jotoba::words::{reading::Reading, sense::Sense, Dict};
+
+    fn word(kana: &str, kanji: Option<(&str, &str)>, pos: PartOfSpeech) -> Word {
+        let dict = |reading: &str| Dict {
+            reading: reading.to_string(),
+            ..Dict::default()
+        };
+        Word {
+            reading: Reading {
+                kana: dict(kana),
+                kanji: kanji.map(|i| dict(i.0)),
+                ..Reading::default()
+            },
+            furigana: kanji.map(|i| i.1.to_string()),
+            senses: vec![Sense {
+                part_of_speech: vec![pos],
+                ..Sense::default()
+            }],
+            ..Word::default()
+        }
+    }
+
+    #[test]
+    fn test_godan() {
+        let pos = PartOfSpeech::Verb(VerbType::Godan(GodanVerbEnding::Ku));
+        let table = of_word(&word("かく", Some(("書く", "[書|か]く")), pos)).unwrap();
+        assert_eq!(table.class, ConjugationClass::Godan);
+        assert_eq!(table.non_past.plain.negative, "[書|か]かない");
+        assert_eq!(table.past.plain.positive, "[書|か]いた");
+        assert_eq!(table.past.polite.negative, "[書|か]きませんでした");
+        assert_eq!(table.volitional.unwrap().plain, "[書|か]こう");
+        assert_eq!(table.potential.unwrap().negative, "[書|か]けない");
+        assert_eq!(
+            table.causative_passive.unwrap().positive,
+            "[書|か]かせられる"
+        );
+    }
+
+    #[test]
+    fn test_iku() {
+        let pos = PartOfSpeech::Verb(VerbType::Godan(GodanVerbEnding::IkuYuku));
+        let table = of_word(&word("いく", Some(("行く", "[行|い]く")), pos)).unwrap();
+        assert_eq!(table.te_form.positive, "[行|い]って");
+        assert_eq!(table.conditional_tara.positive, "[行|い]ったら");
+    }
+
+    #[test]
+    fn test_ichidan() {
+        let pos = PartOfSpeech::Verb(VerbType::Ichidan);
+        let table = of_word(&word("たべる", Some(("食べる", "[食|た]べる")), pos)).unwrap();
+        assert_eq!(table.non_past.polite.positive, "[食|た]べます");
+        assert_eq!(table.imperative.unwrap().positive, "[食|た]べろ");
+        assert_eq!(table.desiderative.unwrap().negative, "[食|た]べたくない");
+    }
+
+    #[test]
+    fn test_kuru() {
+        let pos = PartOfSpeech::Verb(VerbType::Kuru);
+        let table = of_word(&word("くる", Some(("来る", "[来|く]る")), pos)).unwrap();
+        assert_eq!(table.non_past.plain.negative, "[来|こ]ない");
+        assert_eq!(table.past.plain.positive, "[来|き]た");
+    }
+
+    #[test]
+    fn test_suru_special() {
+        let pos = PartOfSpeech::Verb(VerbType::Irregular(IrregularVerb::SuruSpecial));
+        let table = of_word(&word("あいする", Some(("愛する", "[愛|あい]する")), pos)).unwrap();
+        assert_eq!(table.class, ConjugationClass::SuruSpecial);
+        assert_eq!(table.non_past.plain.negative, "[愛|あい]さない");
+        assert_eq!(table.non_past.polite.positive, "[愛|あい]します");
+        assert_eq!(table.past.plain.positive, "[愛|あい]した");
+        assert_eq!(table.volitional.unwrap().plain, "[愛|あい]そう");
+        assert_eq!(table.potential.unwrap().positive, "[愛|あい]せる");
+        assert_eq!(table.passive.unwrap().negative, "[愛|あい]されない");
+    }
+
+    #[test]
+    fn test_suru_noun() {
+        let pos = PartOfSpeech::Verb(VerbType::Irregular(IrregularVerb::NounOrAuxSuru));
+        let table = of_word(&word(
+            "べんきょう",
+            Some(("勉強", "[勉強|べんきょう]")),
+            pos,
+        ))
+        .unwrap();
+        assert_eq!(table.class, ConjugationClass::SuruNoun);
+        assert_eq!(table.past.plain.positive, "[勉強|べんきょう]した");
+        assert_eq!(table.potential.unwrap().positive, "[勉強|べんきょう]できる");
+    }
+
+    #[test]
+    fn test_adjectives() {
+        let pos = PartOfSpeech::Adjective(AdjectiveType::Keiyoushi);
+        let table = of_word(&word("たかい", Some(("高い", "[高|たか]い")), pos)).unwrap();
+        assert_eq!(table.past.plain.negative, "[高|たか]くなかった");
+
+        let pos = PartOfSpeech::Adjective(AdjectiveType::KeiyoushiYoiIi);
+        let table = of_word(&word("いい", None, pos)).unwrap();
+        assert_eq!(table.non_past.plain.positive, "いい");
+        assert_eq!(table.past.plain.positive, "よかった");
+
+        let table = of_word(&word("いい", Some(("良い", "[良|い]い")), pos)).unwrap();
+        assert_eq!(table.non_past.plain.positive, "[良|い]い");
+        assert_eq!(table.non_past.plain.negative, "[良|よ]くない");
+        assert_eq!(table.past.plain.positive, "[良|よ]かった");
+
+        let pos = PartOfSpeech::Adjective(AdjectiveType::Na);
+        let table = of_word(&word("しずか", Some(("静か", "[静|しず]か")), pos)).unwrap();
+        assert_eq!(table.non_past.polite.negative, "[静|しず]かではありません");
+    }
+}
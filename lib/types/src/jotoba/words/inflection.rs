@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use jp_inflections::{Verb, VerbType, WordForm};
 
 /// A single Inflection
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Inflection {
     Negative,
     Polite,
@@ -28,6 +28,12 @@ pub enum Inflection {
     Tara,
     Tari,
     Ba,
+    /// ～ざるを得ない
+    ZaruWoEnai,
+    /// ～なければならない
+    Obligation,
+    /// ～っぱなし
+    Ppanashi,
 }
 
 #[cfg(feature = "jotoba_intern")]
@@ -56,6 +62,9 @@ impl localization::traits::Translatable for Inflection {
             Inflection::Tara => "Tara",
             Inflection::Tari => "Tari",
             Inflection::Ba => "Ba",
+            Inflection::ZaruWoEnai => "ZaruWoEnai",
+            Inflection::Obligation => "Obligation",
+            Inflection::Ppanashi => "Ppanashi",
         }
     }
 
@@ -68,6 +77,36 @@ impl localization::traits::Translatable for Inflection {
     }
 }
 
+#[cfg(feature = "jotoba_intern")]
+impl From<japanese::deinflect::Reason> for Inflection {
+    fn from(reason: japanese::deinflect::Reason) -> Self {
+        use japanese::deinflect::Reason;
+        match reason {
+            Reason::Negative => Inflection::Negative,
+            Reason::Polite => Inflection::Polite,
+            Reason::Past => Inflection::Past,
+            Reason::TeForm => Inflection::TeForm,
+            Reason::Potential => Inflection::Potential,
+            Reason::Passive => Inflection::Passive,
+            Reason::Causative => Inflection::Causative,
+            Reason::CausativePassive => Inflection::CausativePassive,
+            Reason::PotentialOrPassive => Inflection::PotentialOrPassive,
+            Reason::Imperative => Inflection::Imperative,
+            Reason::Tai => Inflection::Tai,
+            Reason::TeIru => Inflection::TeIru,
+            Reason::TeAru => Inflection::TeAru,
+            Reason::TeMiru => Inflection::TeMiru,
+            Reason::TeShimau => Inflection::TeShimau,
+            Reason::Chau => Inflection::Chau,
+            Reason::TeOku => Inflection::TeOku,
+            Reason::Toku => Inflection::Toku,
+            Reason::Tara => Inflection::Tara,
+            Reason::Tari => Inflection::Tari,
+            Reason::Ba => Inflection::Ba,
+        }
+    }
+}
+
 /// A set of different inflections which will be displayed for vebs
 #[derive(Serialize, Deserialize)]
 pub struct Inflections {
@@ -87,7 +126,7 @@ pub struct Inflections {
     pub imperative: InflectionPair,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct InflectionPair {
     #[serde(rename = "p")]
     pub positive: String,
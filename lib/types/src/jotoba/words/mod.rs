@@ -1,3 +1,4 @@
+pub mod conjugation;
 pub mod dialect;
 pub mod dict;
 pub mod field;
@@ -23,7 +24,7 @@ use jp_utils::{
 };
 use misc::Misc;
 use part_of_speech::{PartOfSpeech, PosSimple};
-use pitch::{raw_data::PitchValues, Pitch};
+use pitch::{raw_data::PitchValues, Pitch, PitchPattern};
 use reading::{Reading, ReadingIter};
 use sense::{Sense, SenseGlossIter};
 use serde::{Deserialize, Serialize};
@@ -33,6 +34,10 @@ use std::{
     path::Path,
 };
 
+/// First sequence ID reserved for words of custom dictionaries. JMdict sequence IDs are always
+/// below this value
+pub const CUSTOM_SEQ_START: u32 = 20_000_000;
+
 /// A single word in Jotobas word search
 #[derive(Clone, Default, Serialize, Deserialize, Eq)]
 pub struct Word {
@@ -56,6 +61,12 @@ impl Word {
         self.common
     }
 
+    /// Returns `true` if the word comes from a custom dictionary
+    #[inline]
+    pub fn is_custom(&self) -> bool {
+        self.sequence >= CUSTOM_SEQ_START
+    }
+
     /// Returns the jlpt level of a word. `None` if a word doesn't have a JLPT lvl assigned
     #[inline]
     pub fn get_jlpt_lvl(&self) -> Option<u8> {
@@ -269,6 +280,26 @@ impl Word {
             .collect()
     }
 
+    /// Returns the accent pattern of each pitch of the word
+    pub fn pitch_patterns(&self) -> impl Iterator<Item = PitchPattern> + '_ {
+        let mora = pitch::mora_count(self.get_kana());
+        self.accents
+            .iter()
+            .map(move |drop| PitchPattern::new(drop, mora))
+    }
+
+    /// Returns `true` if one of the words pitches has the given accent pattern
+    #[inline]
+    pub fn has_pitch_pattern(&self, pattern: PitchPattern) -> bool {
+        self.pitch_patterns().any(|i| i == pattern)
+    }
+
+    /// Returns `true` if one of the words pitches drops after `drop` morae
+    #[inline]
+    pub fn has_pitch_drop(&self, drop: u8) -> bool {
+        self.accents.iter().any(|i| i == drop)
+    }
+
     /// Returns a renderable vec of accents with kana characters
     #[inline]
     pub fn get_first_pitch(&self) -> Option<Pitch> {
@@ -374,6 +405,19 @@ impl Word {
     pub fn get_inflections(&self) -> Option<inflection::Inflections> {
         inflection::of_word(self)
     }
+
+    /// Returns the full [`ConjugationTable`] of the word if it's a verb or adjective
+    ///
+    /// [`ConjugationTable`]: conjugation::ConjugationTable
+    #[inline]
+    pub fn get_conjugation_table(&self) -> Option<conjugation::ConjugationTable> {
+        conjugation::of_word(self)
+    }
+
+    /// Returns `true` if the word can be the dictionary form of `deinflection`
+    pub fn matches_deinflection(&self, deinflection: &japanese::deinflect::Deinflection) -> bool {
+        self.get_pos().any(|i| deinflection.matches(i.word_class()))
+    }
 }
 
 /// Removes all senses which ain't in the provided language or english in case `show_english` is
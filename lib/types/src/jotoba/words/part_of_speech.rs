@@ -226,6 +226,27 @@ impl PartOfSpeech {
     }
 }
 
+#[cfg(feature = "jotoba_intern")]
+impl PartOfSpeech {
+    /// Returns the word class used to validate deinflections
+    pub fn word_class(&self) -> japanese::deinflect::WordClass {
+        use japanese::deinflect::WordClass;
+
+        match self {
+            PartOfSpeech::Verb(VerbType::Ichidan | VerbType::IchidanKureru) => WordClass::V1,
+            PartOfSpeech::Verb(VerbType::Godan(_)) => WordClass::V5,
+            PartOfSpeech::Verb(VerbType::Irregular(
+                IrregularVerb::Suru | IrregularVerb::SuruSpecial | IrregularVerb::NounOrAuxSuru,
+            )) => WordClass::VS,
+            PartOfSpeech::Verb(VerbType::Kuru) => WordClass::VK,
+            PartOfSpeech::Adjective(AdjectiveType::Keiyoushi | AdjectiveType::KeiyoushiYoiIi) => {
+                WordClass::ADJ_I
+            }
+            _ => WordClass::NONE,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Serialize, Deserialize, Hash)]
 #[repr(u8)]
 pub enum VerbType {
@@ -110,6 +110,38 @@ impl PitchPart {
     }
 }
 
+/// Accent pattern of a word determined by the position of its pitch drop
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum PitchPattern {
+    /// No drop
+    Heiban,
+    /// Drop after the first mora
+    Atamadaka,
+    /// Drop within the word
+    Nakadaka,
+    /// Drop after the last mora
+    Odaka,
+}
+
+impl PitchPattern {
+    /// Returns the pattern of a word with `mora` morae whose pitch drops after `drop` morae
+    pub fn new(drop: u8, mora: usize) -> Self {
+        match drop {
+            0 => Self::Heiban,
+            1 => Self::Atamadaka,
+            _ if drop as usize >= mora => Self::Odaka,
+            _ => Self::Nakadaka,
+        }
+    }
+}
+
+/// Returns the amount of morae of `kana` the same way they get counted for pitch drops
+#[inline]
+pub fn mora_count(kana: &str) -> usize {
+    split_kana(kana).count()
+}
+
 /// Returns an iterator over all kana characters. The reason for Item to be &str is that 'きゅう'
 /// gets split up into ["きゅ", "う"] which can't be represented with only one char
 pub fn split_kana(inp: &str) -> impl Iterator<Item = &str> {
@@ -153,6 +185,16 @@ mod test {
         assert_eq!(out, vec!["こ", "れ", "が", "す", "き"]);
     }
 
+    #[test]
+    fn test_pitch_pattern() {
+        assert_eq!(PitchPattern::new(0, 3), PitchPattern::Heiban);
+        assert_eq!(PitchPattern::new(1, 3), PitchPattern::Atamadaka);
+        assert_eq!(PitchPattern::new(1, 1), PitchPattern::Atamadaka);
+        assert_eq!(PitchPattern::new(2, 3), PitchPattern::Nakadaka);
+        assert_eq!(PitchPattern::new(3, 3), PitchPattern::Odaka);
+        assert_eq!(mora_count("きょうかしょ"), 4);
+    }
+
     #[test]
     fn test_split_kana2() {
         let inp = "";
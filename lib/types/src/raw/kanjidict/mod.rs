@@ -1,3 +1,5 @@
+use crate::jotoba::language::Language;
+
 /// An kanji character. Represents one Kanji
 #[derive(Default, Clone, Debug)]
 pub struct Character {
@@ -9,6 +11,7 @@ pub struct Character {
     pub korean_hangul: Vec<String>,
     pub vietnamese: Vec<String>,
     pub meaning: Vec<String>,
+    pub foreign_meanings: Vec<(Language, Vec<String>)>,
     pub grade: Option<u8>,
     pub stroke_count: u8,
     pub variant: Vec<String>,
@@ -0,0 +1,336 @@
+//! Cells for long living data like dictionaries and indexes which can be replaced at runtime.
+//!
+//! Each value is owned by an [`Arc`] and handed out as `'static` reference, since search results
+//! and the code producing them rely on `'static` data. Such a reference is only valid until the
+//! value gets replaced. Therefore everything that might run while data gets replaced (eg. requests)
+//! has to hold a [`ReadPin`] for as long as it uses references of a cell. Replacing values is only
+//! possible within [`swap_all`] which waits for all pins to be released. This way all cells
+//! swapped together get published as a single generation and replaced values can be freed safely.
+//!
+//! Work running on other threads than the one holding a pin (eg. blocking tasks) has to get a
+//! clone of that pin moved in. Taking a new pin instead would wait for a pending swap which in
+//! turn waits for the held pin.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    ptr,
+    sync::{
+        atomic::{AtomicPtr, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+
+/// Gate between readers holding a [`ReadPin`] and [`swap_all`]
+static GATE: Gate = Gate {
+    state: Mutex::new(GateState {
+        readers: 0,
+        swapping: false,
+        wakers: vec![],
+    }),
+    cond: Condvar::new(),
+};
+
+struct Gate {
+    state: Mutex<GateState>,
+    cond: Condvar,
+}
+
+struct GateState {
+    readers: usize,
+    /// `true` from the moment a swap is requested until it is done. No new pins are handed out
+    /// during that time so the swap can't starve
+    swapping: bool,
+    /// Tasks waiting in [`pin_async`] for the swap to finish
+    wakers: Vec<Waker>,
+}
+
+/// Keeps values of all cells from being replaced while held. Clones keep the same generation
+/// pinned and can be moved to other threads
+pub struct ReadPin {
+    _private: (),
+}
+
+impl ReadPin {
+    /// Registers a new reader. The caller has to make sure no swap is running
+    fn new(state: &mut GateState) -> Self {
+        state.readers += 1;
+        Self { _private: () }
+    }
+}
+
+impl Clone for ReadPin {
+    /// Pins the same generation again without waiting for a pending swap, since the swap can't
+    /// run while `self` is held
+    fn clone(&self) -> Self {
+        Self::new(&mut GATE.state.lock().unwrap())
+    }
+}
+
+impl Drop for ReadPin {
+    fn drop(&mut self) {
+        let mut state = GATE.state.lock().unwrap();
+        state.readers -= 1;
+        if state.readers == 0 {
+            GATE.cond.notify_all();
+        }
+    }
+}
+
+/// Pins the current values of all cells. Blocks the thread while a swap is pending or running,
+/// so async code should use [`pin_async`] instead. Must not be called while holding a pin, use a
+/// clone of the held pin instead
+pub fn pin() -> ReadPin {
+    let mut state = GATE.state.lock().unwrap();
+    while state.swapping {
+        state = GATE.cond.wait(state).unwrap();
+    }
+    ReadPin::new(&mut state)
+}
+
+/// Pins the current values of all cells like [`pin`] but waits for a pending or running swap
+/// without blocking the thread
+#[inline]
+pub fn pin_async() -> PinFuture {
+    PinFuture { _private: () }
+}
+
+/// Future returned by [`pin_async`]
+pub struct PinFuture {
+    _private: (),
+}
+
+impl Future for PinFuture {
+    type Output = ReadPin;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = GATE.state.lock().unwrap();
+        if state.swapping {
+            state.wakers.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+        Poll::Ready(ReadPin::new(&mut state))
+    }
+}
+
+/// Runs `swap` once no [`ReadPin`] is held anymore. New pins wait from the moment this gets called
+/// until `swap` returned, so all cells replaced within `swap` get published together. Must not be
+/// called while holding a pin
+pub fn swap_all<R, F: FnOnce() -> R>(swap: F) -> R {
+    let mut state = GATE.state.lock().unwrap();
+    while state.swapping {
+        state = GATE.cond.wait(state).unwrap();
+    }
+    state.swapping = true;
+    while state.readers > 0 {
+        state = GATE.cond.wait(state).unwrap();
+    }
+    drop(state);
+
+    // Lets readers continue even if `swap` panics
+    let _guard = SwapGuard;
+    swap()
+}
+
+struct SwapGuard;
+
+impl Drop for SwapGuard {
+    fn drop(&mut self) {
+        let mut state = GATE.state.lock().unwrap();
+        state.swapping = false;
+        for waker in state.wakers.drain(..) {
+            waker.wake();
+        }
+        GATE.cond.notify_all();
+    }
+}
+
+/// A cell holding a value which can be replaced at runtime. See the module documentation for
+/// how long references to the value stay valid
+pub struct SwapCell<T> {
+    value: Mutex<Option<Arc<T>>>,
+    /// Points to the value owned by `value`. Allows accessing the value without locking
+    ptr: AtomicPtr<T>,
+    cond: Condvar,
+}
+
+impl<T: Send + Sync + 'static> SwapCell<T> {
+    /// Creates a new empty SwapCell
+    pub const fn new() -> Self {
+        Self {
+            value: Mutex::new(None),
+            ptr: AtomicPtr::new(ptr::null_mut()),
+            cond: Condvar::new(),
+        }
+    }
+
+    /// Returns the current value or `None` if no value was set yet
+    #[inline]
+    pub fn get(&self) -> Option<&'static T> {
+        // Safety: The pointer is either null or points to the value owned by `self.value`, which
+        // only gets dropped within `swap_all` while no reader holds a pin
+        unsafe { self.ptr.load(Ordering::Acquire).as_ref() }
+    }
+
+    /// Returns the current value without checking whether the cell was set
+    ///
+    /// # Safety
+    /// The cell must have been set before
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self) -> &'static T {
+        &*self.ptr.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if a value was set
+    #[inline]
+    pub fn is_set(&self) -> bool {
+        !self.ptr.load(Ordering::Acquire).is_null()
+    }
+
+    /// Sets the value if the cell is empty. Returns `false` if there already was a value
+    pub fn set(&self, value: T) -> bool {
+        let mut current = self.value.lock().unwrap();
+        if current.is_some() {
+            return false;
+        }
+        self.publish(&mut current, value);
+        true
+    }
+
+    /// Replaces the current value with `value` and returns the replaced one. All following calls
+    /// to `get` return the new value. Has to be called within [`swap_all`]
+    pub fn swap(&self, value: T) -> Option<Arc<T>> {
+        let mut current = self.value.lock().unwrap();
+        self.publish(&mut current, value)
+    }
+
+    /// Blocks until a value was set and returns it
+    pub fn wait(&self) -> &'static T {
+        let mut current = self.value.lock().unwrap();
+        loop {
+            if let Some(value) = self.get() {
+                return value;
+            }
+            current = self.cond.wait(current).unwrap();
+        }
+    }
+
+    fn publish(&self, current: &mut Option<Arc<T>>, value: T) -> Option<Arc<T>> {
+        let new = Arc::new(value);
+        self.ptr
+            .store(Arc::as_ptr(&new) as *mut T, Ordering::Release);
+        let old = current.replace(new);
+        self.cond.notify_all();
+        old
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for SwapCell<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{sync::atomic::AtomicBool, task::Wake, thread, time::Duration};
+
+    #[test]
+    fn test_set_swap() {
+        let cell: SwapCell<u32> = SwapCell::new();
+        assert!(!cell.is_set());
+        assert_eq!(cell.get(), None);
+
+        assert!(cell.set(1));
+        assert!(!cell.set(2));
+        assert_eq!(cell.get(), Some(&1));
+
+        let old = swap_all(|| cell.swap(3));
+        assert_eq!(cell.get(), Some(&3));
+        assert_eq!(old.as_deref(), Some(&1));
+    }
+
+    #[test]
+    fn test_old_value_dropped() {
+        let cell: SwapCell<Arc<()>> = SwapCell::new();
+        let value = Arc::new(());
+        cell.set(value.clone());
+        assert_eq!(Arc::strong_count(&value), 2);
+
+        drop(swap_all(|| cell.swap(Arc::new(()))));
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_swap_waits_for_pin() {
+        static CELL: SwapCell<u32> = SwapCell::new();
+        CELL.set(1);
+
+        let pin = pin();
+        let handle = thread::spawn(|| swap_all(|| CELL.swap(2)));
+
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(CELL.get(), Some(&1));
+
+        // Clones must not wait for the pending swap and keep the value alive on other threads
+        let clone = pin.clone();
+        drop(pin);
+        let reader = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            let value = *CELL.get().unwrap();
+            drop(clone);
+            value
+        });
+
+        assert_eq!(reader.join().unwrap(), 1);
+        handle.join().unwrap();
+        assert_eq!(CELL.get(), Some(&2));
+    }
+
+    /// Wakes nobody but remembers being woken
+    struct Flag(AtomicBool);
+
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_pending_swap_blocks_new_pins() {
+        static CELL: SwapCell<u32> = SwapCell::new();
+        CELL.set(1);
+
+        let pin = pin();
+        let handle = thread::spawn(|| swap_all(|| CELL.swap(2)));
+        thread::sleep(Duration::from_millis(50));
+
+        // New pins have to wait for the pending swap, otherwise overlapping requests would
+        // starve it
+        let flag = Arc::new(Flag(AtomicBool::new(false)));
+        let waker = Waker::from(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+        let mut fut = pin_async();
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_pending());
+
+        drop(pin);
+        handle.join().unwrap();
+        assert!(flag.0.load(Ordering::SeqCst));
+
+        let Poll::Ready(_pin) = Pin::new(&mut fut).poll(&mut cx) else {
+            panic!("pin not ready after swap");
+        };
+        assert_eq!(CELL.get(), Some(&2));
+    }
+
+    #[test]
+    fn test_wait() {
+        static CELL: SwapCell<u32> = SwapCell::new();
+        let handle = std::thread::spawn(|| *CELL.wait());
+        CELL.set(5);
+        assert_eq!(handle.join().unwrap(), 5);
+    }
+}